@@ -41,51 +41,81 @@ macro_rules! debug_log {
 /// Logs contract initialization in debug mode.
 #[cfg(feature = "debug-log")]
 pub fn log_initialize(env: &Env, admin: &soroban_sdk::Address, usdc_token: &soroban_sdk::Address, fee_bps: u32) {
+    if !logs_enabled(env) {
+        return;
+    }
     soroban_sdk::log!(env, "Initialize: admin={}, usdc_token={}, fee_bps={}", admin, usdc_token, fee_bps);
 }
 
 /// Logs agent registration in debug mode.
 #[cfg(feature = "debug-log")]
 pub fn log_register_agent(env: &Env, agent: &soroban_sdk::Address) {
+    if !logs_enabled(env) {
+        return;
+    }
     soroban_sdk::log!(env, "Register agent: {}", agent);
 }
 
 /// Logs agent removal in debug mode.
 #[cfg(feature = "debug-log")]
 pub fn log_remove_agent(env: &Env, agent: &soroban_sdk::Address) {
+    if !logs_enabled(env) {
+        return;
+    }
     soroban_sdk::log!(env, "Remove agent: {}", agent);
 }
 
 /// Logs fee update in debug mode.
 #[cfg(feature = "debug-log")]
 pub fn log_update_fee(env: &Env, fee_bps: u32) {
+    if !logs_enabled(env) {
+        return;
+    }
     soroban_sdk::log!(env, "Update fee: fee_bps={}", fee_bps);
 }
 
 /// Logs remittance creation in debug mode.
 #[cfg(feature = "debug-log")]
 pub fn log_create_remittance(env: &Env, remittance_id: u64, sender: &soroban_sdk::Address, agent: &soroban_sdk::Address, amount: i128, fee: i128) {
+    if !logs_enabled(env) {
+        return;
+    }
     soroban_sdk::log!(env, "Create remittance: id={}, sender={}, agent={}, amount={}, fee={}", remittance_id, sender, agent, amount, fee);
 }
 
 /// Logs payout confirmation in debug mode.
 #[cfg(feature = "debug-log")]
 pub fn log_confirm_payout(env: &Env, remittance_id: u64, payout_amount: i128) {
+    if !logs_enabled(env) {
+        return;
+    }
     soroban_sdk::log!(env, "Confirm payout: remittance_id={}, payout_amount={}", remittance_id, payout_amount);
 }
 
 /// Logs remittance cancellation in debug mode.
 #[cfg(feature = "debug-log")]
 pub fn log_cancel_remittance(env: &Env, remittance_id: u64) {
+    if !logs_enabled(env) {
+        return;
+    }
     soroban_sdk::log!(env, "Cancel remittance: remittance_id={}", remittance_id);
 }
 
 /// Logs fee withdrawal in debug mode.
 #[cfg(feature = "debug-log")]
 pub fn log_withdraw_fees(env: &Env, to: &soroban_sdk::Address, fees: i128) {
+    if !logs_enabled(env) {
+        return;
+    }
     soroban_sdk::log!(env, "Withdraw fees: to={}, fees={}", to, fees);
 }
 
+/// Debug logs are written only while event verbosity is `Verbose`.
+#[cfg(feature = "debug-log")]
+fn logs_enabled(env: &Env) -> bool {
+    crate::get_event_verbosity(env) == crate::EventVerbosity::Verbose
+}
+
 // Non-feature-gated stubs for compile-time compatibility
 
 /// Logs contract initialization - no-op in release.
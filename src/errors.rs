@@ -33,15 +33,17 @@ pub enum ContractError {
     RemittanceNotFound = 6,
     
     /// Operation not allowed in current remittance status.
-    /// Cause: Confirming/cancelling already completed or cancelled remittance.
+    /// Cause: Confirming/cancelling already completed or cancelled remittance, or releasing
+    /// from an escrow whose milestones have all been released.
     InvalidStatus = 7,
     
     /// Arithmetic operation resulted in overflow.
     /// Cause: Fee calculation or amount operations exceeded i128 limits.
     Overflow = 8,
     
-    /// No accumulated fees available to withdraw.
-    /// Cause: Calling withdraw_fees() when accumulated fees are zero.
+    /// No accumulated fees, rebates or rewards available to withdraw.
+    /// Cause: Calling withdraw_fees(), claim_agent_rebate() or claim_referral_rewards()
+    /// when the balance is zero.
     NoFeesToWithdraw = 9,
     
     /// Address validation failed.
@@ -59,4 +61,160 @@ pub enum ContractError {
     /// Contract is paused. Settlements are temporarily disabled.
     /// Cause: Attempting confirm_payout() while contract is in paused state.
     ContractPaused = 13,
+
+    /// Quote ID does not exist or has already been used.
+    /// Cause: Calling create_remittance_from_quote() with an unknown or consumed quote.
+    QuoteNotFound = 14,
+
+    /// Quote validity window has passed.
+    /// Cause: Calling create_remittance_from_quote() after the quote's expires_at timestamp.
+    QuoteExpired = 15,
+
+    /// Remittance would push the sender's outstanding Pending value past its cap.
+    /// Cause: Creating a remittance while exposure caps are enabled and the cap is reached.
+    ExposureCapExceeded = 16,
+
+    /// Remittance would push total locked value past the contract-wide ceiling.
+    /// Cause: Creating a remittance when max_total_locked has been reached.
+    TvlCapExceeded = 17,
+
+    /// No guardian has been configured.
+    /// Cause: Calling a guardian-approved operation before set_guardian().
+    GuardianNotSet = 19,
+
+    /// Recovery vault is not registered, not yet active, or does not match.
+    /// Cause: Calling emergency_evacuate() before the vault timelock elapses or with another address.
+    InvalidRecoveryVault = 20,
+
+    /// Contract funds were evacuated and it is locked pending post-mortem.
+    /// Cause: Creating remittances or unpausing before resume_after_evacuation().
+    ContractEvacuated = 21,
+
+    /// Requested pause end is not in the future.
+    /// Cause: Calling pause() with a pause_until timestamp at or before the current ledger time.
+    InvalidPauseWindow = 22,
+
+    /// Tag list is too long or repeats a tag.
+    /// Cause: Passing more than MAX_TAGS tags, or the same tag twice, at creation,
+    /// or calling watch() with a new tag once MAX_WATCH_TAGS are registered.
+    InvalidTags = 23,
+
+    /// Caller does not hold the role required for this operation.
+    /// Cause: Calling authorize_remittance() without the Authorizer role, or withdraw_fees() as neither admin nor Treasurer.
+    MissingRole = 24,
+
+    /// Remittance has not been pre-authorized for payout.
+    /// Cause: Calling confirm_payout() while pre-authorization is required and no Authorizer has approved the remittance.
+    RemittanceNotAuthorized = 25,
+
+    /// Sender has not yet confirmed receipt of a dual-confirmation remittance.
+    /// Cause: Calling confirm_payout() before confirm_received() and before the dual-confirmation timeout.
+    SenderConfirmationRequired = 26,
+
+    /// Creation flags contain bits this contract does not understand.
+    /// Cause: Passing a flags value outside SUPPORTED_FLAGS at creation.
+    InvalidFlags = 27,
+
+    /// Sender is still within a rate-limit cooldown or has used up the current window.
+    /// Cause: Creating a remittance before get_cooldown_remaining() or get_window_remaining() reaches zero.
+    RateLimited = 28,
+
+    /// No escrow is linked to the given remittance.
+    /// Cause: Querying or releasing an escrow for a remittance that was never converted.
+    EscrowNotFound = 29,
+
+    /// Milestone schedule is empty, too long, or does not add up to the net payout.
+    /// Cause: Calling convert_to_escrow() with non-positive milestones, more than MAX_MILESTONES, or a total other than amount minus fee.
+    InvalidMilestones = 30,
+
+    /// No admin recovery address is configured.
+    /// Cause: Calling claim_admin() or check_admin_recovery() without a recovery address.
+    AdminRecoveryNotSet = 32,
+
+    /// Admin has been active too recently for recovery.
+    /// Cause: Calling claim_admin() before the inactivity deadline.
+    AdminStillActive = 33,
+
+    /// Currency code is not three ASCII letters.
+    /// Cause: Passing a currency that is not an ISO 4217-shaped code, e.g. "US" or "US1".
+    InvalidCurrency = 34,
+
+    /// Country code is not two ASCII letters.
+    /// Cause: Passing a country that is not an ISO 3166-1 alpha-2-shaped code, e.g. "NGA".
+    InvalidCountry = 35,
+
+    /// Batch is empty or larger than MAX_BATCH_SIZE.
+    /// Cause: Calling batch_settle_with_netting() with no remittances or too many.
+    InvalidBatch = 38,
+
+    /// Settlement batch does not exist.
+    /// Cause: Querying a batch ID that was never recorded.
+    BatchNotFound = 39,
+
+    /// Agent has not pre-approved settlement of a batch entry.
+    /// Cause: Batch-settling a remittance without a prior approve_settlement() while agent approval is required.
+    AgentApprovalMissing = 40,
+
+    /// Refunds to an address other than the sender are turned off.
+    /// Cause: Calling cancel_remittance_to() while alternate refunds are disabled.
+    AlternateRefundDisabled = 41,
+
+    /// Contract token balance cannot cover the settlement.
+    /// Cause: Settling while the contract holds less than the remittances' amount (payout plus fee).
+    InsufficientContractLiquidity = 42,
+
+    /// Netting batch submitted outside the allowed settlement windows.
+    /// Cause: Calling batch_settle_with_netting() outside every configured window without the WindowOverride role.
+    OutsideSettlementWindow = 43,
+
+    /// Settlement window configuration is malformed.
+    /// Cause: No windows, more than MAX_SETTLEMENT_WINDOWS, a bound past the end of the day, or an empty window.
+    InvalidSettlementWindow = 44,
+
+    /// Agent float is smaller than the requested withdrawal.
+    /// Cause: Calling withdraw_float() for more than get_agent_float().
+    InsufficientAgentFloat = 45,
+
+    /// Corridor is closed to new remittances.
+    /// Cause: Creating a remittance or quote on a corridor switched off with set_corridor_enabled(),
+    /// or in a currency or to a country missing from the registry while it is enforced.
+    CorridorDisabled = 46,
+
+    /// Fee strategy contract could not compute a valid fee.
+    /// Cause: The configured strategy's compute_fees() trapped, returned an error or an unexpected type,
+    /// or returned a negative fee or one above the strategy's max_fee_bps of the amount.
+    FeeStrategyFailed = 47,
+
+    /// Address does not implement the token interface.
+    /// Cause: Whitelisting an address whose name(), symbol() or decimals() trap or return unexpected types.
+    NotATokenContract = 50,
+
+    /// A token transfer into or out of the contract failed.
+    /// Cause: Short allowance or balance, a frozen account, or the token rejecting the transfer.
+    /// The stage and reason are only in the failed call's `token` / `xfer_fail` diagnostic event.
+    TokenTransferFailed = 51,
+
+    /// Remittance expires sooner than its corridor allows.
+    /// Cause: Creating a remittance whose expiry is less than the corridor's min_seconds away.
+    ExpiryTooShort = 52,
+
+    /// Remittance expires later than its corridor allows.
+    /// Cause: Creating a remittance whose expiry is more than the corridor's max_seconds away,
+    /// or without an expiry while the corridor sets a maximum.
+    ExpiryTooLong = 53,
+
+    /// Agent session key has expired.
+    /// Cause: Settling with a session key after its expiry, or registering one whose expiry has passed.
+    SessionKeyExpired = 54,
+
+    /// Payout would take the agent session key past its cap.
+    /// Cause: Settling with a session key whose total payouts would exceed the cap it was registered with.
+    SessionCapExceeded = 55,
+
+    /// Queued timelock update does not exist.
+    /// Cause: Executing or cancelling an update ID that was never queued, or was already executed or cancelled.
+    UpdateNotFound = 56,
+    // The contract spec allows at most 50 error cases. Codes of removed
+    // cases (18, 31, 36, 37, 48, 49) are retired rather than reused; new cases continue
+    // after the highest code in use.
 }
@@ -1,6 +1,37 @@
-use soroban_sdk::{symbol_short, Address, Env};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, String, Symbol, Vec};
 
-const SCHEMA_VERSION: u32 = 1;
+use crate::{
+    get_event_verbosity, next_event_sequence, BatchAbort, ChallengeConfig, ContractMetadata, EpochReport, EscrowStatus, EventEnvelope, EventVerbosity, ExpiryBounds, Feature, FeeStrategy, FxLock, NettingFeeMode, PayoutRoute, QueuedUpdate,
+    RateLimitConfig, ReferralConfig, RemittanceStatus, ReviewRecord, Role, SelfCap, SessionKey, SettlementWindow, TokenInfo, TokenTransferStage,
+};
+
+const SCHEMA_VERSION: u32 = 5;
+
+// Every event payload is `(EventEnvelope, ...)`. The envelope carries the
+// schema version, an `event_sequence` that increases by one per published
+// event across the whole contract (so indexers can spot gaps and duplicates
+// in what they ingested), the ledger sequence and timestamp, and the level
+// the event is published at. Events above the admin-configured verbosity
+// are not published and do not consume a sequence number.
+
+/// Envelope for an event at `level`, or `None` when the configured
+/// verbosity suppresses it.
+fn envelope(env: &Env, level: EventVerbosity) -> Option<EventEnvelope> {
+    if level > get_event_verbosity(env) {
+        return None;
+    }
+
+    Some(EventEnvelope {
+        schema_version: SCHEMA_VERSION,
+        event_sequence: next_event_sequence(env),
+        ledger_sequence: env.ledger().sequence(),
+        timestamp: env.ledger().timestamp(),
+        level,
+    })
+}
+
+// Remittance and settlement events carry the integrator-supplied `external_ref`
+// as a third topic so webhook processors can route them without a lookup.
 
 // ── Remittance Events ──────────────────────────────────────────────
 
@@ -12,19 +43,27 @@ pub fn emit_remittance_created(
     token: Address,
     amount: i128,
     fee: i128,
+    currency: String,
+    country: String,
+    tags: Vec<Symbol>,
+    external_ref: Option<BytesN<32>>,
 ) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
     env.events().publish(
-        (symbol_short!("remit"), symbol_short!("created")),
+        (symbol_short!("remit"), symbol_short!("created"), external_ref),
         (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+            envelope,
             remittance_id,
             sender,
             agent,
             token,
             amount,
             fee,
+            currency,
+            country,
+            tags,
         ),
     );
 }
@@ -36,18 +75,214 @@ pub fn emit_remittance_completed(
     agent: Address,
     token: Address,
     amount: i128,
+    currency: String,
+    country: String,
+    fiat_amount: Option<i128>,
+    external_ref: Option<BytesN<32>>,
 ) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
     env.events().publish(
-        (symbol_short!("remit"), symbol_short!("complete")),
+        (symbol_short!("remit"), symbol_short!("complete"), external_ref),
         (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+            envelope,
             remittance_id,
             sender,
             agent,
             token,
             amount,
+            currency,
+            country,
+            fiat_amount,
+        ),
+    );
+}
+
+pub fn emit_fx_locked(env: &Env, remittance_id: u64, sender: Address, payout_amount: i128, fx_lock: FxLock) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("fx"), symbol_short!("locked")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+            payout_amount,
+            fx_lock,
+        ),
+    );
+}
+
+/// Declared FX terms next to the executed token transfer, so the rate can
+/// be reconciled per settlement.
+pub fn emit_fx_settled(
+    env: &Env,
+    remittance_id: u64,
+    token: Address,
+    source_amount: i128,
+    currency: String,
+    destination_amount: i128,
+    rate: i128,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("settle"), symbol_short!("fx")),
+        (
+            envelope,
+            remittance_id,
+            token,
+            source_amount,
+            currency,
+            destination_amount,
+            rate,
+        ),
+    );
+}
+
+pub fn emit_remittance_linked(env: &Env, parent_id: u64, child_id: u64) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("remit"), symbol_short!("linked")),
+        (
+            envelope,
+            parent_id,
+            child_id,
+        ),
+    );
+}
+
+pub fn emit_recipient_updated(env: &Env, remittance_id: u64, sender: Address, recipient: Option<Address>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("remit"), symbol_short!("recipient")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+            recipient,
+        ),
+    );
+}
+
+pub fn emit_payout_claimed(env: &Env, remittance_id: u64, recipient: Address, token: Address, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("remit"), symbol_short!("claimed")),
+        (
+            envelope,
+            remittance_id,
+            recipient,
+            token,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_refund_redirected(env: &Env, remittance_id: u64, sender: Address, refund_to: Address, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("remit"), symbol_short!("refund_to")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+            refund_to,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_refund_under_pause(env: &Env, remittance_id: u64, sender: Address, refund_to: Address, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("remit"), symbol_short!("rfnd_paus")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+            refund_to,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_remittance_expiring_soon(env: &Env, remittance_id: u64, sender: Address, agent: Address, expiry: u64, seconds_left: u64) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("remit"), symbol_short!("expiring")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+            agent,
+            expiry,
+            seconds_left,
+        ),
+    );
+}
+
+pub fn emit_remittance_transitioned(
+    env: &Env,
+    remittance_id: u64,
+    admin: Address,
+    from: RemittanceStatus,
+    to: RemittanceStatus,
+    reason_hash: BytesN<32>,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("transit")),
+        (
+            envelope,
+            remittance_id,
+            admin,
+            from,
+            to,
+            reason_hash,
+        ),
+    );
+}
+
+pub fn emit_remittance_declined(
+    env: &Env,
+    remittance_id: u64,
+    sender: Address,
+    agent: Address,
+    token: Address,
+    refund: i128,
+    reason: Option<Symbol>,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("remit"), symbol_short!("declined")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+            agent,
+            token,
+            refund,
+            reason,
         ),
     );
 }
@@ -59,18 +294,54 @@ pub fn emit_remittance_cancelled(
     agent: Address,
     token: Address,
     amount: i128,
+    currency: String,
+    country: String,
+    external_ref: Option<BytesN<32>>,
 ) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
     env.events().publish(
-        (symbol_short!("remit"), symbol_short!("cancel")),
+        (symbol_short!("remit"), symbol_short!("cancel"), external_ref),
         (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+            envelope,
             remittance_id,
             sender,
             agent,
             token,
             amount,
+            currency,
+            country,
+        ),
+    );
+}
+
+// ── Quote Events ───────────────────────────────────────────────────
+
+pub fn emit_quote_created(
+    env: &Env,
+    quote_id: u64,
+    sender: Address,
+    amount: i128,
+    fee: i128,
+    currency: String,
+    country: String,
+    expires_at: u64,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::Verbose) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("quote"), symbol_short!("created")),
+        (
+            envelope,
+            quote_id,
+            sender,
+            amount,
+            fee,
+            currency,
+            country,
+            expires_at,
         ),
     );
 }
@@ -78,113 +349,1695 @@ pub fn emit_remittance_cancelled(
 // ── Agent Events ───────────────────────────────────────────────────
 
 pub fn emit_agent_registered(env: &Env, agent: Address, admin: Address) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
     env.events().publish(
         (symbol_short!("agent"), symbol_short!("register")),
         (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+            envelope,
             agent,
             admin,
         ),
     );
 }
 
-pub fn emit_agent_removed(env: &Env, agent: Address, admin: Address) {
+pub fn emit_agent_removed(env: &Env, agent: Address, admin: Address, reason: Option<Symbol>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
     env.events().publish(
         (symbol_short!("agent"), symbol_short!("removed")),
         (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+            envelope,
             agent,
             admin,
+            reason,
+        ),
+    );
+}
+
+pub fn emit_agent_rebate_accrued(env: &Env, remittance_id: u64, agent: Address, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Verbose) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("rebate"), symbol_short!("accrued")),
+        (
+            envelope,
+            remittance_id,
+            agent,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_agent_surcharge_accrued(env: &Env, remittance_id: u64, agent: Address, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Verbose) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("surcharge"), symbol_short!("accrued")),
+        (
+            envelope,
+            remittance_id,
+            agent,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_referrer_set(env: &Env, sender: Address, referrer: Address) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("referral"), symbol_short!("set")),
+        (
+            envelope,
+            sender,
+            referrer,
+        ),
+    );
+}
+
+pub fn emit_referral_accrued(env: &Env, remittance_id: u64, referrer: Address, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Verbose) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("referral"), symbol_short!("accrued")),
+        (
+            envelope,
+            remittance_id,
+            referrer,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_referral_claimed(env: &Env, referrer: Address, token: Address, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("referral"), symbol_short!("claimed")),
+        (
+            envelope,
+            referrer,
+            token,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_agent_rebate_claimed(env: &Env, agent: Address, token: Address, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("rebate"), symbol_short!("claimed")),
+        (
+            envelope,
+            agent,
+            token,
+            amount,
+        ),
+    );
+}
+
+// ── Float Events ───────────────────────────────────────────────────
+
+pub fn emit_float_deposited(env: &Env, agent: Address, token: Address, amount: i128, float: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("float"), symbol_short!("deposit")),
+        (
+            envelope,
+            agent,
+            token,
+            amount,
+            float,
+        ),
+    );
+}
+
+pub fn emit_float_withdrawn(env: &Env, agent: Address, token: Address, amount: i128, float: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("float"), symbol_short!("withdraw")),
+        (
+            envelope,
+            agent,
+            token,
+            amount,
+            float,
+        ),
+    );
+}
+
+pub fn emit_float_credited(env: &Env, agent: Address, amount: i128, float: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Verbose) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("float"), symbol_short!("credited")),
+        (
+            envelope,
+            agent,
+            amount,
+            float,
+        ),
+    );
+}
+
+pub fn emit_float_mode_updated(env: &Env, agent: Address, enabled: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("float"), symbol_short!("mode")),
+        (
+            envelope,
+            agent,
+            enabled,
         ),
     );
 }
 
 // ── Fee Events ─────────────────────────────────────────────────────
 
-pub fn emit_fee_updated(env: &Env, admin: Address, old_fee_bps: u32, new_fee_bps: u32) {
+pub fn emit_token_whitelist_updated(env: &Env, admin: Address, token: Address, info: Option<TokenInfo>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
     env.events().publish(
-        (symbol_short!("fee"), symbol_short!("updated")),
+        (symbol_short!("token"), symbol_short!("whitelist")),
         (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+            envelope,
             admin,
-            old_fee_bps,
-            new_fee_bps,
+            token,
+            info,
         ),
     );
 }
 
-pub fn emit_fees_withdrawn(
-    env: &Env,
-    admin: Address,
-    recipient: Address,
-    token: Address,
-    amount: i128,
-) {
+/// Published at every verbosity so indexers always learn about the change.
+pub fn emit_event_verbosity_updated(env: &Env, admin: Address, verbosity: EventVerbosity) {
+    let Some(envelope) = envelope(env, EventVerbosity::ErrorsOnly) else {
+        return;
+    };
     env.events().publish(
-        (symbol_short!("fee"), symbol_short!("withdraw")),
+        (symbol_short!("admin"), symbol_short!("verbosity")),
         (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+            envelope,
             admin,
-            recipient,
-            token,
-            amount,
+            verbosity,
         ),
     );
 }
 
-pub fn emit_paused(env: &Env, admin: Address) {
+pub fn emit_timelock_delay_updated(env: &Env, admin: Address, delay_seconds: Option<u64>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
     env.events().publish(
-        (symbol_short!("admin"), symbol_short!("paused")),
+        (symbol_short!("admin"), symbol_short!("timelock")),
         (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+            envelope,
             admin,
+            delay_seconds,
         ),
     );
 }
 
-pub fn emit_unpaused(env: &Env, admin: Address) {
+pub fn emit_update_queued(env: &Env, admin: Address, queued: QueuedUpdate) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
     env.events().publish(
-        (symbol_short!("admin"), symbol_short!("unpaused")),
+        (symbol_short!("timelock"), symbol_short!("queued")),
         (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
+            envelope,
             admin,
+            queued,
         ),
     );
 }
 
-// ── Settlement Events ──────────────────────────────────────────────
+pub fn emit_update_executed(env: &Env, admin: Address, id: u64) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("timelock"), symbol_short!("executed")),
+        (
+            envelope,
+            admin,
+            id,
+        ),
+    );
+}
 
-pub fn emit_settlement_completed(
-    env: &Env,
-    sender: Address,
-    recipient: Address,
-    token: Address,
-    amount: i128,
-) {
+pub fn emit_update_cancelled(env: &Env, admin: Address, id: u64) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
     env.events().publish(
-        (symbol_short!("settle"), symbol_short!("complete")),
+        (symbol_short!("timelock"), symbol_short!("cancelled")),
         (
-            SCHEMA_VERSION,
-            env.ledger().sequence(),
-            env.ledger().timestamp(),
-            sender,
-            recipient,
-            token,
-            amount,
+            envelope,
+            admin,
+            id,
+        ),
+    );
+}
+
+pub fn emit_fee_updated(env: &Env, admin: Address, old_fee_bps: u32, new_fee_bps: u32) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("fee"), symbol_short!("updated")),
+        (
+            envelope,
+            admin,
+            old_fee_bps,
+            new_fee_bps,
         ),
     );
 }
 
-```
+pub fn emit_fee_strategy_updated(env: &Env, admin: Address, strategy: Option<FeeStrategy>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("fee"), symbol_short!("strategy")),
+        (
+            envelope,
+            admin,
+            strategy,
+        ),
+    );
+}
+
+pub fn emit_treasury_updated(env: &Env, admin: Address, token: Option<Address>, treasury: Option<Address>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("fee"), symbol_short!("treasury")),
+        (
+            envelope,
+            admin,
+            token,
+            treasury,
+        ),
+    );
+}
+
+pub fn emit_token_fee_updated(env: &Env, admin: Address, token: Address, fee_bps: Option<u32>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("fee"), symbol_short!("token")),
+        (
+            envelope,
+            admin,
+            token,
+            fee_bps,
+        ),
+    );
+}
+
+pub fn emit_agent_rebate_updated(env: &Env, admin: Address, old_rebate_bps: u32, new_rebate_bps: u32) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("fee"), symbol_short!("rebate")),
+        (
+            envelope,
+            admin,
+            old_rebate_bps,
+            new_rebate_bps,
+        ),
+    );
+}
+
+pub fn emit_agent_surcharge_updated(env: &Env, admin: Address, agent: Address, old_surcharge_bps: u32, new_surcharge_bps: u32) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("fee"), symbol_short!("surcharge"), agent),
+        (
+            envelope,
+            admin,
+            old_surcharge_bps,
+            new_surcharge_bps,
+        ),
+    );
+}
+
+pub fn emit_reserve_updated(env: &Env, admin: Address, old_reserve_bps: u32, new_reserve_bps: u32) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("fee"), symbol_short!("reserve")),
+        (
+            envelope,
+            admin,
+            old_reserve_bps,
+            new_reserve_bps,
+        ),
+    );
+}
+
+pub fn emit_insurance_premium_updated(env: &Env, admin: Address, old_premium_bps: u32, new_premium_bps: u32) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("fee"), symbol_short!("insurance")),
+        (
+            envelope,
+            admin,
+            old_premium_bps,
+            new_premium_bps,
+        ),
+    );
+}
+
+pub fn emit_settlement_challenged(env: &Env, remittance_id: u64, challenger: Address, evidence_hash: BytesN<32>, bond: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::ErrorsOnly) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("settle"), symbol_short!("challenge")),
+        (
+            envelope,
+            remittance_id,
+            challenger,
+            evidence_hash,
+            bond,
+        ),
+    );
+}
+
+pub fn emit_challenge_resolved(env: &Env, remittance_id: u64, arbitrator: Address, upheld: bool, bond: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("settle"), symbol_short!("ruling")),
+        (
+            envelope,
+            remittance_id,
+            arbitrator,
+            upheld,
+            bond,
+        ),
+    );
+}
+
+pub fn emit_corridor_receive_limit_updated(env: &Env, admin: Address, currency: String, country: String, limit: Option<i128>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("corridor"), symbol_short!("recv_lim")),
+        (
+            envelope,
+            admin,
+            currency,
+            country,
+            limit,
+        ),
+    );
+}
+
+pub fn emit_corridor_instant_updated(env: &Env, admin: Address, currency: String, country: String, window_seconds: Option<u64>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("corridor"), symbol_short!("instant")),
+        (
+            envelope,
+            admin,
+            currency,
+            country,
+            window_seconds,
+        ),
+    );
+}
+
+pub fn emit_payout_held(env: &Env, remittance_id: u64, payee: Address, amount: i128, release_at: u64) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("held"), symbol_short!("payout")),
+        (
+            envelope,
+            remittance_id,
+            payee,
+            amount,
+            release_at,
+        ),
+    );
+}
+
+pub fn emit_chargeback_opened(env: &Env, remittance_id: u64, sender: Address, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("held"), symbol_short!("dispute")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+            amount,
+        ),
+    );
+}
+
+/// A held payout left the contract: `refunded` payouts went back to the
+/// sender after an upheld chargeback, the rest to the agent.
+pub fn emit_held_payout_released(env: &Env, remittance_id: u64, recipient: Address, amount: i128, refunded: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("held"), symbol_short!("released")),
+        (
+            envelope,
+            remittance_id,
+            recipient,
+            amount,
+            refunded,
+        ),
+    );
+}
+
+pub fn emit_epoch_closed(env: &Env, caller: Address, report: EpochReport) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("epoch"), symbol_short!("closed"), report.epoch),
+        (
+            envelope,
+            caller,
+            report,
+        ),
+    );
+}
+
+pub fn emit_insurance_claim_paid(env: &Env, remittance_id: u64, sender: Address, arbitrator: Address, token: Address, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("insurance"), symbol_short!("claim")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+            arbitrator,
+            token,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_sla_penalty_updated(env: &Env, admin: Address, old_penalty_bps: u32, new_penalty_bps: u32) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("fee"), symbol_short!("sla_pen")),
+        (
+            envelope,
+            admin,
+            old_penalty_bps,
+            new_penalty_bps,
+        ),
+    );
+}
+
+pub fn emit_corridor_enabled_updated(env: &Env, admin: Address, currency: String, country: String, enabled: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("corridor"), symbol_short!("enabled")),
+        (
+            envelope,
+            admin,
+            currency,
+            country,
+            enabled,
+        ),
+    );
+}
+
+pub fn emit_corridor_sla_updated(env: &Env, admin: Address, currency: String, country: String, max_seconds: Option<u64>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("corridor"), symbol_short!("sla")),
+        (
+            envelope,
+            admin,
+            currency,
+            country,
+            max_seconds,
+        ),
+    );
+}
+
+pub fn emit_corridor_expiry_bounds_updated(env: &Env, admin: Address, currency: String, country: String, bounds: Option<ExpiryBounds>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("corridor"), symbol_short!("expiry")),
+        (
+            envelope,
+            admin,
+            currency,
+            country,
+            bounds,
+        ),
+    );
+}
+
+pub fn emit_fees_withdrawn(
+    env: &Env,
+    caller: Address,
+    recipient: Address,
+    token: Address,
+    amount: i128,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("fee"), symbol_short!("withdraw")),
+        (
+            envelope,
+            caller,
+            recipient,
+            token,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_paused(env: &Env, admin: Address, reason: Option<Symbol>, pause_until: Option<u64>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("paused")),
+        (
+            envelope,
+            admin,
+            reason,
+            pause_until,
+        ),
+    );
+}
+
+pub fn emit_max_pause_updated(env: &Env, admin: Address, max_pause_seconds: Option<u64>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("max_pause")),
+        (
+            envelope,
+            admin,
+            max_pause_seconds,
+        ),
+    );
+}
+
+pub fn emit_unpaused(env: &Env, admin: Address) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("unpaused")),
+        (
+            envelope,
+            admin,
+        ),
+    );
+}
+
+pub fn emit_priority_tip_added(env: &Env, remittance_id: u64, sender: Address, tip: i128, total_tip: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("remit"), symbol_short!("tip")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+            tip,
+            total_tip,
+        ),
+    );
+}
+
+pub fn emit_remittance_received(env: &Env, remittance_id: u64, sender: Address) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("remit"), symbol_short!("received")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+        ),
+    );
+}
+
+pub fn emit_contract_metadata_updated(env: &Env, admin: Address, metadata: ContractMetadata) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("metadata")),
+        (
+            envelope,
+            admin,
+            metadata,
+        ),
+    );
+}
+
+pub fn emit_default_expiry_updated(env: &Env, admin: Address, default_expiry_seconds: Option<u64>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("def_exp")),
+        (
+            envelope,
+            admin,
+            default_expiry_seconds,
+        ),
+    );
+}
+
+pub fn emit_dual_confirm_timeout_updated(env: &Env, admin: Address, timeout_seconds: u64) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("dual_tout")),
+        (
+            envelope,
+            admin,
+            timeout_seconds,
+        ),
+    );
+}
+
+// ── Watch Events ───────────────────────────────────────────────────
+
+pub fn emit_watch_added(env: &Env, remittance_id: u64, watcher_tag: Symbol) {
+    let Some(envelope) = envelope(env, EventVerbosity::Verbose) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("watch"), symbol_short!("added"), watcher_tag),
+        (
+            envelope,
+            remittance_id,
+        ),
+    );
+}
+
+pub fn emit_watched_status(env: &Env, remittance_id: u64, watcher_tag: Symbol, status: RemittanceStatus) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("watch"), symbol_short!("status"), watcher_tag),
+        (
+            envelope,
+            remittance_id,
+            status,
+        ),
+    );
+}
+
+// ── Notification Events ────────────────────────────────────────────
+
+pub fn emit_notification_contract_updated(env: &Env, sender: Address, contract: Option<Address>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("notify"), symbol_short!("updated")),
+        (
+            envelope,
+            sender,
+            contract,
+        ),
+    );
+}
+
+pub fn emit_notification_failed(env: &Env, remittance_id: u64, contract: Address) {
+    let Some(envelope) = envelope(env, EventVerbosity::ErrorsOnly) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("notify"), symbol_short!("failed")),
+        (
+            envelope,
+            remittance_id,
+            contract,
+        ),
+    );
+}
+
+// ── Review Events ──────────────────────────────────────────────────
+
+pub fn emit_review_held(env: &Env, remittance_id: u64, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("review"), symbol_short!("held")),
+        (
+            envelope,
+            remittance_id,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_review_decided(env: &Env, remittance_id: u64, review: ReviewRecord) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("review"), symbol_short!("decided")),
+        (
+            envelope,
+            remittance_id,
+            review.reviewer,
+            review.approved,
+            review.requested_at,
+            review.reviewed_at,
+        ),
+    );
+}
+
+pub fn emit_review_threshold_updated(env: &Env, admin: Address, threshold: Option<i128>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("review"), symbol_short!("threshold")),
+        (
+            envelope,
+            admin,
+            threshold,
+        ),
+    );
+}
+
+// ── Escrow Events ──────────────────────────────────────────────────
+
+pub fn emit_escrow_converted(env: &Env, remittance_id: u64, sender: Address, agent: Address, token: Address, milestones: Vec<i128>, status: EscrowStatus) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("escrow"), symbol_short!("converted")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+            agent,
+            token,
+            milestones,
+            status,
+        ),
+    );
+}
+
+pub fn emit_escrow_released(
+    env: &Env,
+    remittance_id: u64,
+    actor: Address,
+    agent: Address,
+    token: Address,
+    milestone: u32,
+    amount: i128,
+    old_status: EscrowStatus,
+    new_status: EscrowStatus,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("escrow"), symbol_short!("released")),
+        (
+            envelope,
+            remittance_id,
+            actor,
+            agent,
+            token,
+            milestone,
+            amount,
+            old_status,
+            new_status,
+        ),
+    );
+}
+
+pub fn emit_escrow_amount_proposed(env: &Env, remittance_id: u64, agent: Address, amount: i128, remaining: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("escrow"), symbol_short!("proposed")),
+        (
+            envelope,
+            remittance_id,
+            agent,
+            amount,
+            remaining,
+        ),
+    );
+}
+
+pub fn emit_escrow_settled(env: &Env, remittance_id: u64, sender: Address, agent: Address, token: Address, amount: i128, refund: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("escrow"), symbol_short!("settled")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+            agent,
+            token,
+            amount,
+            refund,
+        ),
+    );
+}
+
+pub fn emit_escrow_disputed(env: &Env, remittance_id: u64, sender: Address, evidence_hash: BytesN<32>, remaining: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("escrow"), symbol_short!("disputed")),
+        (
+            envelope,
+            remittance_id,
+            sender,
+            evidence_hash,
+            remaining,
+        ),
+    );
+}
+
+pub fn emit_escrow_dispute_resolved(
+    env: &Env,
+    remittance_id: u64,
+    arbitrator: Address,
+    sender: Address,
+    agent: Address,
+    token: Address,
+    sender_share_bps: u32,
+    refund: i128,
+    amount: i128,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("escrow"), symbol_short!("resolved")),
+        (
+            envelope,
+            remittance_id,
+            arbitrator,
+            sender,
+            agent,
+            token,
+            sender_share_bps,
+            refund,
+            amount,
+        ),
+    );
+}
+
+// ── Role Events ────────────────────────────────────────────────────
+
+pub fn emit_role_updated(env: &Env, admin: Address, role: Role, address: Address, granted: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("role"), symbol_short!("updated")),
+        (
+            envelope,
+            admin,
+            role,
+            address,
+            granted,
+        ),
+    );
+}
+
+pub fn emit_sender_allowlist_toggled(env: &Env, admin: Address, enabled: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("allowlist")),
+        (
+            envelope,
+            admin,
+            enabled,
+        ),
+    );
+}
+
+pub fn emit_config_changed(env: &Env, field: Symbol, actor: Address, old_hash: BytesN<32>, new_hash: BytesN<32>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("config"), symbol_short!("changed"), field),
+        (
+            envelope,
+            actor,
+            old_hash,
+            new_hash,
+        ),
+    );
+}
+
+pub fn emit_config_snapshot_taken(env: &Env, admin: Address, snapshot_id: u64) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("config"), symbol_short!("snapshot")),
+        (
+            envelope,
+            admin,
+            snapshot_id,
+        ),
+    );
+}
+
+pub fn emit_config_rolled_back(env: &Env, admin: Address, snapshot_id: u64) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("config"), symbol_short!("rollback")),
+        (
+            envelope,
+            admin,
+            snapshot_id,
+        ),
+    );
+}
+
+pub fn emit_private_mode_updated(env: &Env, admin: Address, enabled: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("private")),
+        (
+            envelope,
+            admin,
+            enabled,
+        ),
+    );
+}
+
+pub fn emit_feature_updated(env: &Env, admin: Address, feature: Feature, enabled: bool, features: u32) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("feature")),
+        (
+            envelope,
+            admin,
+            feature,
+            enabled,
+            features,
+        ),
+    );
+}
+
+pub fn emit_authorization_required_updated(env: &Env, admin: Address, required: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("auth_req")),
+        (
+            envelope,
+            admin,
+            required,
+        ),
+    );
+}
+
+pub fn emit_remittance_authorized(env: &Env, remittance_id: u64, authorizer: Address) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("remit"), symbol_short!("approved")),
+        (
+            envelope,
+            remittance_id,
+            authorizer,
+        ),
+    );
+}
+
+// ── Corridor Registry Events ───────────────────────────────────────
+
+pub fn emit_corridor_registry_toggled(env: &Env, admin: Address, enabled: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("registry"), symbol_short!("toggled")),
+        (
+            envelope,
+            admin,
+            enabled,
+        ),
+    );
+}
+
+pub fn emit_currency_registry_updated(env: &Env, admin: Address, currency: String, allowed: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("registry"), symbol_short!("currency")),
+        (
+            envelope,
+            admin,
+            currency,
+            allowed,
+        ),
+    );
+}
+
+pub fn emit_country_registry_updated(env: &Env, admin: Address, country: String, allowed: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("registry"), symbol_short!("country")),
+        (
+            envelope,
+            admin,
+            country,
+            allowed,
+        ),
+    );
+}
+
+// ── Exposure Events ────────────────────────────────────────────────
+
+pub fn emit_self_cap_updated(env: &Env, sender: Address, cap_type: Symbol, cap: SelfCap) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("self_cap"), cap_type, sender),
+        (
+            envelope,
+            cap,
+        ),
+    );
+}
+
+pub fn emit_exposure_cap_updated(
+    env: &Env,
+    admin: Address,
+    sender: Option<Address>,
+    cap: Option<i128>,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("exposure"), symbol_short!("cap")),
+        (
+            envelope,
+            admin,
+            sender,
+            cap,
+        ),
+    );
+}
+
+pub fn emit_exposure_caps_toggled(env: &Env, admin: Address, enabled: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("exposure"), symbol_short!("toggled")),
+        (
+            envelope,
+            admin,
+            enabled,
+        ),
+    );
+}
+
+/// `subject`'s usage of a limit crossed `threshold_pct` percent of it.
+/// `resets_at` is when a windowed limit starts over, `None` for limits that
+/// only free up as remittances close.
+pub fn emit_limit_threshold_crossed(
+    env: &Env,
+    subject: Address,
+    limit_type: Symbol,
+    threshold_pct: u32,
+    usage: i128,
+    limit: i128,
+    resets_at: Option<u64>,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("limit"), limit_type, subject),
+        (
+            envelope,
+            threshold_pct,
+            usage,
+            limit,
+            resets_at,
+        ),
+    );
+}
+
+pub fn emit_rate_limit_updated(env: &Env, admin: Address, config: Option<RateLimitConfig>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("ratelimit")),
+        (
+            envelope,
+            admin,
+            config,
+        ),
+    );
+}
+
+pub fn emit_referral_config_updated(env: &Env, admin: Address, config: Option<ReferralConfig>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("referral")),
+        (
+            envelope,
+            admin,
+            config,
+        ),
+    );
+}
+
+pub fn emit_challenge_config_updated(env: &Env, admin: Address, config: Option<ChallengeConfig>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("challenge")),
+        (
+            envelope,
+            admin,
+            config,
+        ),
+    );
+}
+
+pub fn emit_tvl_cap_updated(env: &Env, admin: Address, max_total_locked: Option<i128>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("exposure"), symbol_short!("tvl_cap")),
+        (
+            envelope,
+            admin,
+            max_total_locked,
+        ),
+    );
+}
+
+// ── Admin Recovery Events ──────────────────────────────────────────
+
+pub fn emit_admin_recovery_updated(env: &Env, admin: Address, recovery: Option<Address>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("recovery"), symbol_short!("updated")),
+        (
+            envelope,
+            admin,
+            recovery,
+        ),
+    );
+}
+
+pub fn emit_admin_recovery_warning(env: &Env, admin: Address, recovery: Address, seconds_remaining: u64) {
+    let Some(envelope) = envelope(env, EventVerbosity::ErrorsOnly) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("recovery"), symbol_short!("warning")),
+        (
+            envelope,
+            admin,
+            recovery,
+            seconds_remaining,
+        ),
+    );
+}
+
+pub fn emit_admin_recovered(env: &Env, previous_admin: Address, new_admin: Address) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("recovery"), symbol_short!("claimed")),
+        (
+            envelope,
+            previous_admin,
+            new_admin,
+        ),
+    );
+}
+
+// ── Emergency Events ───────────────────────────────────────────────
+
+pub fn emit_guardian_set(env: &Env, admin: Address, guardian: Address) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("guardian")),
+        (
+            envelope,
+            admin,
+            guardian,
+        ),
+    );
+}
+
+pub fn emit_recovery_vault_set(env: &Env, admin: Address, vault: Address, active_at: u64) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("emergency"), symbol_short!("vault")),
+        (
+            envelope,
+            admin,
+            vault,
+            active_at,
+        ),
+    );
+}
+
+pub fn emit_evacuated(
+    env: &Env,
+    admin: Address,
+    guardian: Address,
+    token: Address,
+    vault: Address,
+    amount: i128,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("emergency"), symbol_short!("evacuate")),
+        (
+            envelope,
+            admin,
+            guardian,
+            token,
+            vault,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_evacuation_resumed(env: &Env, admin: Address, guardian: Address) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("emergency"), symbol_short!("resumed")),
+        (
+            envelope,
+            admin,
+            guardian,
+        ),
+    );
+}
+
+// ── Settlement Events ──────────────────────────────────────────────
+
+pub fn emit_batch_settled(
+    env: &Env,
+    batch_id: u64,
+    submitter: Address,
+    remittance_ids: Vec<u64>,
+    fee_mode: NettingFeeMode,
+    fee_discount: i128,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("batch"), symbol_short!("settled")),
+        (
+            envelope,
+            batch_id,
+            submitter,
+            remittance_ids,
+            fee_mode,
+            fee_discount,
+        ),
+    );
+}
+
+pub fn emit_batch_aborted(env: &Env, abort: BatchAbort) {
+    let Some(envelope) = envelope(env, EventVerbosity::ErrorsOnly) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("batch"), symbol_short!("aborted"), abort.submitter.clone()),
+        (
+            envelope,
+            abort.entry_count,
+            abort.failing_remittance_id,
+            abort.error_code,
+        ),
+    );
+}
+
+pub fn emit_corridor_fee_mode_updated(env: &Env, admin: Address, currency: String, country: String, mode: NettingFeeMode) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("corridor"), symbol_short!("fee_mode")),
+        (
+            envelope,
+            admin,
+            currency,
+            country,
+            mode,
+        ),
+    );
+}
+
+pub fn emit_settlement_approved(env: &Env, remittance_id: u64, agent: Address) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("settle"), symbol_short!("approved")),
+        (
+            envelope,
+            remittance_id,
+            agent,
+        ),
+    );
+}
+
+pub fn emit_agent_signing_key_updated(env: &Env, agent: Address, key: Option<BytesN<32>>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("agent"), symbol_short!("sig_key")),
+        (
+            envelope,
+            agent,
+            key,
+        ),
+    );
+}
+
+pub fn emit_token_transfer_failed(
+    env: &Env,
+    stage: TokenTransferStage,
+    token: Address,
+    from: Address,
+    to: Address,
+    amount: i128,
+    reason: Symbol,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::ErrorsOnly) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("token"), symbol_short!("xfer_fail"), stage),
+        (
+            envelope,
+            token,
+            from,
+            to,
+            amount,
+            reason,
+        ),
+    );
+}
+
+pub fn emit_payout_route_updated(env: &Env, agent: Address, route: Option<PayoutRoute>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("agent"), symbol_short!("route")),
+        (
+            envelope,
+            agent,
+            route,
+        ),
+    );
+}
+
+pub fn emit_payout_routed(env: &Env, agent: Address, destination: Address, memo: String, token: Address, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("settle"), symbol_short!("routed"), destination),
+        (
+            envelope,
+            agent,
+            memo,
+            token,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_session_key_registered(env: &Env, agent: Address, key: BytesN<32>, session: Option<SessionKey>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("agent"), symbol_short!("session")),
+        (
+            envelope,
+            agent,
+            key,
+            session,
+        ),
+    );
+}
+
+pub fn emit_payout_session_signed(env: &Env, remittance_id: u64, agent: Address, key: BytesN<32>, amount: i128) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("settle"), symbol_short!("session")),
+        (
+            envelope,
+            remittance_id,
+            agent,
+            key,
+            amount,
+        ),
+    );
+}
+
+pub fn emit_payout_signed(env: &Env, remittance_id: u64, agent: Address, nonce: u64) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("settle"), symbol_short!("signed")),
+        (
+            envelope,
+            remittance_id,
+            agent,
+            nonce,
+        ),
+    );
+}
+
+pub fn emit_alternate_refund_toggled(env: &Env, admin: Address, enabled: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("alt_rfnd")),
+        (
+            envelope,
+            admin,
+            enabled,
+        ),
+    );
+}
+
+pub fn emit_paused_refunds_toggled(env: &Env, admin: Address, allowed: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("admin"), symbol_short!("paus_rfnd")),
+        (
+            envelope,
+            admin,
+            allowed,
+        ),
+    );
+}
+
+pub fn emit_settlement_windows_updated(env: &Env, admin: Address, windows: Option<Vec<SettlementWindow>>) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("batch"), symbol_short!("windows")),
+        (
+            envelope,
+            admin,
+            windows,
+        ),
+    );
+}
+
+pub fn emit_batch_approval_mode_updated(env: &Env, admin: Address, required: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("batch"), symbol_short!("approval")),
+        (
+            envelope,
+            admin,
+            required,
+        ),
+    );
+}
+
+pub fn emit_batch_mode_updated(env: &Env, admin: Address, permissionless: bool) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("batch"), symbol_short!("mode")),
+        (
+            envelope,
+            admin,
+            permissionless,
+        ),
+    );
+}
+
+pub fn emit_settlement_rejected(env: &Env, remittance_id: u64, reason: u32, currency: String, country: String) {
+    let Some(envelope) = envelope(env, EventVerbosity::ErrorsOnly) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("settle"), symbol_short!("rejected")),
+        (
+            envelope,
+            remittance_id,
+            reason,
+            currency,
+            country,
+        ),
+    );
+}
+
+// `amount` is the net payout; `gross_amount - platform_fee == amount`.
+pub fn emit_settlement_completed(
+    env: &Env,
+    sender: Address,
+    recipient: Address,
+    token: Address,
+    amount: i128,
+    gross_amount: i128,
+    platform_fee: i128,
+    sla_penalty: i128,
+    external_ref: Option<BytesN<32>>,
+) {
+    let Some(envelope) = envelope(env, EventVerbosity::Standard) else {
+        return;
+    };
+    env.events().publish(
+        (symbol_short!("settle"), symbol_short!("complete"), external_ref),
+        (
+            envelope,
+            sender,
+            recipient,
+            token,
+            amount,
+            gross_amount,
+            platform_fee,
+            sla_penalty,
+        ),
+    );
+}
@@ -0,0 +1,125 @@
+//! Deterministic sandbox state for integration tests.
+//!
+//! Compiled only into test builds and builds with the "testutils" feature.
+//! `seed_demo_state` goes through the same internal paths as the public
+//! entry points but checks only the admin's signature. Token transfers still
+//! need the generated senders' auth below the root invocation, so the host
+//! must mock auths with `env.mock_all_auths_allowing_non_root_auth()`. The
+//! admin must also hold enough of the settlement token to fund every
+//! generated remittance.
+
+use soroban_sdk::{contractimpl, contracttype, testutils::Address as _, token, vec, Address, Env, String, Vec};
+
+use crate::{
+    cancel_pending_remittance, compute_fee, convert_pending_to_escrow, emit_agent_registered, get_counters,
+    get_remittance, load_settlement_context, open_remittance, pay_agent, record_settlement, require_admin, set_agent_registered,
+    set_counters, set_sender_exposure_cap, ContractError, SwiftRemitContract, SwiftRemitContractClient,
+};
+
+/// Maximum number of remittances `seed_demo_state` creates per call.
+pub const MAX_SEED_REMITTANCES: u32 = 50;
+
+/// Maximum number of agents `seed_demo_state` registers per call.
+pub const MAX_SEED_AGENTS: u32 = 10;
+
+/// Exposure cap set on every generated sender.
+pub const SEED_SENDER_EXPOSURE_CAP: i128 = 1_000_000;
+
+/// Addresses and IDs created by `seed_demo_state`. `senders[i]` sends to
+/// `agents[i]`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DemoState {
+    pub agents: Vec<Address>,
+    pub senders: Vec<Address>,
+    pub remittance_ids: Vec<u64>,
+}
+
+#[contractimpl]
+impl SwiftRemitContract {
+    /// Registers `n_agents` agents, each paired with a sender, and creates
+    /// `n_remittances` remittances round-robin across the pairs. Remittance
+    /// `i` is for `1000 + 100 * i` on the USD/NG corridor and is left, by
+    /// `i % 4`, Pending, Completed, Cancelled or converted into a two-stage
+    /// escrow. Every sender gets an exposure cap of
+    /// `SEED_SENDER_EXPOSURE_CAP`. Admin only.
+    pub fn seed_demo_state(env: Env, n_remittances: u32, n_agents: u32) -> Result<DemoState, ContractError> {
+        let admin = require_admin(&env)?;
+
+        if n_agents == 0 || n_agents > MAX_SEED_AGENTS || n_remittances > MAX_SEED_REMITTANCES {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let context = load_settlement_context(&env)?;
+        let token_client = token::Client::new(&env, &context.usdc_token);
+        let currency = String::from_str(&env, "USD");
+        let country = String::from_str(&env, "NG");
+
+        let mut agents = Vec::new(&env);
+        let mut senders = Vec::new(&env);
+        for _ in 0..n_agents {
+            let agent = Address::generate(&env);
+            let sender = Address::generate(&env);
+            set_agent_registered(&env, &agent, true);
+            emit_agent_registered(&env, agent.clone(), admin.clone());
+            set_sender_exposure_cap(&env, &sender, Some(SEED_SENDER_EXPOSURE_CAP));
+            agents.push_back(agent);
+            senders.push_back(sender);
+        }
+
+        let mut remittance_ids = Vec::new(&env);
+        for i in 0..n_remittances {
+            let pair = i % n_agents;
+            let sender = senders.get_unchecked(pair);
+            let agent = agents.get_unchecked(pair);
+            let amount = 1000 + 100 * i as i128;
+
+            token_client.transfer(&admin, &sender, &amount);
+
+            let fee = compute_fee(&env, &sender, Some(&agent), amount, &currency, &country)?;
+            let remittance_id = open_remittance(
+                &env,
+                sender.clone(),
+                agent.clone(),
+                amount,
+                fee,
+                0,
+                currency.clone(),
+                country.clone(),
+                None,
+                None,
+                Vec::new(&env),
+                0,
+                0,
+            )?;
+            let remittance = get_remittance(&env, remittance_id)?;
+
+            match i % 4 {
+                1 => {
+                    let payout_amount = amount - fee;
+                    pay_agent(&env, &context.usdc_token, &agent, payout_amount)?;
+                    let mut counters = get_counters(&env)?;
+                    record_settlement(&env, &context, remittance, payout_amount, None, &mut counters)?;
+                    set_counters(&env, &counters);
+                }
+                2 => {
+                    cancel_pending_remittance(&env, &context, remittance, &sender)?;
+                }
+                3 => {
+                    let net_amount = amount - fee;
+                    let first = net_amount / 2;
+                    convert_pending_to_escrow(&env, remittance, vec![&env, first, net_amount - first])?;
+                }
+                _ => {}
+            }
+
+            remittance_ids.push_back(remittance_id);
+        }
+
+        Ok(DemoState {
+            agents,
+            senders,
+            remittance_ids,
+        })
+    }
+}
@@ -0,0 +1,133 @@
+//! Consistency checks over stored state, for fuzzing and pre-upgrade
+//! verification.
+//!
+//! Compiled only into test builds and builds with the "invariants" feature,
+//! so production WASM never exposes `debug_check_invariants`.
+
+use soroban_sdk::{contractimpl, contracttype, symbol_short, token, Env, Symbol, Vec};
+
+use crate::{
+    get_counters, get_escrow, get_remittance, owed_balances, get_review, get_settlement_hash, get_usdc_token, ContractError,
+    RemittanceStatus, SwiftRemitContract, SwiftRemitContractClient,
+};
+
+/// Maximum number of remittances inspected by one `debug_check_invariants` call.
+pub const MAX_INVARIANT_SAMPLE: u32 = 50;
+
+/// A failed check. `remittance_id` is 0 for contract-wide checks.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantViolation {
+    pub remittance_id: u64,
+    pub check: Symbol,
+}
+
+/// Result of `debug_check_invariants`. `complete` is true when the sample
+/// covered every remittance, which also enables the locked-total check.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InvariantReport {
+    pub checked_count: u32,
+    pub complete: bool,
+    pub violations: Vec<InvariantViolation>,
+}
+
+#[contractimpl]
+impl SwiftRemitContract {
+    /// Checks up to `MAX_INVARIANT_SAMPLE` remittances starting at
+    /// `start_id` together with the contract-wide counters and returns every
+    /// violation found. Never modifies state.
+    ///
+    /// Checks, by report symbol:
+    /// - `missing`: every ID up to the remittance counter has a record
+    /// - `beyond`: no record exists past the remittance counter
+    /// - `amounts`: amount is positive, fee, surcharge and tip are non-negative and the fee plus surcharge does not exceed the amount
+    /// - `escrow`: Converted remittances have an escrow
+    /// - `settled`: Completed remittances have a settlement hash
+    /// - `review`: ReviewRejected remittances have a review record
+    /// - `counters`: fees and locked totals are non-negative and settlements plus cancellations never exceed remittances created
+    /// - `solvency`: the token balance covers locked funds, unwithdrawn fees and every other owed balance (insurance fund, challenge bonds, held payouts, agent floats, claimable rebates and referral rewards, pending tips)
+    /// - `locked`: on a complete sample, Pending and PendingReview amounts add up to the locked total
+    pub fn debug_check_invariants(env: Env, start_id: u64, limit: u32) -> Result<InvariantReport, ContractError> {
+        let counters = get_counters(&env)?;
+        let mut violations = Vec::new(&env);
+
+        let start_id = start_id.max(1);
+        let limit = limit.min(MAX_INVARIANT_SAMPLE);
+        let mut checked_count = 0;
+        let mut sampled_locked: i128 = 0;
+
+        let mut id = start_id;
+        while checked_count < limit && id <= counters.remittance_counter {
+            checked_count += 1;
+
+            let Ok(remittance) = get_remittance(&env, id) else {
+                record(&mut violations, id, symbol_short!("missing"));
+                id += 1;
+                continue;
+            };
+
+            if remittance.amount <= 0
+                || remittance.fee < 0
+                || remittance.agent_surcharge < 0
+                || remittance.fee.saturating_add(remittance.agent_surcharge) > remittance.amount
+                || remittance.priority_tip < 0
+            {
+                record(&mut violations, id, symbol_short!("amounts"));
+            }
+
+            match remittance.status {
+                RemittanceStatus::Pending | RemittanceStatus::PendingReview | RemittanceStatus::PayoutFailed => {
+                    sampled_locked = sampled_locked.saturating_add(remittance.amount);
+                }
+                RemittanceStatus::Converted if get_escrow(&env, id).is_err() => {
+                    record(&mut violations, id, symbol_short!("escrow"));
+                }
+                RemittanceStatus::Completed if get_settlement_hash(&env, id).is_none() => {
+                    record(&mut violations, id, symbol_short!("settled"));
+                }
+                RemittanceStatus::ReviewRejected if get_review(&env, id).is_none() => {
+                    record(&mut violations, id, symbol_short!("review"));
+                }
+                _ => {}
+            }
+
+            id += 1;
+        }
+
+        if get_remittance(&env, counters.remittance_counter.saturating_add(1)).is_ok() {
+            record(&mut violations, 0, symbol_short!("beyond"));
+        }
+
+        let closed = counters
+            .total_settlements
+            .saturating_add(counters.total_cancellations);
+        if counters.accumulated_fees < 0 || counters.total_locked < 0 || closed > counters.remittance_counter {
+            record(&mut violations, 0, symbol_short!("counters"));
+        }
+
+        let balance = token::Client::new(&env, &get_usdc_token(&env)?).balance(&env.current_contract_address());
+        let liabilities = counters
+            .total_locked
+            .saturating_add(counters.accumulated_fees)
+            .saturating_add(owed_balances(&env)?);
+        if balance < liabilities {
+            record(&mut violations, 0, symbol_short!("solvency"));
+        }
+
+        let complete = start_id == 1 && id > counters.remittance_counter;
+        if complete && sampled_locked != counters.total_locked {
+            record(&mut violations, 0, symbol_short!("locked"));
+        }
+
+        Ok(InvariantReport {
+            checked_count,
+            complete,
+            violations,
+        })
+    }
+}
+
+fn record(violations: &mut Vec<InvariantViolation>, remittance_id: u64, check: Symbol) {
+    violations.push_back(InvariantViolation { remittance_id, check });
+}
@@ -1,21 +1,141 @@
 #![no_std]
+// Contract entry points and event payloads routinely exceed clippy's argument limit.
+#![allow(clippy::too_many_arguments)]
 
 mod debug;
 mod errors;
 mod events;
+#[cfg(any(test, feature = "testutils"))]
+mod fixtures;
+#[cfg(any(test, feature = "invariants"))]
+mod invariants;
 mod storage;
 mod types;
 mod validation;
 
-use soroban_sdk::{contract, contractimpl, token, Address, Env};
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{
+    contract, contractimpl, symbol_short, token, xdr::ToXdr, Address, BytesN, Env, IntoVal, Map, String,
+    Symbol, Val, Vec,
+};
 
 pub use debug::*;
 pub use errors::ContractError;
 pub use events::*;
+#[cfg(any(test, feature = "testutils"))]
+pub use fixtures::*;
+#[cfg(any(test, feature = "invariants"))]
+pub use invariants::*;
 pub use storage::*;
 pub use types::*;
 pub use validation::*;
 
+/// How long a quote returned by `create_quote` can be redeemed, in seconds.
+pub const QUOTE_VALIDITY_SECONDS: u64 = 300;
+
+/// Function invoked on the configured fee strategy contract. It receives
+/// `(sender, agent, amount, currency, country, token)` and returns a
+/// `FeeBreakdown`.
+pub const FEE_STRATEGY_FUNCTION: &str = "compute_fees";
+
+/// Function invoked on a sender's notification contract after one of its
+/// remittances settles or is refunded. It receives a `RemittanceNotice`.
+pub const NOTIFICATION_FUNCTION: &str = "on_remittance_update";
+
+/// Maximum number of analytics tags a remittance can carry.
+pub const MAX_TAGS: u32 = 5;
+
+/// Maximum number of watcher tags a remittance can carry.
+pub const MAX_WATCH_TAGS: u32 = 4;
+
+/// Maximum length of a `PayoutRoute` memo, matching a Stellar text memo.
+pub const MAX_PAYOUT_MEMO_LEN: u32 = 28;
+
+/// Maximum number of remittance IDs `export_remittance_page` scans per call.
+pub const MAX_EXPORT_SCAN: u64 = 100;
+
+/// Maximum number of addresses in one `set_roles` call.
+pub const MAX_ROLE_BATCH: u32 = 50;
+
+/// Maximum number of remittances in one `admin_transition_batch` call.
+pub const MAX_TRANSITION_BATCH: u32 = 50;
+
+/// Fixed-point scale of `FxLock::rate`.
+pub const FX_RATE_SCALE: i128 = 10_000_000;
+
+/// Maximum number of linked remittances under one parent.
+pub const MAX_LINKED_CHILDREN: u32 = 20;
+
+/// Percentages of a limit whose crossing emits a `limit` event.
+pub const LIMIT_ALERT_THRESHOLDS_PCT: [u32; 2] = [80, 100];
+
+/// Maximum number of closed IDs the open-remittance head skips per close.
+pub const MAX_OPEN_HEAD_ADVANCE: u32 = 50;
+
+/// Maximum number of remittance IDs `scan_expiring`, `get_aged_pending`
+/// and `get_settlement_queue` visit per call.
+pub const MAX_OPEN_SCAN: u64 = 100;
+
+/// Number of configuration snapshots retained; older ones are pruned.
+pub const MAX_CONFIG_SNAPSHOTS: u32 = 10;
+
+/// Creation flag: payout also needs the sender's `confirm_received` until
+/// the dual-confirmation timeout elapses.
+pub const FLAG_DUAL_CONFIRM: u32 = 1 << 0;
+
+/// Creation flag: keep the remittance open-ended even when a default expiry
+/// policy is configured.
+pub const FLAG_NO_DEFAULT_EXPIRY: u32 = 1 << 1;
+
+/// Creation flag: pull the deposit with `transfer_from` against the sender's
+/// prior allowance to this contract instead of a sender-signed transfer.
+pub const FLAG_PULL_FUNDING: u32 = 1 << 2;
+
+/// Creation flag: pay the insurance premium on top of the amount so the
+/// sender can be compensated from the insurance fund if the payout is lost.
+pub const FLAG_INSURED: u32 = 1 << 3;
+
+/// All creation flags understood by this contract version.
+pub const SUPPORTED_FLAGS: u32 = FLAG_DUAL_CONFIRM | FLAG_NO_DEFAULT_EXPIRY | FLAG_PULL_FUNDING | FLAG_INSURED;
+
+/// Default time after creation when an agent-only confirmation suffices for
+/// a dual-confirmation remittance.
+pub const DEFAULT_DUAL_CONFIRM_TIMEOUT_SECONDS: u64 = 3 * 24 * 60 * 60;
+
+/// Maximum number of milestones in an escrow created by `convert_to_escrow`.
+pub const MAX_MILESTONES: u32 = 10;
+
+/// Admin inactivity after which the recovery address may claim the admin
+/// role.
+pub const ADMIN_RECOVERY_INACTIVITY_SECONDS: u64 = 180 * 24 * 60 * 60;
+
+/// How long before the recovery deadline `check_admin_recovery` starts
+/// emitting warnings.
+pub const ADMIN_RECOVERY_WARNING_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+/// Maximum number of remittances settled by one `batch_settle_with_netting`.
+pub const MAX_BATCH_SIZE: u32 = 20;
+
+/// How long a submitter's aborted batch attempt is retained.
+pub const BATCH_ABORT_TTL_SECONDS: u64 = 24 * 60 * 60;
+
+/// Maximum number of daily settlement windows.
+pub const MAX_SETTLEMENT_WINDOWS: u32 = 8;
+
+/// Delay before a newly registered recovery vault can receive an evacuation.
+pub const RECOVERY_VAULT_DELAY_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// Length of a UTC day, for daily windows and caps.
+pub const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Days of payouts counted against an agent's corridor receive limit.
+pub const RECEIVE_LIMIT_WINDOW_DAYS: u64 = 30;
+
+/// Wait before a sender's raise or removal of their own cap takes effect.
+pub const SELF_CAP_RAISE_DELAY_SECONDS: u64 = 24 * 60 * 60;
+
 #[contract]
 pub struct SwiftRemitContract;
 
@@ -26,6 +146,7 @@ impl SwiftRemitContract {
         admin: Address,
         usdc_token: Address,
         fee_bps: u32,
+        recovery: Option<Address>,
     ) -> Result<(), ContractError> {
         if has_admin(&env) {
             return Err(ContractError::AlreadyInitialized);
@@ -38,8 +159,10 @@ impl SwiftRemitContract {
         set_admin(&env, &admin);
         set_usdc_token(&env, &usdc_token);
         set_platform_fee_bps(&env, fee_bps);
-        set_remittance_counter(&env, 0);
-        set_accumulated_fees(&env, 0);
+        archive_fee_schedule(&env)?;
+        set_counters(&env, &Counters::default());
+        set_admin_recovery(&env, recovery.as_ref());
+        set_last_admin_activity(&env, env.ledger().timestamp());
 
         log_initialize(&env, &admin, &usdc_token, fee_bps);
 
@@ -47,10 +170,10 @@ impl SwiftRemitContract {
     }
 
     pub fn register_agent(env: Env, agent: Address) -> Result<(), ContractError> {
-        let admin = get_admin(&env)?;
-        admin.require_auth();
+        let admin = require_admin(&env)?;
 
         set_agent_registered(&env, &agent, true);
+        set_agent_removal_reason(&env, &agent, None);
         emit_agent_registered(&env, agent.clone(), admin.clone());
 
         log_register_agent(&env, &agent);
@@ -58,244 +181,5087 @@ impl SwiftRemitContract {
         Ok(())
     }
 
-    pub fn remove_agent(env: Env, agent: Address) -> Result<(), ContractError> {
-        let admin = get_admin(&env)?;
-        admin.require_auth();
+    /// Deregisters an agent, recording an optional machine-readable reason
+    /// code alongside the removal.
+    pub fn remove_agent(env: Env, agent: Address, reason: Option<Symbol>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
 
         set_agent_registered(&env, &agent, false);
-        emit_agent_removed(&env, agent.clone(), admin.clone());
+        set_agent_removal_reason(&env, &agent, reason.as_ref());
+        emit_agent_removed(&env, agent.clone(), admin.clone(), reason);
 
         log_remove_agent(&env, &agent);
 
         Ok(())
     }
 
+    /// Sets the platform fee. Fails with `InvalidStatus` while a timelock
+    /// delay is set; use `queue_fee_update` then.
     pub fn update_fee(env: Env, fee_bps: u32) -> Result<(), ContractError> {
-        let admin = get_admin(&env)?;
-        admin.require_auth();
+        let admin = require_admin(&env)?;
 
-        if fee_bps > 10000 {
-            return Err(ContractError::InvalidFeeBps);
+        if get_timelock_delay(&env).is_some() {
+            return Err(ContractError::InvalidStatus);
         }
+        validate_timelocked_update(&TimelockedUpdate::PlatformFee(fee_bps))?;
 
-        set_platform_fee_bps(&env, fee_bps);
-        let old_fee = get_platform_fee_bps(&env)?;
-        emit_fee_updated(&env, admin.clone(), old_fee, fee_bps);
+        apply_platform_fee(&env, admin, fee_bps)
+    }
+
+    /// Sets how long `queue_fee_update` holds fee and treasury changes
+    /// before they can be executed; while set, `update_fee`, `set_treasury`,
+    /// `set_token_fee_bps`, `set_treasury_for_token`, `set_fee_strategy`,
+    /// `update_agent_rebate`, `set_agent_surcharge_bps`,
+    /// `set_insurance_premium_bps` and `set_sla_penalty_bps` are disabled
+    /// and `rollback_config` may not change any of the settings they cover.
+    /// A longer delay applies immediately, a
+    /// shorter one or `None` must itself be queued as
+    /// `TimelockedUpdate::Delay`, otherwise this fails with `InvalidStatus`.
+    pub fn set_timelock_delay(env: Env, delay_seconds: Option<u64>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
 
-        log_update_fee(&env, fee_bps);
+        validate_timelocked_update(&TimelockedUpdate::Delay(delay_seconds))?;
+        if delay_seconds.unwrap_or(0) < get_timelock_delay(&env).unwrap_or(0) {
+            return Err(ContractError::InvalidStatus);
+        }
 
+        apply_timelock_delay(&env, admin, delay_seconds);
         Ok(())
     }
 
-    pub fn create_remittance(
+    /// Queues an economics change to take effect once the timelock delay
+    /// has passed, announcing it with a `timelock/queued` event. Returns the
+    /// update ID for `execute_fee_update` and `cancel_queued_update`.
+    pub fn queue_fee_update(env: Env, update: TimelockedUpdate) -> Result<u64, ContractError> {
+        let admin = require_admin(&env)?;
+
+        validate_timelocked_update(&update)?;
+
+        let id = get_queued_update_counter(&env)
+            .checked_add(1)
+            .ok_or(ContractError::Overflow)?;
+        let queued_at = env.ledger().timestamp();
+        let eta = queued_at
+            .checked_add(get_timelock_delay(&env).unwrap_or(0))
+            .ok_or(ContractError::Overflow)?;
+        let queued = QueuedUpdate { id, update, queued_at, eta };
+
+        set_queued_update_counter(&env, id);
+        set_queued_update(&env, id, Some(&queued));
+        emit_update_queued(&env, admin, queued);
+
+        Ok(id)
+    }
+
+    /// Applies a queued update whose delay has passed. Fails with
+    /// `UpdateNotFound` for IDs never queued or already executed or
+    /// cancelled, and with `InvalidStatus` while the ledger time is before
+    /// the update's `eta`.
+    pub fn execute_fee_update(env: Env, id: u64) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let queued = get_queued_update(&env, id).ok_or(ContractError::UpdateNotFound)?;
+        if env.ledger().timestamp() < queued.eta {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        set_queued_update(&env, id, None);
+        match queued.update {
+            TimelockedUpdate::PlatformFee(fee_bps) => apply_platform_fee(&env, admin.clone(), fee_bps)?,
+            TimelockedUpdate::Treasury(treasury) => apply_treasury(&env, admin.clone(), treasury),
+            TimelockedUpdate::TokenFee(token, fee_bps) => apply_token_fee_bps(&env, admin.clone(), token, fee_bps),
+            TimelockedUpdate::TokenTreasury(token, treasury) => apply_token_treasury(&env, admin.clone(), token, treasury),
+            TimelockedUpdate::Delay(delay_seconds) => apply_timelock_delay(&env, admin.clone(), delay_seconds),
+            TimelockedUpdate::FeeStrategy(strategy) => apply_fee_strategy(&env, admin.clone(), Some(strategy)),
+            TimelockedUpdate::RemoveFeeStrategy => apply_fee_strategy(&env, admin.clone(), None),
+            TimelockedUpdate::AgentRebate(rebate_bps) => apply_agent_rebate(&env, admin.clone(), rebate_bps)?,
+            TimelockedUpdate::AgentSurcharge(agent, surcharge_bps) => apply_agent_surcharge(&env, admin.clone(), agent, surcharge_bps)?,
+            TimelockedUpdate::InsurancePremium(premium_bps) => apply_insurance_premium(&env, admin.clone(), premium_bps),
+            TimelockedUpdate::SlaPenalty(penalty_bps) => apply_sla_penalty(&env, admin.clone(), penalty_bps)?,
+        }
+        emit_update_executed(&env, admin, id);
+
+        Ok(())
+    }
+
+    /// Drops a queued update before it is executed. Fails with
+    /// `UpdateNotFound` for IDs never queued or already executed or
+    /// cancelled.
+    pub fn cancel_queued_update(env: Env, id: u64) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        get_queued_update(&env, id).ok_or(ContractError::UpdateNotFound)?;
+        set_queued_update(&env, id, None);
+        emit_update_cancelled(&env, admin, id);
+
+        Ok(())
+    }
+
+    pub fn get_timelock_delay(env: Env) -> Option<u64> {
+        get_timelock_delay(&env)
+    }
+
+    pub fn get_queued_update(env: Env, id: u64) -> Option<QueuedUpdate> {
+        get_queued_update(&env, id)
+    }
+
+    pub fn set_exposure_caps_enabled(env: Env, enabled: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let previous = are_exposure_caps_enabled(&env);
+        set_exposure_caps_enabled(&env, enabled);
+        record_config_change(&env, "exposure_caps_enabled", admin.clone(), previous, enabled);
+        emit_exposure_caps_toggled(&env, admin, enabled);
+
+        Ok(())
+    }
+
+    /// Sets the exposure cap applied to every sender without an individual
+    /// override. `None` removes the global cap.
+    pub fn set_global_exposure_cap(env: Env, cap: Option<i128>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if matches!(cap, Some(cap) if cap <= 0) {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let previous = get_global_exposure_cap(&env);
+        set_global_exposure_cap(&env, cap);
+        record_config_change(&env, "global_exposure_cap", admin.clone(), previous, cap);
+        emit_exposure_cap_updated(&env, admin, None, cap);
+
+        Ok(())
+    }
+
+    /// Overrides the global exposure cap for one sender. `None` removes the
+    /// override so the global cap applies again.
+    pub fn set_sender_exposure_cap(
         env: Env,
         sender: Address,
-        agent: Address,
-        amount: i128,
-        expiry: Option<u64>,
-    ) -> Result<u64, ContractError> {
-        sender.require_auth();
+        cap: Option<i128>,
+    ) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
 
-        if amount <= 0 {
+        if matches!(cap, Some(cap) if cap <= 0) {
             return Err(ContractError::InvalidAmount);
         }
 
-        if !is_agent_registered(&env, &agent) {
-            return Err(ContractError::AgentNotRegistered);
+        let previous = (sender.clone(), get_sender_exposure_cap(&env, &sender));
+        set_sender_exposure_cap(&env, &sender, cap);
+        record_config_change(&env, "sender_exposure_cap", admin.clone(), previous, (sender.clone(), cap));
+        emit_exposure_cap_updated(&env, admin, Some(sender), cap);
+
+        Ok(())
+    }
+
+    /// Sets the ceiling on total value held for Pending remittances.
+    /// `None` removes the ceiling.
+    pub fn set_max_total_locked(env: Env, max_total_locked: Option<i128>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if matches!(max_total_locked, Some(max) if max <= 0) {
+            return Err(ContractError::InvalidAmount);
         }
 
-        let fee_bps = get_platform_fee_bps(&env)?;
-        let fee = amount
-            .checked_mul(fee_bps as i128)
-            .ok_or(ContractError::Overflow)?
-            .checked_div(10000)
-            .ok_or(ContractError::Overflow)?;
+        let previous = get_max_total_locked(&env);
+        set_max_total_locked(&env, max_total_locked);
+        record_config_change(&env, "max_total_locked", admin.clone(), previous, max_total_locked);
+        emit_tvl_cap_updated(&env, admin, max_total_locked);
 
-        let usdc_token = get_usdc_token(&env)?;
-        let token_client = token::Client::new(&env, &usdc_token);
-        token_client.transfer(&sender, &env.current_contract_address(), &amount);
+        Ok(())
+    }
 
-        let counter = get_remittance_counter(&env)?;
-        let remittance_id = counter.checked_add(1).ok_or(ContractError::Overflow)?;
+    /// Sets the highest event level the contract publishes. Lower verbosity
+    /// saves footprint on high-frequency deployments; debug logs are only
+    /// written at `Verbose`.
+    pub fn set_event_verbosity(env: Env, verbosity: EventVerbosity) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
 
-        let remittance = Remittance {
-            id: remittance_id,
-            sender: sender.clone(),
-            agent: agent.clone(),
-            amount,
-            fee,
-            status: RemittanceStatus::Pending,
-            expiry,
-        };
+        let previous = get_event_verbosity(&env);
+        set_event_verbosity(&env, verbosity);
+        record_config_change(&env, "event_verbosity", admin.clone(), previous, verbosity);
+        emit_event_verbosity_updated(&env, admin, verbosity);
 
-        set_remittance(&env, remittance_id, &remittance);
-        set_remittance_counter(&env, remittance_id);
+        Ok(())
+    }
 
-        emit_remittance_created(&env, remittance_id, sender.clone(), agent.clone(), usdc_token.clone(), amount, fee);
+    /// Lets anyone submit netting batches when `true`; otherwise only the
+    /// admin and BatchSettlers may.
+    pub fn set_batch_permissionless(env: Env, permissionless: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
 
-        log_create_remittance(&env, remittance_id, &sender, &agent, amount, fee);
+        let previous = is_batch_permissionless(&env);
+        set_batch_permissionless(&env, permissionless);
+        record_config_change(&env, "batch_permissionless", admin.clone(), previous, permissionless);
+        emit_batch_mode_updated(&env, admin, permissionless);
 
-        Ok(remittance_id)
+        Ok(())
     }
 
-    pub fn confirm_payout(env: Env, remittance_id: u64) -> Result<(), ContractError> {
-        if is_paused(&env) {
-            return Err(ContractError::ContractPaused);
+    /// Restricts netting batches to the given daily UTC windows. `None`
+    /// allows batches at any time.
+    pub fn set_settlement_windows(env: Env, windows: Option<Vec<SettlementWindow>>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if let Some(windows) = &windows {
+            validate_settlement_windows(windows)?;
         }
 
-        let mut remittance = get_remittance(&env, remittance_id)?;
+        let previous = get_settlement_windows(&env);
+        set_settlement_windows(&env, windows.clone());
+        record_config_change(&env, "settlement_windows", admin.clone(), previous, windows.clone());
+        emit_settlement_windows_updated(&env, admin, windows);
 
-        remittance.agent.require_auth();
+        Ok(())
+    }
 
-        if remittance.status != RemittanceStatus::Pending {
-            return Err(ContractError::InvalidStatus);
+    /// Requires every batch entry to carry the agent's `approve_settlement`
+    /// when `true`.
+    pub fn set_batch_approval_required(env: Env, required: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let previous = is_batch_agent_approval_required(&env);
+        set_batch_agent_approval_required(&env, required);
+        record_config_change(&env, "batch_approval_required", admin.clone(), previous, required);
+        emit_batch_approval_mode_updated(&env, admin, required);
+
+        Ok(())
+    }
+
+    /// Turns corridor registry enforcement on or off. While on, remittances
+    /// and quotes must use a registered currency and country.
+    pub fn set_corridor_registry_enabled(env: Env, enabled: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let previous = is_corridor_registry_enabled(&env);
+        set_corridor_registry_enabled(&env, enabled);
+        record_config_change(&env, "corridor_registry_enabled", admin.clone(), previous, enabled);
+        emit_corridor_registry_toggled(&env, admin, enabled);
+
+        Ok(())
+    }
+
+    /// Adds (`allowed = true`) or removes an ISO 4217 currency code from the
+    /// corridor registry.
+    pub fn set_currency_allowed(env: Env, currency: String, allowed: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let currency = normalize_currency(&env, &currency)?;
+        let currencies = update_registry(get_allowed_currencies(&env), &currency, allowed);
+        let previous = get_allowed_currencies(&env);
+        set_allowed_currencies(&env, &currencies);
+        record_config_change(&env, "allowed_currencies", admin.clone(), previous, currencies);
+        emit_currency_registry_updated(&env, admin, currency, allowed);
+
+        Ok(())
+    }
+
+    /// Adds (`allowed = true`) or removes an ISO 3166-1 alpha-2 country code
+    /// from the corridor registry.
+    pub fn set_country_allowed(env: Env, country: String, allowed: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let country = normalize_country(&env, &country)?;
+        let countries = update_registry(get_allowed_countries(&env), &country, allowed);
+        let previous = get_allowed_countries(&env);
+        set_allowed_countries(&env, &countries);
+        record_config_change(&env, "allowed_countries", admin.clone(), previous, countries);
+        emit_country_registry_updated(&env, admin, country, allowed);
+
+        Ok(())
+    }
+
+    /// Configures per-sender rate limiting on remittance creation. `None`
+    /// removes all limits.
+    pub fn set_rate_limit(env: Env, config: Option<RateLimitConfig>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if matches!(&config, Some(c) if c.max_per_window > 0 && c.window_seconds == 0) {
+            return Err(ContractError::InvalidAmount);
         }
 
-        // Check for duplicate settlement execution
-        if has_settlement_hash(&env, remittance_id) {
-            return Err(ContractError::DuplicateSettlement);
+        let previous = get_rate_limit_config(&env);
+        set_rate_limit_config(&env, config.clone());
+        record_config_change(&env, "rate_limit", admin.clone(), previous, config.clone());
+        emit_rate_limit_updated(&env, admin, config);
+
+        Ok(())
+    }
+
+    /// Configures the settlement challenge period and the bond a Challenger
+    /// must post. `None` makes settlements final as soon as they complete.
+    pub fn set_challenge_config(env: Env, config: Option<ChallengeConfig>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if matches!(&config, Some(c) if c.bond < 0) {
+            return Err(ContractError::InvalidAmount);
         }
 
-        // Check if settlement has expired
-        if let Some(expiry_time) = remittance.expiry {
-            let current_time = env.ledger().timestamp();
-            if current_time > expiry_time {
-                return Err(ContractError::SettlementExpired);
-            }
+        let previous = get_challenge_config(&env);
+        set_challenge_config(&env, config.clone());
+        record_config_change(&env, "challenge_config", admin.clone(), previous, config.clone());
+        emit_challenge_config_updated(&env, admin, config);
+
+        Ok(())
+    }
+
+    /// Challenges a completed settlement within the challenge period,
+    /// posting the configured bond. The settlement cannot become final until
+    /// an Arbitrator rules. Each settlement can be challenged once.
+    pub fn challenge_settlement(
+        env: Env,
+        challenger: Address,
+        remittance_id: u64,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        challenger.require_auth();
+        if !has_role(&env, &Role::Challenger, &challenger) {
+            return Err(ContractError::MissingRole);
         }
 
-        // Validate the agent address before transfer
-        validate_address(&remittance.agent)?;
+        let config = get_challenge_config(&env).ok_or(ContractError::InvalidStatus)?;
+        let remittance = get_remittance(&env, remittance_id)?;
+        let proof = get_settlement_proof(&env, remittance_id).ok_or(ContractError::InvalidStatus)?;
+        let deadline = proof.settled_at.saturating_add(config.period_seconds);
+        if remittance.status != RemittanceStatus::Completed
+            || env.ledger().timestamp() > deadline
+            || get_settlement_challenge(&env, remittance_id).is_some()
+        {
+            return Err(ContractError::InvalidStatus);
+        }
 
-        let payout_amount = remittance
-            .amount
-            .checked_sub(remittance.fee)
-            .ok_or(ContractError::Overflow)?;
+        if config.bond > 0 {
+            let usdc_token = get_usdc_token(&env)?;
+            transfer_token(&env, &usdc_token, &challenger, &env.current_contract_address(), config.bond, TokenTransferStage::Deposit)?;
 
-        let usdc_token = get_usdc_token(&env)?;
-        let token_client = token::Client::new(&env, &usdc_token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &remittance.agent,
-            &payout_amount,
-        );
+            let held = get_held_challenge_bonds(&env)
+                .checked_add(config.bond)
+                .ok_or(ContractError::Overflow)?;
+            set_held_challenge_bonds(&env, held);
+        }
 
-        let current_fees = get_accumulated_fees(&env)?;
-        let new_fees = current_fees
-            .checked_add(remittance.fee)
-            .ok_or(ContractError::Overflow)?;
-        set_accumulated_fees(&env, new_fees);
+        let challenge = SettlementChallenge {
+            challenger: challenger.clone(),
+            evidence_hash: evidence_hash.clone(),
+            bond: config.bond,
+            challenged_at: env.ledger().timestamp(),
+            resolved: false,
+            upheld: false,
+        };
+        set_settlement_challenge(&env, remittance_id, &challenge);
+        emit_settlement_challenged(&env, remittance_id, challenger, evidence_hash, config.bond);
+
+        Ok(())
+    }
 
-        remittance.status = RemittanceStatus::Completed;
-        set_remittance(&env, remittance_id, &remittance);
+    /// Rules on an open settlement challenge. An upheld challenge returns
+    /// the bond to the challenger and leaves the settlement permanently
+    /// unfinalized; a rejected one forfeits the bond to platform fees.
+    /// Upholding a challenge on an insured remittance also compensates its
+    /// sender from the insurance fund. Callable by the admin or an
+    /// Arbitrator.
+    pub fn resolve_challenge(env: Env, arbitrator: Address, remittance_id: u64, upheld: bool) -> Result<(), ContractError> {
+        require_admin_or_role(&env, &arbitrator, &Role::Arbitrator)?;
 
-        // Mark settlement as executed to prevent duplicates
-        set_settlement_hash(&env, remittance_id);
+        let mut challenge = get_settlement_challenge(&env, remittance_id)
+            .filter(|challenge| !challenge.resolved)
+            .ok_or(ContractError::InvalidStatus)?;
 
-        emit_remittance_completed(&env, remittance_id, remittance.sender.clone(), remittance.agent.clone(), usdc_token.clone(), payout_amount);
-        
-        // Emit settlement completed event with final executed values
-        emit_settlement_completed(&env, remittance.sender.clone(), remittance.agent.clone(), usdc_token.clone(), payout_amount);
+        if challenge.bond > 0 {
+            let held = get_held_challenge_bonds(&env)
+                .checked_sub(challenge.bond)
+                .ok_or(ContractError::Overflow)?;
+            set_held_challenge_bonds(&env, held);
 
-        log_confirm_payout(&env, remittance_id, payout_amount);
+            if upheld {
+                let usdc_token = get_usdc_token(&env)?;
+                transfer_token(&env, &usdc_token, &env.current_contract_address(), &challenge.challenger, challenge.bond, TokenTransferStage::Refund)?;
+            } else {
+                let mut counters = get_counters(&env)?;
+                counters.accumulated_fees = counters
+                    .accumulated_fees
+                    .checked_add(challenge.bond)
+                    .ok_or(ContractError::Overflow)?;
+                set_counters(&env, &counters);
+            }
+        }
+
+        challenge.resolved = true;
+        challenge.upheld = upheld;
+        set_settlement_challenge(&env, remittance_id, &challenge);
+        if upheld {
+            pay_insurance_claim(&env, &get_remittance(&env, remittance_id)?, &arbitrator)?;
+        }
+        emit_challenge_resolved(&env, remittance_id, arbitrator, upheld, challenge.bond);
 
         Ok(())
     }
 
-    pub fn cancel_remittance(env: Env, remittance_id: u64) -> Result<(), ContractError> {
-        let mut remittance = get_remittance(&env, remittance_id)?;
+    /// Closes the current accounting epoch and starts the next one, so
+    /// settlements from now on are attributed to it. The closed epoch's
+    /// report is final. Callable by the admin or an EpochKeeper.
+    pub fn advance_epoch(env: Env, caller: Address) -> Result<u64, ContractError> {
+        require_admin_or_role(&env, &caller, &Role::EpochKeeper)?;
 
-        remittance.sender.require_auth();
+        let now = env.ledger().timestamp();
+        let epoch = get_current_epoch(&env);
+        let mut report = current_epoch_report(&env, epoch);
+        report.closed_at = now;
+        set_epoch_report(&env, &report);
 
-        if remittance.status != RemittanceStatus::Pending {
+        let next = epoch.checked_add(1).ok_or(ContractError::Overflow)?;
+        set_current_epoch(&env, next);
+        set_epoch_report(&env, &EpochReport {
+            epoch: next,
+            started_at: now,
+            closed_at: 0,
+            remittances: 0,
+            fees: 0,
+            protocol_fees: 0,
+            rebates: 0,
+        });
+        emit_epoch_closed(&env, caller, report);
+
+        Ok(next)
+    }
+
+    pub fn get_current_epoch(env: Env) -> u64 {
+        get_current_epoch(&env)
+    }
+
+    /// Fee accounting for `epoch`, running totals while it is current.
+    /// Fails with `InvalidStatus` for epochs not yet started.
+    pub fn get_epoch_report(env: Env, epoch: u64) -> Result<EpochReport, ContractError> {
+        if epoch > get_current_epoch(&env) {
             return Err(ContractError::InvalidStatus);
         }
+        Ok(current_epoch_report(&env, epoch))
+    }
 
-        let usdc_token = get_usdc_token(&env)?;
-        let token_client = token::Client::new(&env, &usdc_token);
-        token_client.transfer(
-            &env.current_contract_address(),
-            &remittance.sender,
-            &remittance.amount,
-        );
+    /// Opens a chargeback on a payout held in its corridor's chargeback
+    /// window. The payout stays held until an Arbitrator rules through
+    /// `resolve_chargeback`. Requires sender auth.
+    pub fn open_chargeback(env: Env, remittance_id: u64) -> Result<(), ContractError> {
+        let remittance = get_remittance(&env, remittance_id)?;
 
-        remittance.status = RemittanceStatus::Cancelled;
-        set_remittance(&env, remittance_id, &remittance);
+        remittance.sender.require_auth();
+        increment_nonce(&env, &remittance.sender);
 
-        emit_remittance_cancelled(&env, remittance_id, remittance.sender.clone(), remittance.agent.clone(), usdc_token.clone(), remittance.amount);
+        let mut held = get_held_payout(&env, remittance_id).ok_or(ContractError::InvalidStatus)?;
+        if held.disputed || env.ledger().timestamp() >= held.release_at {
+            return Err(ContractError::InvalidStatus);
+        }
 
-        log_cancel_remittance(&env, remittance_id);
+        held.disputed = true;
+        set_held_payout(&env, remittance_id, Some(&held));
+        emit_chargeback_opened(&env, remittance_id, remittance.sender, held.amount);
 
         Ok(())
     }
 
-    pub fn withdraw_fees(env: Env, to: Address) -> Result<(), ContractError> {
-        let admin = get_admin(&env)?;
-        admin.require_auth();
+    /// Rules on a chargeback: `refund` returns the held payout to the
+    /// sender, otherwise it is released to its payee. Callable by the admin
+    /// or an Arbitrator.
+    pub fn resolve_chargeback(env: Env, arbitrator: Address, remittance_id: u64, refund: bool) -> Result<(), ContractError> {
+        require_admin_or_role(&env, &arbitrator, &Role::Arbitrator)?;
 
-        // Validate the recipient address
-        validate_address(&to)?;
+        let held = get_held_payout(&env, remittance_id)
+            .filter(|held| held.disputed)
+            .ok_or(ContractError::InvalidStatus)?;
+        let remittance = get_remittance(&env, remittance_id)?;
+        let usdc_token = get_usdc_token(&env)?;
+
+        release_held_payout(&env, remittance_id, &held)?;
+        if refund {
+            transfer_token(&env, &usdc_token, &env.current_contract_address(), &remittance.sender, held.amount, TokenTransferStage::Refund)?;
+            emit_held_payout_released(&env, remittance_id, remittance.sender, held.amount, true);
+        } else {
+            pay_held_payout(&env, &usdc_token, &remittance, &held)?;
+            emit_held_payout_released(&env, remittance_id, held.payee, held.amount, false);
+        }
 
-        let fees = get_accumulated_fees(&env)?;
+        Ok(())
+    }
 
-        if fees <= 0 {
-            return Err(ContractError::NoFeesToWithdraw);
+    /// Pays out a held payout whose chargeback window has closed without a
+    /// dispute. Callable by anyone.
+    pub fn release_matured(env: Env, remittance_id: u64) -> Result<(), ContractError> {
+        if is_paused(&env) {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let held = get_held_payout(&env, remittance_id).ok_or(ContractError::InvalidStatus)?;
+        if held.disputed || env.ledger().timestamp() < held.release_at {
+            return Err(ContractError::InvalidStatus);
         }
 
+        let remittance = get_remittance(&env, remittance_id)?;
         let usdc_token = get_usdc_token(&env)?;
-        let token_client = token::Client::new(&env, &usdc_token);
-        token_client.transfer(&env.current_contract_address(), &to, &fees);
+        release_held_payout(&env, remittance_id, &held)?;
+        pay_held_payout(&env, &usdc_token, &remittance, &held)?;
+        emit_held_payout_released(&env, remittance_id, held.payee, held.amount, false);
+
+        Ok(())
+    }
 
-        set_accumulated_fees(&env, 0);
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_held_payout(env: Env, remittance_id: u64) -> Result<Option<HeldPayout>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_held_payout(&env, remittance_id))
+    }
 
-        emit_fees_withdrawn(&env, admin.clone(), to.clone(), usdc_token.clone(), fees);
+    /// Sets the premium charged on remittances created with `FLAG_INSURED`,
+    /// in basis points of the amount. Zero stops offering insurance. Fails
+    /// with `InvalidStatus` while a timelock delay is set; use
+    /// `queue_fee_update` then.
+    pub fn set_insurance_premium_bps(env: Env, premium_bps: u32) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
 
-        log_withdraw_fees(&env, &to, fees);
+        if get_timelock_delay(&env).is_some() {
+            return Err(ContractError::InvalidStatus);
+        }
+        validate_timelocked_update(&TimelockedUpdate::InsurancePremium(premium_bps))?;
 
+        apply_insurance_premium(&env, admin, premium_bps);
         Ok(())
     }
 
-    pub fn get_remittance(env: Env, remittance_id: u64) -> Result<Remittance, ContractError> {
-        get_remittance(&env, remittance_id)
-    }
+    /// Configures referral rewards: the share of the platform fee on a
+    /// referred sender's settlements paid to their referrer, and for how
+    /// long after the referral. `None` stops new rewards from accruing.
+    pub fn set_referral_config(env: Env, config: Option<ReferralConfig>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
 
-    pub fn get_settlement(env: Env, id: u64) -> Result<Remittance, ContractError> {
-        get_remittance(&env, id)
+        if matches!(&config, Some(c) if c.share_bps > 10000) {
+            return Err(ContractError::InvalidFeeBps);
+        }
+
+        let previous = get_referral_config(&env);
+        set_referral_config(&env, config.clone());
+        record_config_change(&env, "referral_config", admin.clone(), previous, config.clone());
+        emit_referral_config_updated(&env, admin, config);
+
+        Ok(())
     }
 
-    pub fn get_accumulated_fees(env: Env) -> Result<i128, ContractError> {
-        get_accumulated_fees(&env)
+    /// Caps the value `sender` may send per UTC day. Lowering applies at
+    /// once; raising or removing (`None`) applies after
+    /// `SELF_CAP_RAISE_DELAY_SECONDS`, so a stolen key cannot lift the cap
+    /// straight away.
+    pub fn set_my_daily_cap(env: Env, sender: Address, cap: Option<i128>) -> Result<SelfCap, ContractError> {
+        sender.require_auth();
+        increment_nonce(&env, &sender);
+
+        let updated = update_self_cap(&env, get_self_daily_cap(&env, &sender), cap)?;
+        set_self_daily_cap(&env, &sender, &updated);
+        emit_self_cap_updated(&env, sender, symbol_short!("daily"), updated.clone());
+
+        Ok(updated)
     }
 
-    pub fn is_agent_registered(env: Env, agent: Address) -> bool {
-        is_agent_registered(&env, &agent)
+    /// Caps the amount of any single remittance from `sender`, with the same
+    /// delay on raises as `set_my_daily_cap`.
+    pub fn set_my_max_tx(env: Env, sender: Address, cap: Option<i128>) -> Result<SelfCap, ContractError> {
+        sender.require_auth();
+        increment_nonce(&env, &sender);
+
+        let updated = update_self_cap(&env, get_self_max_tx(&env, &sender), cap)?;
+        set_self_max_tx(&env, &sender, &updated);
+        emit_self_cap_updated(&env, sender, symbol_short!("max_tx"), updated.clone());
+
+        Ok(updated)
     }
 
-    pub fn get_platform_fee_bps(env: Env) -> Result<u32, ContractError> {
-        get_platform_fee_bps(&env)
+    /// Records who referred `sender`. Can only be set once, and a sender
+    /// cannot refer themselves.
+    pub fn set_referrer(env: Env, sender: Address, referrer: Address) -> Result<(), ContractError> {
+        sender.require_auth();
+        increment_nonce(&env, &sender);
+
+        if referrer == sender {
+            return Err(ContractError::InvalidAddress);
+        }
+        if get_referral(&env, &sender).is_some() {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let referral = Referral {
+            referrer: referrer.clone(),
+            referred_at: env.ledger().timestamp(),
+        };
+        set_referral(&env, &sender, &referral);
+        emit_referrer_set(&env, sender, referrer);
+
+        Ok(())
     }
 
-    pub fn pause(env: Env) -> Result<(), ContractError> {
-        let admin = get_admin(&env)?;
-        admin.require_auth();
+    /// Sets the expiry window applied to remittances created without an
+    /// explicit `expiry`. `None` leaves such remittances open-ended.
+    pub fn set_default_expiry_seconds(env: Env, default_expiry_seconds: Option<u64>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
 
-        set_paused(&env, true);
-        emit_paused(&env, admin);
+        if default_expiry_seconds == Some(0) {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let previous = get_default_expiry_seconds(&env);
+        set_default_expiry_seconds(&env, default_expiry_seconds);
+        record_config_change(&env, "default_expiry_seconds", admin.clone(), previous, default_expiry_seconds);
+        emit_default_expiry_updated(&env, admin, default_expiry_seconds);
 
         Ok(())
     }
 
-    pub fn unpause(env: Env) -> Result<(), ContractError> {
-        let admin = get_admin(&env)?;
-        admin.require_auth();
+    /// Sets how long before its expiry a Pending remittance is flagged with a
+    /// `remit/expiring` event, giving senders and agents notice before it
+    /// becomes refund-only. `None` turns the warnings off.
+    pub fn set_expiry_warning_seconds(env: Env, warning_seconds: Option<u64>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
 
-        set_paused(&env, false);
-        emit_unpaused(&env, admin);
+        if warning_seconds == Some(0) {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let previous = get_expiry_warning_seconds(&env);
+        set_expiry_warning_seconds(&env, warning_seconds);
+        record_config_change(&env, "expiry_warning_seconds", admin, previous, warning_seconds);
 
         Ok(())
     }
 
-    pub fn is_paused(env: Env) -> bool {
-        is_paused(&env)
+    pub fn get_expiry_warning_seconds(env: Env) -> Option<u64> {
+        get_expiry_warning_seconds(&env)
+    }
+
+    /// Adds or removes a token from the whitelist. Whitelisting probes the
+    /// token's name, symbol and decimals and caches them for
+    /// `get_token_info`; an address that does not answer all three is
+    /// rejected with `NotATokenContract`.
+    pub fn set_token_whitelisted(env: Env, token: Address, whitelisted: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let info = match whitelisted {
+            true => Some(probe_token(&env, &token)?),
+            false => None,
+        };
+        let previous = (token.clone(), get_token_info(&env, &token));
+        set_token_info(&env, &token, info.as_ref());
+        record_config_change(&env, "token_whitelist", admin.clone(), previous, (token.clone(), info.clone()));
+        emit_token_whitelist_updated(&env, admin, token, info);
+
+        Ok(())
     }
+
+    /// Overrides the platform fee for remittances denominated in `token`.
+    /// `None` removes the override so the global platform fee applies.
+    /// Fails with `InvalidStatus` while a timelock delay is set; use
+    /// `queue_fee_update` then.
+    pub fn set_token_fee_bps(env: Env, token: Address, fee_bps: Option<u32>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if get_timelock_delay(&env).is_some() {
+            return Err(ContractError::InvalidStatus);
+        }
+        validate_timelocked_update(&TimelockedUpdate::TokenFee(token.clone(), fee_bps))?;
+
+        apply_token_fee_bps(&env, admin, token, fee_bps);
+        Ok(())
+    }
+
+    /// Sets the default destination for `forward_fees`. `None` removes it,
+    /// leaving only per-token treasuries. Fails with `InvalidStatus` while
+    /// a timelock delay is set; use `queue_fee_update` then.
+    pub fn set_treasury(env: Env, treasury: Option<Address>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if get_timelock_delay(&env).is_some() {
+            return Err(ContractError::InvalidStatus);
+        }
+        validate_timelocked_update(&TimelockedUpdate::Treasury(treasury.clone()))?;
+
+        apply_treasury(&env, admin, treasury);
+        Ok(())
+    }
+
+    /// Routes fees collected in `token` to `treasury` instead of the default
+    /// treasury. `None` removes the override. Fails with `InvalidStatus`
+    /// while a timelock delay is set; use `queue_fee_update` then.
+    pub fn set_treasury_for_token(env: Env, token: Address, treasury: Option<Address>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if get_timelock_delay(&env).is_some() {
+            return Err(ContractError::InvalidStatus);
+        }
+        validate_timelocked_update(&TimelockedUpdate::TokenTreasury(token.clone(), treasury.clone()))?;
+
+        apply_token_treasury(&env, admin, token, treasury);
+        Ok(())
+    }
+
+    /// Delegates fee calculation to an external contract implementing
+    /// `compute_fees(sender, agent, amount, currency, country, token) ->
+    /// FeeBreakdown`. The strategy replaces the platform and token fee rates
+    /// for new remittances and quotes; `agent` is `None` for quotes. Fees
+    /// above `max_fee_bps` of the amount are rejected. `None` restores the
+    /// built-in calculation. Fails with `InvalidStatus` while a timelock
+    /// delay is set; use `queue_fee_update` then.
+    pub fn set_fee_strategy(env: Env, strategy: Option<FeeStrategy>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if get_timelock_delay(&env).is_some() {
+            return Err(ContractError::InvalidStatus);
+        }
+        if let Some(strategy) = &strategy {
+            validate_timelocked_update(&TimelockedUpdate::FeeStrategy(strategy.clone()))?;
+        }
+
+        apply_fee_strategy(&env, admin, strategy);
+        Ok(())
+    }
+
+    /// Sets the share of each settlement's platform fee that accrues to the
+    /// settling agent, in basis points of the fee. Fails with
+    /// `InvalidStatus` while a timelock delay is set; use
+    /// `queue_fee_update` then.
+    pub fn update_agent_rebate(env: Env, rebate_bps: u32) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if get_timelock_delay(&env).is_some() {
+            return Err(ContractError::InvalidStatus);
+        }
+        validate_timelocked_update(&TimelockedUpdate::AgentRebate(rebate_bps))?;
+
+        apply_agent_rebate(&env, admin, rebate_bps)
+    }
+
+    /// Sets the surcharge charged on remittances paid out by `agent`, in
+    /// basis points of the amount. It is deducted from the payout on top of
+    /// the platform fee and accrues to the agent's claimable balance rather
+    /// than to platform fees. Zero removes the surcharge. Fails with
+    /// `InvalidStatus` while a timelock delay is set; use
+    /// `queue_fee_update` then.
+    pub fn set_agent_surcharge_bps(env: Env, agent: Address, surcharge_bps: u32) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if get_timelock_delay(&env).is_some() {
+            return Err(ContractError::InvalidStatus);
+        }
+        validate_timelocked_update(&TimelockedUpdate::AgentSurcharge(agent.clone(), surcharge_bps))?;
+
+        apply_agent_surcharge(&env, admin, agent, surcharge_bps)
+    }
+
+    /// Opens or closes a corridor to new remittances. Remittances already
+    /// created on a closed corridor can still settle or be refunded.
+    pub fn set_corridor_enabled(env: Env, currency: String, country: String, enabled: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+
+        let previous = (currency.clone(), country.clone(), is_corridor_enabled(&env, &currency, &country));
+        set_corridor_enabled(&env, &currency, &country, enabled);
+        record_config_change(&env, "corridor_enabled", admin.clone(), previous, (currency.clone(), country.clone(), enabled));
+        emit_corridor_enabled_updated(&env, admin, currency, country, enabled);
+
+        Ok(())
+    }
+
+    /// Sets the maximum seconds from creation to settlement for a corridor.
+    /// `None` removes the SLA.
+    pub fn set_corridor_sla(env: Env, currency: String, country: String, max_seconds: Option<u64>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+
+        let previous = (currency.clone(), country.clone(), get_corridor_sla(&env, &currency, &country));
+        set_corridor_sla(&env, &currency, &country, max_seconds);
+        record_config_change(&env, "corridor_sla", admin.clone(), previous, (currency.clone(), country.clone(), max_seconds));
+        emit_corridor_sla_updated(&env, admin, currency, country, max_seconds);
+
+        Ok(())
+    }
+
+    /// Limits how far ahead a corridor's remittances may expire, checked at
+    /// creation against the supplied or defaulted expiry. Too-short expiries
+    /// fail with `ExpiryTooShort`; too-long or open-ended ones with
+    /// `ExpiryTooLong`. Passing `None` for both removes the bounds.
+    pub fn set_corridor_expiry_bounds(
+        env: Env,
+        currency: String,
+        country: String,
+        min_seconds: Option<u64>,
+        max_seconds: Option<u64>,
+    ) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if matches!((min_seconds, max_seconds), (Some(min), Some(max)) if min > max) || max_seconds == Some(0) {
+            return Err(ContractError::InvalidAmount);
+        }
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+
+        let bounds = (min_seconds.is_some() || max_seconds.is_some()).then_some(ExpiryBounds { min_seconds, max_seconds });
+        let previous = (currency.clone(), country.clone(), get_corridor_expiry_bounds(&env, &currency, &country));
+        set_corridor_expiry_bounds(&env, &currency, &country, bounds.as_ref());
+        record_config_change(&env, "corridor_expiry_bounds", admin.clone(), previous, (currency.clone(), country.clone(), bounds.clone()));
+        emit_corridor_expiry_bounds_updated(&env, admin, currency, country, bounds);
+
+        Ok(())
+    }
+
+    pub fn get_corridor_expiry_bounds(env: Env, currency: String, country: String) -> Result<Option<ExpiryBounds>, ContractError> {
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+        Ok(get_corridor_expiry_bounds(&env, &currency, &country))
+    }
+
+    /// Caps how much each agent may receive in payouts from the corridor
+    /// over the last `RECEIVE_LIMIT_WINDOW_DAYS` UTC days, counting the
+    /// current day. Settlements that would exceed it fail with
+    /// `ExposureCapExceeded`. `None` removes the limit.
+    pub fn set_corridor_receive_limit(env: Env, currency: String, country: String, limit: Option<i128>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if matches!(limit, Some(limit) if limit <= 0) {
+            return Err(ContractError::InvalidAmount);
+        }
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+
+        let previous = (currency.clone(), country.clone(), get_corridor_receive_limit(&env, &currency, &country));
+        set_corridor_receive_limit(&env, &currency, &country, limit);
+        record_config_change(&env, "corridor_receive_limit", admin.clone(), previous, (currency.clone(), country.clone(), limit));
+        emit_corridor_receive_limit_updated(&env, admin, currency, country, limit);
+
+        Ok(())
+    }
+
+    /// Marks a corridor as instant with a chargeback window of
+    /// `window_seconds`, or clears the flag with `None`. Remittances in an
+    /// instant corridor settle without waiting for dual confirmation, but
+    /// the agent's payout is held until the window closes so the sender can
+    /// open a chargeback.
+    pub fn set_corridor_instant(env: Env, currency: String, country: String, window_seconds: Option<u64>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+
+        let previous = (currency.clone(), country.clone(), get_corridor_chargeback_window(&env, &currency, &country));
+        set_corridor_chargeback_window(&env, &currency, &country, window_seconds);
+        record_config_change(&env, "corridor_instant", admin.clone(), previous, (currency.clone(), country.clone(), window_seconds));
+        emit_corridor_instant_updated(&env, admin, currency, country, window_seconds);
+
+        Ok(())
+    }
+
+    /// Chooses how fees are charged on the corridor's remittances in netting
+    /// batches. Under `Net`, when a batch carries flows in both directions
+    /// between the same sender and agent, their fees are charged on the
+    /// netted amount instead of the gross.
+    pub fn set_corridor_fee_mode(env: Env, currency: String, country: String, mode: NettingFeeMode) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+
+        let previous = (currency.clone(), country.clone(), get_corridor_fee_mode(&env, &currency, &country));
+        set_corridor_fee_mode(&env, &currency, &country, &mode);
+        record_config_change(&env, "corridor_fee_mode", admin.clone(), previous, (currency.clone(), country.clone(), mode.clone()));
+        emit_corridor_fee_mode_updated(&env, admin, currency, country, mode);
+
+        Ok(())
+    }
+
+    /// Sets the share of the platform fee paid to the sender when a
+    /// remittance settles after its corridor SLA, in basis points of the fee.
+    /// Fails with `InvalidStatus` while a timelock delay is set; use
+    /// `queue_fee_update` then.
+    pub fn set_sla_penalty_bps(env: Env, penalty_bps: u32) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if get_timelock_delay(&env).is_some() {
+            return Err(ContractError::InvalidStatus);
+        }
+        validate_timelocked_update(&TimelockedUpdate::SlaPenalty(penalty_bps))?;
+
+        apply_sla_penalty(&env, admin, penalty_bps)
+    }
+
+    /// Sets the reserve the contract must hold beyond exact backing of
+    /// Pending remittances, in basis points of the total locked value.
+    /// Fee withdrawals that would dip into the reserve are rejected.
+    pub fn set_reserve_bps(env: Env, reserve_bps: u32) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if reserve_bps > 10000 {
+            return Err(ContractError::InvalidFeeBps);
+        }
+
+        let old_reserve_bps = get_reserve_bps(&env);
+        set_reserve_bps(&env, reserve_bps);
+        record_config_change(&env, "reserve_bps", admin.clone(), old_reserve_bps, reserve_bps);
+        emit_reserve_updated(&env, admin, old_reserve_bps, reserve_bps);
+
+        Ok(())
+    }
+
+    /// Creates a Pending remittance. A non-zero `priority_tip` is deposited
+    /// with the amount and orders the remittance in the settlement queue,
+    /// as if added with `add_priority_tip`.
+    pub fn create_remittance(
+        env: Env,
+        sender: Address,
+        agent: Address,
+        amount: i128,
+        currency: String,
+        country: String,
+        expiry: Option<u64>,
+        external_ref: Option<BytesN<32>>,
+        tags: Vec<Symbol>,
+        flags: u32,
+        priority_tip: i128,
+    ) -> Result<u64, ContractError> {
+        sender.require_auth();
+        increment_nonce(&env, &sender);
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+        let fee = compute_fee(&env, &sender, Some(&agent), amount, &currency, &country)?;
+        let agent_surcharge = calculate_fee(amount, get_agent_surcharge_bps(&env, &agent))?;
+
+        open_remittance(
+            &env,
+            sender,
+            agent,
+            amount,
+            fee,
+            agent_surcharge,
+            currency,
+            country,
+            expiry,
+            external_ref,
+            tags,
+            flags,
+            priority_tip,
+        )
+    }
+
+    /// Locks the current platform fee for `amount` on the given corridor and
+    /// returns the quote. When `agent` is given the quote also shows and
+    /// locks that agent's surcharge. `fee_bps` is the rate the quoted fee
+    /// works out to on `amount`, rounded down, so it matches the fee whether
+    /// it came from the platform rate or a fee strategy. The quote can be
+    /// redeemed with `create_remittance_from_quote` until `expires_at`.
+    pub fn create_quote(
+        env: Env,
+        sender: Address,
+        currency: String,
+        country: String,
+        amount: i128,
+        agent: Option<Address>,
+    ) -> Result<Quote, ContractError> {
+        sender.require_auth();
+        increment_nonce(&env, &sender);
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+        check_corridor_open(&env, &currency, &country)?;
+
+        let fee = compute_fee(&env, &sender, agent.as_ref(), amount, &currency, &country)?;
+        let fee_bps = fee
+            .checked_mul(10000)
+            .map(|scaled| scaled / amount)
+            .and_then(|bps| u32::try_from(bps).ok())
+            .ok_or(ContractError::Overflow)?;
+        let agent_surcharge = match &agent {
+            Some(agent) => calculate_fee(amount, get_agent_surcharge_bps(&env, agent))?,
+            None => 0,
+        };
+        let payout_amount = amount
+            .checked_sub(fee)
+            .and_then(|payout| payout.checked_sub(agent_surcharge))
+            .ok_or(ContractError::Overflow)?;
+        let expires_at = env
+            .ledger()
+            .timestamp()
+            .checked_add(QUOTE_VALIDITY_SECONDS)
+            .ok_or(ContractError::Overflow)?;
+
+        let quote_id = get_quote_counter(&env)
+            .checked_add(1)
+            .ok_or(ContractError::Overflow)?;
+
+        let quote = Quote {
+            id: quote_id,
+            sender: sender.clone(),
+            amount,
+            currency: currency.clone(),
+            country: country.clone(),
+            fee_bps,
+            fee,
+            agent,
+            agent_surcharge,
+            payout_amount,
+            expires_at,
+        };
+
+        set_quote(&env, quote_id, &quote);
+        set_quote_counter(&env, quote_id);
+
+        emit_quote_created(&env, quote_id, sender, amount, fee, currency, country, expires_at);
+
+        Ok(quote)
+    }
+
+    /// Creates a remittance like `create_remittance` and links it under
+    /// `parent_id`, e.g. the last-mile leg of a payment routed through a hub
+    /// agent. Settling the child fails with `InvalidStatus` until its parent
+    /// is Completed. Linking fails with `InvalidStatus` unless the parent is
+    /// Pending, held for review or Completed and has fewer than
+    /// `MAX_LINKED_CHILDREN` children, and with `InvalidAddress` unless
+    /// `sender` is the parent's sender or its agent (the hub forwarding it).
+    pub fn create_linked_remittance(
+        env: Env,
+        parent_id: u64,
+        sender: Address,
+        agent: Address,
+        amount: i128,
+        currency: String,
+        country: String,
+        expiry: Option<u64>,
+        external_ref: Option<BytesN<32>>,
+        tags: Vec<Symbol>,
+        flags: u32,
+    ) -> Result<u64, ContractError> {
+        let parent = get_remittance(&env, parent_id)?;
+        if sender != parent.sender && sender != parent.agent {
+            return Err(ContractError::InvalidAddress);
+        }
+        if !matches!(
+            parent.status,
+            RemittanceStatus::Pending | RemittanceStatus::PendingReview | RemittanceStatus::Completed
+        ) || get_remittance_children(&env, parent_id).len() >= MAX_LINKED_CHILDREN
+        {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let remittance_id = Self::create_remittance(
+            env.clone(),
+            sender,
+            agent,
+            amount,
+            currency,
+            country,
+            expiry,
+            external_ref,
+            tags,
+            flags,
+            0,
+        )?;
+
+        link_remittance(&env, parent_id, remittance_id);
+        emit_remittance_linked(&env, parent_id, remittance_id);
+
+        Ok(remittance_id)
+    }
+
+    /// Locks the exchange rate for a remittance's fiat payout: the
+    /// `destination_amount` in minor units of the remittance currency and
+    /// the `rate` it was derived from, scaled by `FX_RATE_SCALE`.
+    /// `destination_amount` must equal the token payout converted at `rate`,
+    /// rounded down, and becomes the remittance's `fiat_amount`. The lock is
+    /// recorded on the settlement proof and in a `settle/fx` event. Allowed
+    /// once, before settlement. Requires sender auth.
+    pub fn lock_fx_rate(env: Env, remittance_id: u64, destination_amount: i128, rate: i128) -> Result<(), ContractError> {
+        let mut remittance = get_remittance(&env, remittance_id)?;
+
+        remittance.sender.require_auth();
+        increment_nonce(&env, &remittance.sender);
+
+        if destination_amount <= 0 || rate <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if !matches!(remittance.status, RemittanceStatus::Pending | RemittanceStatus::PendingReview)
+            || get_fx_lock(&env, remittance_id).is_some()
+        {
+            return Err(ContractError::InvalidStatus);
+        }
+        let payout_amount = net_amount(&remittance)?;
+        if fiat_amount(payout_amount, rate)? != destination_amount {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let fx_lock = FxLock {
+            destination_amount,
+            rate,
+            locked_at: env.ledger().timestamp(),
+        };
+        set_fx_lock(&env, remittance_id, &fx_lock);
+        remittance.fiat_amount = Some(destination_amount);
+        save_remittance(&env, &remittance);
+        emit_fx_locked(&env, remittance_id, remittance.sender, payout_amount, fx_lock);
+
+        Ok(())
+    }
+
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_fx_lock(env: Env, remittance_id: u64) -> Result<Option<FxLock>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_fx_lock(&env, remittance_id))
+    }
+
+    /// Creates a remittance charged at the fee locked in `quote_id`, even if
+    /// the platform fee changed after the quote was issued. A quote issued
+    /// for an agent must be redeemed with that agent and keeps its quoted
+    /// surcharge; otherwise the agent's current surcharge applies. Each quote
+    /// can be redeemed once. `priority_tip` works as in `create_remittance`.
+    pub fn create_remittance_from_quote(
+        env: Env,
+        quote_id: u64,
+        agent: Address,
+        expiry: Option<u64>,
+        external_ref: Option<BytesN<32>>,
+        tags: Vec<Symbol>,
+        flags: u32,
+        priority_tip: i128,
+    ) -> Result<u64, ContractError> {
+        let quote = get_quote(&env, quote_id)?;
+
+        quote.sender.require_auth();
+        increment_nonce(&env, &quote.sender);
+
+        if env.ledger().timestamp() > quote.expires_at {
+            return Err(ContractError::QuoteExpired);
+        }
+
+        let agent_surcharge = match &quote.agent {
+            Some(quoted) if *quoted != agent => return Err(ContractError::InvalidAddress),
+            Some(_) => quote.agent_surcharge,
+            None => calculate_fee(quote.amount, get_agent_surcharge_bps(&env, &agent))?,
+        };
+
+        remove_quote(&env, quote_id);
+
+        open_remittance(
+            &env,
+            quote.sender,
+            agent,
+            quote.amount,
+            quote.fee,
+            agent_surcharge,
+            quote.currency,
+            quote.country,
+            expiry,
+            external_ref,
+            tags,
+            flags,
+            priority_tip,
+        )
+    }
+
+    /// Adds a priority tip to a Pending remittance. Tips are held with the
+    /// remittance, move to platform fees on settlement and are refunded on
+    /// cancellation.
+    pub fn add_priority_tip(env: Env, remittance_id: u64, tip: i128) -> Result<(), ContractError> {
+        let mut remittance = get_remittance(&env, remittance_id)?;
+
+        remittance.sender.require_auth();
+        increment_nonce(&env, &remittance.sender);
+
+        if tip <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if remittance.status != RemittanceStatus::Pending {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        remittance.priority_tip = remittance
+            .priority_tip
+            .checked_add(tip)
+            .ok_or(ContractError::Overflow)?;
+
+        let usdc_token = get_usdc_token(&env)?;
+        transfer_token(&env, &usdc_token, &remittance.sender, &env.current_contract_address(), tip, TokenTransferStage::Deposit)?;
+
+        let tips = get_pending_tip_total(&env)
+            .checked_add(tip)
+            .ok_or(ContractError::Overflow)?;
+        set_pending_tip_total(&env, tips);
+        save_remittance(&env, &remittance);
+
+        emit_priority_tip_added(&env, remittance_id, remittance.sender, tip, remittance.priority_tip);
+
+        Ok(())
+    }
+
+    /// Sender acknowledgement that the recipient was paid out, required
+    /// alongside the agent's confirmation for dual-confirmation remittances.
+    pub fn confirm_received(env: Env, remittance_id: u64) -> Result<(), ContractError> {
+        let mut remittance = get_remittance(&env, remittance_id)?;
+
+        remittance.sender.require_auth();
+        increment_nonce(&env, &remittance.sender);
+
+        if remittance.status != RemittanceStatus::Pending || remittance.sender_confirmed {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        remittance.sender_confirmed = true;
+        save_remittance(&env, &remittance);
+
+        emit_remittance_received(&env, remittance_id, remittance.sender);
+
+        Ok(())
+    }
+
+    /// Records an Authorizer's approval of a Pending remittance for payout.
+    pub fn authorize_remittance(env: Env, authorizer: Address, remittance_id: u64) -> Result<(), ContractError> {
+        authorizer.require_auth();
+
+        if !has_role(&env, &Role::Authorizer, &authorizer) {
+            return Err(ContractError::MissingRole);
+        }
+
+        let remittance = get_remittance(&env, remittance_id)?;
+        if remittance.status != RemittanceStatus::Pending
+            || get_remittance_authorizer(&env, remittance_id).is_some()
+        {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        set_remittance_authorizer(&env, remittance_id, &authorizer);
+        emit_remittance_authorized(&env, remittance_id, authorizer);
+
+        Ok(())
+    }
+
+    /// Runs the settlement checks of `confirm_payout` without settling.
+    /// Returns `None` when the remittance could be settled now, otherwise the
+    /// blocking error code, which is also emitted as a `settle/rejected`
+    /// event so indexers see blocked attempts that would otherwise revert.
+    pub fn simulate_settlement(env: Env, remittance_id: u64) -> Result<Option<u32>, ContractError> {
+        let remittance = get_remittance(&env, remittance_id)?;
+        let context = load_settlement_context(&env)?;
+
+        let outcome = if context.paused {
+            Err(ContractError::ContractPaused)
+        } else {
+            check_settleable(&env, &context, &remittance)
+        };
+
+        match outcome {
+            Ok(()) => Ok(None),
+            Err(error) => {
+                let reason = error as u32;
+                emit_settlement_rejected(&env, remittance_id, reason, remittance.currency, remittance.country);
+                Ok(Some(reason))
+            }
+        }
+    }
+
+    pub fn confirm_payout(env: Env, remittance_id: u64) -> Result<(), ContractError> {
+        let context = load_settlement_context(&env)?;
+        if context.paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let remittance = get_remittance(&env, remittance_id)?;
+        require_push_payout(&remittance)?;
+
+        remittance.agent.require_auth();
+
+        settle_payout(&env, &context, remittance)
+    }
+
+    /// Switches a Pending remittance to Pull mode, in which `recipient`
+    /// claims the payout with `claim_payout` instead of the agent receiving
+    /// it. `None` returns it to Push mode. Requires sender auth.
+    pub fn set_payout_recipient(env: Env, remittance_id: u64, recipient: Option<Address>) -> Result<(), ContractError> {
+        let mut remittance = get_remittance(&env, remittance_id)?;
+
+        remittance.sender.require_auth();
+        increment_nonce(&env, &remittance.sender);
+
+        if remittance.status != RemittanceStatus::Pending {
+            return Err(ContractError::InvalidStatus);
+        }
+        if let Some(recipient) = &recipient {
+            validate_address(recipient)?;
+        }
+
+        remittance.payout_mode = if recipient.is_some() { PayoutMode::Pull } else { PayoutMode::Push };
+        remittance.recipient = recipient.clone();
+        save_remittance(&env, &remittance);
+        emit_recipient_updated(&env, remittance_id, remittance.sender, recipient);
+
+        Ok(())
+    }
+
+    /// Settles a Pull mode remittance by paying the recipient directly.
+    /// The usual settlement checks apply. Requires recipient auth.
+    pub fn claim_payout(env: Env, remittance_id: u64) -> Result<(), ContractError> {
+        let context = load_settlement_context(&env)?;
+        if context.paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let remittance = get_remittance(&env, remittance_id)?;
+        let recipient = match (&remittance.payout_mode, &remittance.recipient) {
+            (PayoutMode::Pull, Some(recipient)) => recipient.clone(),
+            _ => return Err(ContractError::InvalidStatus),
+        };
+
+        recipient.require_auth();
+
+        let payout_amount = net_amount(&remittance)?;
+        settle_payout(&env, &context, remittance)?;
+        emit_payout_claimed(&env, remittance_id, recipient, context.usdc_token, payout_amount);
+
+        Ok(())
+    }
+
+    /// Settles like `confirm_payout`, but on the strength of an approval the
+    /// agent signed offline with its registered ed25519 key, so anyone can
+    /// submit it later. The approval must name this contract and remittance,
+    /// be used before its deadline and carry a nonce the agent has not used
+    /// before. An invalid signature aborts the call.
+    pub fn confirm_payout_with_sig(
+        env: Env,
+        remittance_id: u64,
+        approval_payload: PayoutApproval,
+        signature: BytesN<64>,
+    ) -> Result<(), ContractError> {
+        let context = load_settlement_context(&env)?;
+        if context.paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let remittance = get_remittance(&env, remittance_id)?;
+        require_push_payout(&remittance)?;
+        let agent = remittance.agent.clone();
+
+        let public_key = get_agent_signing_key(&env, &agent).ok_or(ContractError::AgentApprovalMissing)?;
+        verify_payout_approval(&env, &remittance, &public_key, &approval_payload, &signature)?;
+
+        settle_payout(&env, &context, remittance)?;
+        emit_payout_signed(&env, remittance_id, agent, approval_payload.nonce);
+
+        Ok(())
+    }
+
+    /// `confirm_payout_with_sig` signed by one of the agent's session keys
+    /// instead of its signing key. Fails with `AgentApprovalMissing` for an
+    /// unknown or revoked key, `SessionKeyExpired` once the session has
+    /// expired and `SessionCapExceeded` if the payout would take the session
+    /// past its cap. Approval nonces are shared with the signing key.
+    pub fn confirm_payout_with_session(
+        env: Env,
+        remittance_id: u64,
+        session_key: BytesN<32>,
+        approval_payload: PayoutApproval,
+        signature: BytesN<64>,
+    ) -> Result<(), ContractError> {
+        let context = load_settlement_context(&env)?;
+        if context.paused {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let remittance = get_remittance(&env, remittance_id)?;
+        require_push_payout(&remittance)?;
+        let agent = remittance.agent.clone();
+
+        let mut session = get_session_key(&env, &agent, &session_key).ok_or(ContractError::AgentApprovalMissing)?;
+        if env.ledger().timestamp() > session.expiry {
+            return Err(ContractError::SessionKeyExpired);
+        }
+        let payout_amount = net_amount(&remittance)?;
+        session.spent = session
+            .spent
+            .checked_add(payout_amount)
+            .ok_or(ContractError::Overflow)?;
+        if session.spent > session.cap {
+            return Err(ContractError::SessionCapExceeded);
+        }
+        verify_payout_approval(&env, &remittance, &session_key, &approval_payload, &signature)?;
+        set_session_key(&env, &agent, &session_key, Some(&session));
+
+        settle_payout(&env, &context, remittance)?;
+        emit_payout_session_signed(&env, remittance_id, agent, session_key, payout_amount);
+
+        Ok(())
+    }
+
+    /// Authorizes an ed25519 session key to settle the agent's remittances
+    /// through `confirm_payout_with_session`, up to `cap` in total payouts
+    /// until `expiry`. Registering an existing key again resets its usage.
+    /// Fails with `SessionKeyExpired` if `expiry` has already passed.
+    pub fn register_session_key(env: Env, agent: Address, key: BytesN<32>, cap: i128, expiry: u64) -> Result<(), ContractError> {
+        agent.require_auth();
+
+        if !is_agent_registered(&env, &agent) {
+            return Err(ContractError::AgentNotRegistered);
+        }
+        if cap <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+        if expiry <= env.ledger().timestamp() {
+            return Err(ContractError::SessionKeyExpired);
+        }
+
+        let session = SessionKey { cap, spent: 0, expiry };
+        set_session_key(&env, &agent, &key, Some(&session));
+        emit_session_key_registered(&env, agent, key, Some(session));
+
+        Ok(())
+    }
+
+    /// Revokes a session key before it expires.
+    pub fn revoke_session_key(env: Env, agent: Address, key: BytesN<32>) -> Result<(), ContractError> {
+        agent.require_auth();
+
+        get_session_key(&env, &agent, &key).ok_or(ContractError::AgentApprovalMissing)?;
+        set_session_key(&env, &agent, &key, None);
+        emit_session_key_registered(&env, agent, key, None);
+
+        Ok(())
+    }
+
+    pub fn get_session_key(env: Env, agent: Address, key: BytesN<32>) -> Option<SessionKey> {
+        get_session_key(&env, &agent, &key)
+    }
+
+    /// Registers the ed25519 public key the agent signs offline payout
+    /// approvals with. `None` removes it, which also voids approvals signed
+    /// but not yet submitted.
+    pub fn set_agent_signing_key(env: Env, agent: Address, key: Option<BytesN<32>>) -> Result<(), ContractError> {
+        agent.require_auth();
+
+        if !is_agent_registered(&env, &agent) {
+            return Err(ContractError::AgentNotRegistered);
+        }
+
+        set_agent_signing_key(&env, &agent, key.as_ref());
+        emit_agent_signing_key_updated(&env, agent, key);
+
+        Ok(())
+    }
+
+    pub fn get_agent_signing_key(env: Env, agent: Address) -> Option<BytesN<32>> {
+        get_agent_signing_key(&env, &agent)
+    }
+
+    /// Agent pre-approval allowing a Pending remittance to be settled in a
+    /// netting batch without the agent signing the batch itself.
+    pub fn approve_settlement(env: Env, agent: Address, remittance_id: u64) -> Result<(), ContractError> {
+        agent.require_auth();
+
+        let remittance = get_remittance(&env, remittance_id)?;
+        if remittance.agent != agent {
+            return Err(ContractError::AgentNotRegistered);
+        }
+        if remittance.status != RemittanceStatus::Pending {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        set_settlement_approved(&env, remittance_id);
+        emit_settlement_approved(&env, remittance_id, agent);
+
+        Ok(())
+    }
+
+    /// Settles several remittances in one call, netting payouts so each
+    /// agent receives a single transfer. The submitter must hold the
+    /// BatchSettler role (or be the admin) unless permissionless batching is
+    /// enabled; either way it is recorded on the batch. Outside the
+    /// configured settlement windows only the admin and WindowOverride
+    /// holders may submit.
+    pub fn batch_settle_with_netting(
+        env: Env,
+        submitter: Address,
+        remittance_ids: Vec<u64>,
+    ) -> Result<u64, ContractError> {
+        let context = load_settlement_context(&env)?;
+        authorize_batch(&env, &context, &submitter, &remittance_ids)?;
+
+        let remittances = validate_batch(&env, &context, &remittance_ids).map_err(|(_, error)| error)?;
+        execute_batch(&env, &context, submitter, remittance_ids, remittances)
+    }
+
+    /// `batch_settle_with_netting` that records a failed attempt instead of
+    /// reverting it. When an entry fails validation, or the contract cannot
+    /// cover the batch, nothing is settled, the attempt is kept as the
+    /// submitter's `BatchAbort` for `BATCH_ABORT_TTL_SECONDS` and a
+    /// `batch/aborted` event is emitted, and `None` is returned. Pause,
+    /// authorization, window and size checks still fail the call.
+    pub fn batch_settle_or_abort(
+        env: Env,
+        submitter: Address,
+        remittance_ids: Vec<u64>,
+    ) -> Result<Option<u64>, ContractError> {
+        let context = load_settlement_context(&env)?;
+        authorize_batch(&env, &context, &submitter, &remittance_ids)?;
+
+        match validate_batch(&env, &context, &remittance_ids) {
+            Ok(remittances) => execute_batch(&env, &context, submitter, remittance_ids, remittances).map(Some),
+            Err((failing_remittance_id, error)) => {
+                let abort = BatchAbort {
+                    submitter,
+                    entry_count: remittance_ids.len(),
+                    failing_remittance_id,
+                    error_code: error as u32,
+                    aborted_at: env.ledger().timestamp(),
+                };
+                set_batch_abort(&env, &abort, BATCH_ABORT_TTL_SECONDS);
+                emit_batch_aborted(&env, abort);
+                Ok(None)
+            }
+        }
+    }
+
+    /// The submitter's most recent aborted batch attempt, while retained.
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_batch_abort(env: Env, submitter: Address) -> Result<Option<BatchAbort>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_batch_abort(&env, &submitter))
+    }
+
+    /// Cancels a Pending or PayoutFailed remittance and refunds its amount
+    /// and tip to the sender. Requires sender auth.
+    pub fn cancel_remittance(env: Env, remittance_id: u64) -> Result<(), ContractError> {
+        let remittance = get_remittance(&env, remittance_id)?;
+
+        remittance.sender.require_auth();
+        increment_nonce(&env, &remittance.sender);
+
+        let refund_to = remittance.sender.clone();
+        cancel_pending_remittance(&env, &load_settlement_context(&env)?, remittance, &refund_to)?;
+
+        Ok(())
+    }
+
+    /// Registers a contract that is invoked with a `RemittanceNotice` each
+    /// time one of the sender's remittances settles, is cancelled or is
+    /// refunded. Calls are best-effort: a failing callee does not block the
+    /// operation. `None` unregisters.
+    pub fn set_notification_contract(env: Env, sender: Address, contract: Option<Address>) -> Result<(), ContractError> {
+        sender.require_auth();
+
+        set_notification_contract(&env, &sender, contract.clone());
+        emit_notification_contract_updated(&env, sender, contract);
+
+        Ok(())
+    }
+
+    pub fn get_notification_contract(env: Env, sender: Address) -> Option<Address> {
+        get_notification_contract(&env, &sender)
+    }
+
+    /// Tags a remittance for an off-chain system. Every later status change
+    /// also emits a `watch/status` event with `watcher_tag` as its third
+    /// topic, so indexers can filter by tag. Re-adding a tag is a no-op.
+    /// Requires sender auth.
+    pub fn watch(env: Env, remittance_id: u64, watcher_tag: Symbol) -> Result<(), ContractError> {
+        let remittance = get_remittance(&env, remittance_id)?;
+
+        remittance.sender.require_auth();
+        increment_nonce(&env, &remittance.sender);
+
+        let mut tags = get_watch_tags(&env, remittance_id);
+        if tags.contains(&watcher_tag) {
+            return Ok(());
+        }
+        if tags.len() >= MAX_WATCH_TAGS {
+            return Err(ContractError::InvalidTags);
+        }
+
+        tags.push_back(watcher_tag.clone());
+        set_watch_tags(&env, remittance_id, &tags);
+        emit_watch_added(&env, remittance_id, watcher_tag);
+
+        Ok(())
+    }
+
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_watch_tags(env: Env, remittance_id: u64) -> Result<Vec<Symbol>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_watch_tags(&env, remittance_id))
+    }
+
+    /// Cancels a remittance like `cancel_remittance` but sends the
+    /// refund to `refund_to`, for senders who lost access to the original
+    /// wallet. Requires sender auth and the admin's alternate-refund switch.
+    pub fn cancel_remittance_to(env: Env, remittance_id: u64, refund_to: Address) -> Result<(), ContractError> {
+        if !is_alternate_refund_enabled(&env) {
+            return Err(ContractError::AlternateRefundDisabled);
+        }
+
+        let remittance = get_remittance(&env, remittance_id)?;
+
+        remittance.sender.require_auth();
+        increment_nonce(&env, &remittance.sender);
+        validate_address(&refund_to)?;
+
+        let sender = remittance.sender.clone();
+        let refund = cancel_pending_remittance(&env, &load_settlement_context(&env)?, remittance, &refund_to)?;
+
+        emit_refund_redirected(&env, remittance_id, sender, refund_to, refund);
+
+        Ok(())
+    }
+
+    /// Allows or forbids `cancel_remittance_to`.
+    pub fn set_alternate_refund_enabled(env: Env, enabled: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let previous = is_alternate_refund_enabled(&env);
+        set_alternate_refund_enabled(&env, enabled);
+        record_config_change(&env, "alternate_refund_enabled", admin.clone(), previous, enabled);
+        emit_alternate_refund_toggled(&env, admin, enabled);
+
+        Ok(())
+    }
+
+    pub fn is_alternate_refund_enabled(env: Env) -> bool {
+        is_alternate_refund_enabled(&env)
+    }
+
+    /// Keeps `cancel_remittance` and `cancel_remittance_to` open while the
+    /// contract is paused, so senders can still be refunded during an
+    /// incident. Settlement stays blocked either way.
+    pub fn set_paused_refunds_allowed(env: Env, allowed: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let previous = are_paused_refunds_allowed(&env);
+        set_paused_refunds_allowed(&env, allowed);
+        record_config_change(&env, "paused_refunds_allowed", admin.clone(), previous, allowed);
+        emit_paused_refunds_toggled(&env, admin, allowed);
+
+        Ok(())
+    }
+
+    pub fn are_paused_refunds_allowed(env: Env) -> bool {
+        are_paused_refunds_allowed(&env)
+    }
+
+    /// Moves up to `MAX_TRANSITION_BATCH` remittances to new statuses for
+    /// incident recovery, e.g. after a token freeze. Only Pending to
+    /// PayoutFailed and back are allowed, since neither moves funds; any
+    /// other transition fails the whole batch with `InvalidStatus`. The
+    /// sender can still cancel a PayoutFailed remittance for a refund. Each
+    /// item emits an `admin/transit` event carrying `reason_hash`, a hash of
+    /// the off-chain incident report. Admin only.
+    pub fn admin_transition_batch(env: Env, transitions: Vec<(u64, RemittanceStatus)>, reason_hash: BytesN<32>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if transitions.is_empty() || transitions.len() > MAX_TRANSITION_BATCH {
+            return Err(ContractError::InvalidBatch);
+        }
+
+        for (remittance_id, status) in transitions.iter() {
+            let mut remittance = get_remittance(&env, remittance_id)?;
+            let previous = remittance.status.clone();
+            let allowed = matches!(
+                (&previous, &status),
+                (RemittanceStatus::Pending, RemittanceStatus::PayoutFailed)
+                    | (RemittanceStatus::PayoutFailed, RemittanceStatus::Pending)
+            );
+            if !allowed {
+                return Err(ContractError::InvalidStatus);
+            }
+
+            remittance.status = status.clone();
+            save_remittance(&env, &remittance);
+            emit_watch_events(&env, &remittance);
+            emit_remittance_transitioned(&env, remittance_id, admin.clone(), previous, status, reason_hash.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Lets the assigned agent turn down a Pending remittance it cannot pay
+    /// out. The sender is refunded in full without having to sign, and the
+    /// decline counts against the agent's reputation.
+    pub fn decline_remittance(env: Env, agent: Address, remittance_id: u64, reason: Option<Symbol>) -> Result<(), ContractError> {
+        agent.require_auth();
+
+        let mut remittance = get_remittance(&env, remittance_id)?;
+
+        if remittance.agent != agent {
+            return Err(ContractError::AgentNotRegistered);
+        }
+        if remittance.status != RemittanceStatus::Pending {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let (usdc_token, refund) = refund_sender(&env, &mut remittance, RemittanceStatus::DeclinedByAgent)?;
+
+        let mut reputation = get_agent_reputation(&env, &agent);
+        reputation.declined_count = reputation.declined_count.checked_add(1).ok_or(ContractError::Overflow)?;
+        set_agent_reputation(&env, &agent, &reputation);
+
+        emit_remittance_declined(&env, remittance_id, remittance.sender, agent, usdc_token, refund, reason);
+
+        Ok(())
+    }
+
+    /// Sets the amount above which new remittances are held for compliance
+    /// review. `None` disables review holds.
+    pub fn set_review_threshold(env: Env, threshold: Option<i128>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if matches!(threshold, Some(threshold) if threshold <= 0) {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let previous = get_review_threshold(&env);
+        set_review_threshold(&env, threshold);
+        record_config_change(&env, "review_threshold", admin.clone(), previous, threshold);
+        emit_review_threshold_updated(&env, admin, threshold);
+
+        Ok(())
+    }
+
+    /// Releases a remittance held for review so the agent can settle it.
+    /// Callable by the admin or a ComplianceOfficer.
+    pub fn approve_review(env: Env, officer: Address, remittance_id: u64) -> Result<(), ContractError> {
+        require_admin_or_role(&env, &officer, &Role::ComplianceOfficer)?;
+
+        let mut remittance = get_remittance(&env, remittance_id)?;
+        if remittance.status != RemittanceStatus::PendingReview {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        remittance.status = RemittanceStatus::Pending;
+        save_remittance(&env, &remittance);
+        emit_watch_events(&env, &remittance);
+
+        let review = record_review(&env, &remittance, officer, true);
+        emit_review_decided(&env, remittance_id, review);
+
+        Ok(())
+    }
+
+    /// Rejects a remittance held for review and refunds the sender.
+    /// Callable by the admin or a ComplianceOfficer.
+    pub fn reject_review(env: Env, officer: Address, remittance_id: u64) -> Result<(), ContractError> {
+        require_admin_or_role(&env, &officer, &Role::ComplianceOfficer)?;
+
+        let mut remittance = get_remittance(&env, remittance_id)?;
+        if remittance.status != RemittanceStatus::PendingReview {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        refund_sender(&env, &mut remittance, RemittanceStatus::ReviewRejected)?;
+
+        let review = record_review(&env, &remittance, officer, false);
+        emit_review_decided(&env, remittance_id, review);
+
+        Ok(())
+    }
+
+    /// Converts a Pending remittance into a milestone escrow. The platform
+    /// fee is booked now, the remaining funds stay locked in the escrow and
+    /// `milestones` must add up to exactly that net amount.
+    pub fn convert_to_escrow(env: Env, remittance_id: u64, milestones: Vec<i128>) -> Result<(), ContractError> {
+        let remittance = get_remittance(&env, remittance_id)?;
+
+        remittance.sender.require_auth();
+        increment_nonce(&env, &remittance.sender);
+
+        convert_pending_to_escrow(&env, remittance, milestones)
+    }
+
+    /// Releases the next escrow milestone to the agent and returns the
+    /// amount paid.
+    pub fn release_escrow_milestone(env: Env, remittance_id: u64) -> Result<i128, ContractError> {
+        if is_paused(&env) {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let mut escrow = get_escrow(&env, remittance_id)?;
+
+        escrow.sender.require_auth();
+        increment_nonce(&env, &escrow.sender);
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(ContractError::InvalidStatus);
+        }
+        let amount = escrow
+            .milestones
+            .get(escrow.released_count)
+            .ok_or(ContractError::InvalidStatus)?;
+
+        let usdc_token = get_usdc_token(&env)?;
+        transfer_token(&env, &usdc_token, &env.current_contract_address(), &escrow.agent, amount, TokenTransferStage::Payout)?;
+
+        let old_status = escrow.status.clone();
+        escrow.released_count += 1;
+        escrow.released_amount = escrow
+            .released_amount
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        if escrow.released_count == escrow.milestones.len() {
+            escrow.status = EscrowStatus::Released;
+        }
+        // The agent's offer was made against the old balance
+        escrow.proposed_amount = None;
+        set_escrow(&env, remittance_id, &escrow);
+
+        emit_escrow_released(
+            &env,
+            remittance_id,
+            escrow.sender.clone(),
+            escrow.agent.clone(),
+            usdc_token,
+            escrow.released_count - 1,
+            amount,
+            old_status,
+            escrow.status.clone(),
+        );
+
+        Ok(amount)
+    }
+
+    /// Lets the escrow's agent offer to close it for less than the unreleased
+    /// balance, for obligations it could only partly fulfil. Replaces any
+    /// earlier offer; releasing a milestone withdraws it.
+    pub fn propose_settlement_amount(env: Env, transfer_id: u64, amount: i128) -> Result<(), ContractError> {
+        let mut escrow = get_escrow(&env, transfer_id)?;
+
+        escrow.agent.require_auth();
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(ContractError::InvalidStatus);
+        }
+        let remaining = escrow_remaining(&escrow)?;
+        if amount <= 0 || amount >= remaining {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        escrow.proposed_amount = Some(amount);
+        set_escrow(&env, transfer_id, &escrow);
+        emit_escrow_amount_proposed(&env, transfer_id, escrow.agent, amount, remaining);
+
+        Ok(())
+    }
+
+    /// Accepts the agent's offer in one step: pays the proposed amount to
+    /// the agent, refunds the rest of the unreleased balance to the sender
+    /// and closes the escrow as Settled. Returns the refund.
+    pub fn accept_settlement_amount(env: Env, transfer_id: u64) -> Result<i128, ContractError> {
+        if is_paused(&env) {
+            return Err(ContractError::ContractPaused);
+        }
+
+        let mut escrow = get_escrow(&env, transfer_id)?;
+
+        escrow.sender.require_auth();
+        increment_nonce(&env, &escrow.sender);
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(ContractError::InvalidStatus);
+        }
+        let amount = escrow.proposed_amount.ok_or(ContractError::InvalidStatus)?;
+        let refund = escrow_remaining(&escrow)?
+            .checked_sub(amount)
+            .ok_or(ContractError::Overflow)?;
+
+        let usdc_token = get_usdc_token(&env)?;
+        transfer_token(&env, &usdc_token, &env.current_contract_address(), &escrow.agent, amount, TokenTransferStage::Payout)?;
+        transfer_token(&env, &usdc_token, &env.current_contract_address(), &escrow.sender, refund, TokenTransferStage::Refund)?;
+
+        escrow.released_amount = escrow
+            .released_amount
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        escrow.status = EscrowStatus::Settled;
+        escrow.proposed_amount = None;
+        set_escrow(&env, transfer_id, &escrow);
+
+        emit_escrow_settled(&env, transfer_id, escrow.sender, escrow.agent, usdc_token, amount, refund);
+
+        Ok(refund)
+    }
+
+    /// Disputes an Active escrow, freezing milestone releases and any
+    /// pending offer until an Arbitrator rules with `resolve_escrow_dispute`.
+    /// Requires sender auth.
+    pub fn open_escrow_dispute(env: Env, transfer_id: u64, evidence_hash: BytesN<32>) -> Result<(), ContractError> {
+        let mut escrow = get_escrow(&env, transfer_id)?;
+
+        escrow.sender.require_auth();
+        increment_nonce(&env, &escrow.sender);
+
+        if escrow.status != EscrowStatus::Active {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        escrow.status = EscrowStatus::Disputed;
+        escrow.proposed_amount = None;
+        set_escrow(&env, transfer_id, &escrow);
+        set_escrow_dispute_evidence(&env, transfer_id, &evidence_hash);
+        emit_escrow_disputed(&env, transfer_id, escrow.sender.clone(), evidence_hash, escrow_remaining(&escrow)?);
+
+        Ok(())
+    }
+
+    /// Rules on a disputed escrow by splitting its unreleased balance:
+    /// `sender_share_bps` of it is refunded to the sender and the rest paid
+    /// to the agent, closing the escrow as Resolved. Returns the refund.
+    /// Callable by the admin or an Arbitrator.
+    pub fn resolve_escrow_dispute(env: Env, arbitrator: Address, transfer_id: u64, sender_share_bps: u32) -> Result<i128, ContractError> {
+        require_admin_or_role(&env, &arbitrator, &Role::Arbitrator)?;
+
+        if sender_share_bps > 10000 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let mut escrow = get_escrow(&env, transfer_id)?;
+        if escrow.status != EscrowStatus::Disputed {
+            return Err(ContractError::InvalidStatus);
+        }
+        let remaining = escrow_remaining(&escrow)?;
+        let refund = calculate_fee(remaining, sender_share_bps)?;
+        let amount = remaining.checked_sub(refund).ok_or(ContractError::Overflow)?;
+
+        let usdc_token = get_usdc_token(&env)?;
+        if amount > 0 {
+            transfer_token(&env, &usdc_token, &env.current_contract_address(), &escrow.agent, amount, TokenTransferStage::Payout)?;
+        }
+        if refund > 0 {
+            transfer_token(&env, &usdc_token, &env.current_contract_address(), &escrow.sender, refund, TokenTransferStage::Refund)?;
+        }
+
+        escrow.released_amount = escrow
+            .released_amount
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        escrow.status = EscrowStatus::Resolved;
+        escrow.sender_share_bps = Some(sender_share_bps);
+        set_escrow(&env, transfer_id, &escrow);
+
+        emit_escrow_dispute_resolved(&env, transfer_id, arbitrator, escrow.sender, escrow.agent, usdc_token, sender_share_bps, refund, amount);
+
+        Ok(refund)
+    }
+
+    /// Sends accumulated platform fees to `to`. Callable by the admin or any
+    /// holder of the Treasurer role.
+    pub fn withdraw_fees(env: Env, caller: Address, to: Address) -> Result<(), ContractError> {
+        require_admin_or_role(&env, &caller, &Role::Treasurer)?;
+
+        // Validate the recipient address
+        validate_address(&to)?;
+
+        send_accumulated_fees(&env, caller, to)
+    }
+
+    /// Sends accumulated platform fees to the treasury routed for the
+    /// settlement token: its per-token treasury, else the default treasury.
+    /// Fails with InvalidAddress when neither is set. Callable by the admin
+    /// or any holder of the Treasurer role.
+    pub fn forward_fees(env: Env, caller: Address) -> Result<Address, ContractError> {
+        require_admin_or_role(&env, &caller, &Role::Treasurer)?;
+
+        let treasury = resolve_treasury(&env, &get_usdc_token(&env)?).ok_or(ContractError::InvalidAddress)?;
+        send_accumulated_fees(&env, caller, treasury.clone())?;
+
+        Ok(treasury)
+    }
+
+    /// Treasury that fees collected in `token` are forwarded to, if any.
+    pub fn get_treasury_for_token(env: Env, token: Address) -> Option<Address> {
+        resolve_treasury(&env, &token)
+    }
+
+    /// Pays out the agent's accrued fee rebates and surcharges.
+    pub fn claim_agent_rebate(env: Env, agent: Address) -> Result<i128, ContractError> {
+        agent.require_auth();
+
+        let rebate = get_agent_rebate_balance(&env, &agent);
+        if rebate <= 0 {
+            return Err(ContractError::NoFeesToWithdraw);
+        }
+
+        set_agent_rebate_balance(&env, &agent, 0);
+
+        let usdc_token = get_usdc_token(&env)?;
+        transfer_token(&env, &usdc_token, &env.current_contract_address(), &agent, rebate, TokenTransferStage::Withdrawal)?;
+
+        emit_agent_rebate_claimed(&env, agent, usdc_token, rebate);
+
+        Ok(rebate)
+    }
+
+    /// Pays out the referral rewards accrued to `referrer`.
+    pub fn claim_referral_rewards(env: Env, referrer: Address) -> Result<i128, ContractError> {
+        referrer.require_auth();
+
+        let rewards = get_referral_balance(&env, &referrer);
+        if rewards <= 0 {
+            return Err(ContractError::NoFeesToWithdraw);
+        }
+
+        set_referral_balance(&env, &referrer, 0);
+
+        let usdc_token = get_usdc_token(&env)?;
+        transfer_token(&env, &usdc_token, &env.current_contract_address(), &referrer, rewards, TokenTransferStage::Withdrawal)?;
+
+        emit_referral_claimed(&env, referrer, usdc_token, rewards);
+
+        Ok(rewards)
+    }
+
+    /// Adds `amount` of the agent's own tokens to its float held by the
+    /// contract.
+    pub fn deposit_float(env: Env, agent: Address, amount: i128) -> Result<(), ContractError> {
+        agent.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let usdc_token = get_usdc_token(&env)?;
+        transfer_token(&env, &usdc_token, &agent, &env.current_contract_address(), amount, TokenTransferStage::Deposit)?;
+
+        let float = get_agent_float(&env, &agent)
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        set_agent_float(&env, &agent, float);
+
+        emit_float_deposited(&env, agent, usdc_token, amount, float);
+
+        Ok(())
+    }
+
+    /// Sends `amount` of the agent's float back to it.
+    pub fn withdraw_float(env: Env, agent: Address, amount: i128) -> Result<(), ContractError> {
+        agent.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::InvalidAmount);
+        }
+
+        let float = get_agent_float(&env, &agent);
+        if amount > float {
+            return Err(ContractError::InsufficientAgentFloat);
+        }
+
+        let float = float - amount;
+        set_agent_float(&env, &agent, float);
+
+        let usdc_token = get_usdc_token(&env)?;
+        transfer_token(&env, &usdc_token, &env.current_contract_address(), &agent, amount, TokenTransferStage::Withdrawal)?;
+
+        emit_float_withdrawn(&env, agent, usdc_token, amount, float);
+
+        Ok(())
+    }
+
+    /// When enabled, the agent's settlement payouts are credited to its float
+    /// instead of being transferred, so settling costs no token transfer.
+    /// The float is the agent's own balance held by the contract: settlement
+    /// adds to it and never draws on it, so a settlement cannot exceed it.
+    /// Only `withdraw_float` spends it.
+    pub fn set_settle_from_float(env: Env, agent: Address, enabled: bool) -> Result<(), ContractError> {
+        agent.require_auth();
+
+        if !is_agent_registered(&env, &agent) {
+            return Err(ContractError::AgentNotRegistered);
+        }
+
+        set_settles_from_float(&env, &agent, enabled);
+        emit_float_mode_updated(&env, agent, enabled);
+
+        Ok(())
+    }
+
+    pub fn get_agent_float(env: Env, agent: Address) -> i128 {
+        get_agent_float(&env, &agent)
+    }
+
+    pub fn is_settling_from_float(env: Env, agent: Address) -> bool {
+        settles_from_float(&env, &agent)
+    }
+
+    /// Routes the agent's transferred settlement payouts to a custodial
+    /// destination with the memo it needs to credit the agent's
+    /// sub-account. Payouts credited to float are unaffected. `None` pays the
+    /// agent directly again.
+    pub fn set_payout_route(env: Env, agent: Address, route: Option<PayoutRoute>) -> Result<(), ContractError> {
+        agent.require_auth();
+
+        if !is_agent_registered(&env, &agent) {
+            return Err(ContractError::AgentNotRegistered);
+        }
+        if let Some(route) = &route {
+            validate_address(&route.destination)?;
+            if route.memo.is_empty() || route.memo.len() > MAX_PAYOUT_MEMO_LEN {
+                return Err(ContractError::InvalidAddress);
+            }
+        }
+
+        set_payout_route(&env, &agent, route.as_ref());
+        emit_payout_route_updated(&env, agent, route);
+
+        Ok(())
+    }
+
+    pub fn get_payout_route(env: Env, agent: Address) -> Option<PayoutRoute> {
+        get_payout_route(&env, &agent)
+    }
+
+    /// Fails with `MissingRole` while private mode is on; use
+    /// `get_remittance_as` instead.
+    pub fn get_remittance(env: Env, remittance_id: u64) -> Result<Remittance, ContractError> {
+        require_public_book(&env)?;
+        get_remittance(&env, remittance_id)
+    }
+
+    /// Reads a remittance on behalf of `viewer`, who must authorize the call.
+    /// While private mode is on, only the sender, the assigned agent, the
+    /// admin and Auditor role holders may read it.
+    pub fn get_remittance_as(env: Env, viewer: Address, remittance_id: u64) -> Result<Remittance, ContractError> {
+        let remittance = get_remittance(&env, remittance_id)?;
+        require_remittance_reader(&env, &viewer, &remittance)?;
+        Ok(remittance)
+    }
+
+    /// Pending remittances created at least `min_age_seconds` ago, oldest
+    /// first from `start_id`. Returns up to `limit` of them, visiting at
+    /// most `MAX_OPEN_SCAN` IDs per call; keep paging from `next_start_id`
+    /// until it is `None`. Remittances held for review are not included.
+    pub fn get_aged_pending(env: Env, min_age_seconds: u64, start_id: u64, limit: u32) -> Result<AgedPendingPage, ContractError> {
+        require_public_book(&env)?;
+        let now = env.ledger().timestamp();
+        let last_id = get_counters(&env)?.remittance_counter;
+        let mut remittances = Vec::new(&env);
+        let mut next_id = start_id.max(get_open_remittance_head(&env));
+        let scan_end = next_id.saturating_add(MAX_OPEN_SCAN);
+
+        while next_id <= last_id && next_id < scan_end && remittances.len() < limit {
+            let remittance_id = next_id;
+            next_id += 1;
+
+            if !is_open_remittance(&env, remittance_id) {
+                continue;
+            }
+            let Ok(remittance) = get_remittance(&env, remittance_id) else {
+                continue;
+            };
+            // Later IDs were created later still, so none of them is aged
+            if now.saturating_sub(remittance.created_at) < min_age_seconds {
+                return Ok(AgedPendingPage { remittances, next_start_id: None });
+            }
+            if remittance.status == RemittanceStatus::Pending {
+                remittances.push_back(remittance);
+            }
+        }
+
+        Ok(AgedPendingPage {
+            remittances,
+            next_start_id: (next_id <= last_id).then_some(next_id),
+        })
+    }
+
+    /// Keeper entry point: walks the open remittances from `start_id` and
+    /// emits the expiring-soon warning for up to `limit` Pending ones inside
+    /// the warning window that have not been warned about yet, visiting at
+    /// most `MAX_OPEN_SCAN` IDs per call; keep calling from `next_start_id`
+    /// until it is `None`. Callable by anyone.
+    pub fn scan_expiring(env: Env, start_id: u64, limit: u32) -> ExpiryScan {
+        let last_id = get_counters(&env).map_or(0, |counters| counters.remittance_counter);
+        let mut warned = 0;
+        let mut next_id = start_id.max(get_open_remittance_head(&env));
+        let scan_end = next_id.saturating_add(MAX_OPEN_SCAN);
+
+        while next_id <= last_id && next_id < scan_end && warned < limit {
+            let remittance_id = next_id;
+            next_id += 1;
+
+            if !is_open_remittance(&env, remittance_id) {
+                continue;
+            }
+            if let Ok(remittance) = get_remittance(&env, remittance_id) {
+                if warn_if_expiring(&env, &remittance) {
+                    warned += 1;
+                }
+            }
+        }
+
+        ExpiryScan {
+            warned,
+            next_start_id: (next_id <= last_id).then_some(next_id),
+        }
+    }
+
+    /// Remittances linked under `remittance_id`, in creation order.
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_remittance_children(env: Env, remittance_id: u64) -> Result<Vec<u64>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_remittance_children(&env, remittance_id))
+    }
+
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_remittance_parent(env: Env, remittance_id: u64) -> Result<Option<u64>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_remittance_parent(&env, remittance_id))
+    }
+
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_external_ref(env: Env, remittance_id: u64) -> Result<Option<BytesN<32>>, ContractError> {
+        require_public_book(&env)?;
+        get_remittance(&env, remittance_id)?;
+        Ok(get_external_ref(&env, remittance_id))
+    }
+
+    /// IDs of every remittance `sender` created with `external_ref`, oldest
+    /// first. Fails with `MissingRole` while private mode is on.
+    pub fn find_by_external_ref(env: Env, sender: Address, external_ref: BytesN<32>) -> Result<Vec<u64>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_remittances_by_external_ref(&env, &sender, &external_ref))
+    }
+
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_settlement(env: Env, id: u64) -> Result<Remittance, ContractError> {
+        require_public_book(&env)?;
+        get_remittance(&env, id)
+    }
+
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_batch(env: Env, batch_id: u64) -> Result<SettlementBatch, ContractError> {
+        require_public_book(&env)?;
+        get_batch(&env, batch_id)
+    }
+
+    pub fn is_batch_permissionless(env: Env) -> bool {
+        is_batch_permissionless(&env)
+    }
+
+    pub fn get_settlement_windows(env: Env) -> Option<Vec<SettlementWindow>> {
+        get_settlement_windows(&env)
+    }
+
+    pub fn is_batch_agent_approval_required(env: Env) -> bool {
+        is_batch_agent_approval_required(&env)
+    }
+
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn is_settlement_approved(env: Env, remittance_id: u64) -> Result<bool, ContractError> {
+        require_public_book(&env)?;
+        Ok(is_settlement_approved(&env, remittance_id))
+    }
+
+    pub fn get_review_threshold(env: Env) -> Option<i128> {
+        get_review_threshold(&env)
+    }
+
+    /// Compliance decision on a remittance that was held for review.
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_review(env: Env, remittance_id: u64) -> Result<Option<ReviewRecord>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_review(&env, remittance_id))
+    }
+
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_escrow(env: Env, remittance_id: u64) -> Result<Escrow, ContractError> {
+        require_public_book(&env)?;
+        get_escrow(&env, remittance_id)
+    }
+
+    /// Remittances created by `sender`, oldest first, skipping `cursor`
+    /// entries. Fails with `MissingRole` while private mode is on.
+    pub fn get_remittances_by_sender(env: Env, sender: Address, cursor: u32, limit: u32) -> Result<Vec<Remittance>, ContractError> {
+        require_public_book(&env)?;
+
+        let mut page = Vec::new(&env);
+        let end = cursor.saturating_add(limit).min(get_sender_remittance_count(&env, &sender));
+        for index in cursor..end {
+            if let Some(remittance_id) = get_sender_remittance_id(&env, &sender, index) {
+                page.push_back(get_remittance(&env, remittance_id)?);
+            }
+        }
+
+        Ok(page)
+    }
+
+    /// Number of remittances `sender` has created.
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_sender_remittance_count(env: Env, sender: Address) -> Result<u32, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_sender_remittance_count(&env, &sender))
+    }
+
+    /// Escrows funded by `sender`, oldest first, skipping `start` entries.
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_escrows_by_sender(env: Env, sender: Address, start: u32, limit: u32) -> Result<Vec<Escrow>, ContractError> {
+        require_public_book(&env)?;
+        let count = get_sender_escrow_count(&env, &sender);
+        Ok(load_escrow_page(&env, count, start, limit, |index| get_sender_escrow_id(&env, &sender, index)))
+    }
+
+    /// Escrows paying out to `recipient`, oldest first, skipping `start`
+    /// entries. Fails with `MissingRole` while private mode is on.
+    pub fn get_escrows_by_recipient(env: Env, recipient: Address, start: u32, limit: u32) -> Result<Vec<Escrow>, ContractError> {
+        require_public_book(&env)?;
+        let count = get_recipient_escrow_count(&env, &recipient);
+        Ok(load_escrow_page(&env, count, start, limit, |index| get_recipient_escrow_id(&env, &recipient, index)))
+    }
+
+    /// Evidence hash filed with the escrow's dispute, if one was opened.
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_escrow_dispute_evidence(env: Env, transfer_id: u64) -> Result<Option<BytesN<32>>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_escrow_dispute_evidence(&env, transfer_id))
+    }
+
+    pub fn get_escrow_count(env: Env) -> u64 {
+        get_escrow_count(&env)
+    }
+
+    /// Pending remittances with IDs in `[start_id, start_id + limit)`, in
+    /// settlement priority order: highest tip first, then oldest first.
+    /// `limit` is capped at `MAX_OPEN_SCAN` IDs. Fails with `MissingRole`
+    /// while private mode is on.
+    pub fn get_settlement_queue(env: Env, start_id: u64, limit: u32) -> Result<Vec<u64>, ContractError> {
+        require_public_book(&env)?;
+        let mut queue: Vec<Remittance> = Vec::new(&env);
+        let end_id = start_id
+            .saturating_add((limit as u64).min(MAX_OPEN_SCAN))
+            .min(get_remittance_counter(&env).unwrap_or(0).saturating_add(1));
+
+        for id in start_id.max(1)..end_id {
+            let Ok(remittance) = get_remittance(&env, id) else {
+                continue;
+            };
+            if remittance.status != RemittanceStatus::Pending {
+                continue;
+            }
+
+            // Insertion sort keeps equal tips in ID (FIFO) order
+            let mut position = queue.len();
+            for (i, queued) in queue.iter().enumerate() {
+                if remittance.priority_tip > queued.priority_tip {
+                    position = i as u32;
+                    break;
+                }
+            }
+            queue.insert(position, remittance);
+        }
+
+        let mut ids = Vec::new(&env);
+        for remittance in queue.iter() {
+            ids.push_back(remittance.id);
+        }
+        Ok(ids)
+    }
+
+    /// Hash recorded when the remittance was settled, if it has been.
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_settlement_hash(env: Env, remittance_id: u64) -> Result<Option<BytesN<32>>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_settlement_hash(&env, remittance_id))
+    }
+
+    /// Fee and payout the remittance settles with, alongside the fee the
+    /// current schedule would charge for the same amount. Fails with `MissingRole` while private mode is on.
+    pub fn get_effective_settlement_terms(env: Env, remittance_id: u64) -> Result<SettlementTerms, ContractError> {
+        require_public_book(&env)?;
+        let remittance = get_remittance(&env, remittance_id)?;
+
+        let current_fee = compute_fee(
+            &env,
+            &remittance.sender,
+            Some(&remittance.agent),
+            remittance.amount,
+            &remittance.currency,
+            &remittance.country,
+        )?;
+        let payout_amount = net_amount(&remittance)?;
+
+        Ok(SettlementTerms {
+            remittance_id,
+            amount: remittance.amount,
+            fee: remittance.fee,
+            payout_amount,
+            current_fee,
+        })
+    }
+
+    pub fn get_challenge_config(env: Env) -> Option<ChallengeConfig> {
+        get_challenge_config(&env)
+    }
+
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_settlement_challenge(env: Env, remittance_id: u64) -> Result<Option<SettlementChallenge>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_settlement_challenge(&env, remittance_id))
+    }
+
+    /// Whether a completed settlement can no longer be challenged: its
+    /// challenge period has passed unchallenged, or a challenge against it
+    /// was rejected. Settlements with an open or upheld challenge are never
+    /// final. Fails with `MissingRole` while private mode is on.
+    pub fn is_settlement_final(env: Env, remittance_id: u64) -> Result<bool, ContractError> {
+        require_public_book(&env)?;
+        is_settlement_final(&env, &get_remittance(&env, remittance_id)?)
+    }
+
+    /// A remittance together with its settlement state, agent standing,
+    /// corridor status, payout and countdowns, for rendering in one read.
+    pub fn get_remittance_view(env: Env, remittance_id: u64) -> Result<RemittanceView, ContractError> {
+        require_public_book(&env)?;
+        let remittance = get_remittance(&env, remittance_id)?;
+        let now = env.ledger().timestamp();
+
+        Ok(RemittanceView {
+            settleable: check_settleable(&env, &load_settlement_context(&env)?, &remittance).is_ok(),
+            settlement_final: is_settlement_final(&env, &remittance)?,
+            agent_registered: is_agent_registered(&env, &remittance.agent),
+            agent_reputation: get_agent_reputation(&env, &remittance.agent),
+            corridor_enabled: is_corridor_enabled(&env, &remittance.currency, &remittance.country),
+            payout_amount: net_amount(&remittance)?,
+            expires_in: remittance.expiry.map(|expiry| expiry.saturating_sub(now)),
+            sender_cooldown: cooldown_remaining(&env, &remittance.sender).max(window_remaining(&env, &remittance.sender)),
+            remittance,
+        })
+    }
+
+    /// Flat rows for accounting pipelines, in ID order from `start_id`.
+    /// Returns up to `limit` rows with `status_filter` (all statuses when
+    /// `None`), scanning at most `MAX_EXPORT_SCAN` IDs per call; keep paging
+    /// from `next_start_id` until it is `None`.
+    pub fn export_remittance_page(
+        env: Env,
+        start_id: u64,
+        limit: u32,
+        status_filter: Option<RemittanceStatus>,
+    ) -> Result<RemittanceExportPage, ContractError> {
+        require_public_book(&env)?;
+        export_remittance_page(&env, start_id, limit, status_filter)
+    }
+
+    /// `export_remittance_page` for the admin or an Auditor, which keeps
+    /// working while private mode is on.
+    pub fn export_remittance_page_as(
+        env: Env,
+        auditor: Address,
+        start_id: u64,
+        limit: u32,
+        status_filter: Option<RemittanceStatus>,
+    ) -> Result<RemittanceExportPage, ContractError> {
+        require_admin_or_role(&env, &auditor, &Role::Auditor)?;
+        export_remittance_page(&env, start_id, limit, status_filter)
+    }
+
+    /// Settlement hash together with its preimage fields and ledger details,
+    /// so auditors can recompute the hash off-chain.
+    pub fn get_settlement_proof(env: Env, remittance_id: u64) -> Result<SettlementProof, ContractError> {
+        require_public_book(&env)?;
+        get_remittance(&env, remittance_id)?;
+        get_settlement_proof(&env, remittance_id).ok_or(ContractError::InvalidStatus)
+    }
+
+    /// Resolves a settlement hash back to the remittance it settled.
+    pub fn get_remittance_by_settle_hash(env: Env, hash: BytesN<32>) -> Result<Remittance, ContractError> {
+        require_public_book(&env)?;
+        let remittance_id = get_remittance_id_by_settlement_hash(&env, &hash)
+            .ok_or(ContractError::RemittanceNotFound)?;
+        get_remittance(&env, remittance_id)
+    }
+
+    pub fn get_corridor_stats(env: Env, currency: String, country: String) -> Result<CorridorStats, ContractError> {
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+        Ok(get_corridor_stats(&env, &currency, &country))
+    }
+
+    pub fn is_corridor_enabled(env: Env, currency: String, country: String) -> Result<bool, ContractError> {
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+        Ok(is_corridor_enabled(&env, &currency, &country))
+    }
+
+    pub fn get_corridor_sla(env: Env, currency: String, country: String) -> Result<Option<u64>, ContractError> {
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+        Ok(get_corridor_sla(&env, &currency, &country))
+    }
+
+    pub fn get_corridor_receive_limit(env: Env, currency: String, country: String) -> Result<Option<i128>, ContractError> {
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+        Ok(get_corridor_receive_limit(&env, &currency, &country))
+    }
+
+    /// Payouts `agent` received from the corridor over the current receive
+    /// limit window. Only tracked while the corridor has a limit.
+    pub fn get_agent_receive_usage(env: Env, agent: Address, currency: String, country: String) -> Result<i128, ContractError> {
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+        let (_, received) = received_in_window(&env, &agent, &currency, &country)?;
+        Ok(received)
+    }
+
+    pub fn get_corridor_chargeback_window(env: Env, currency: String, country: String) -> Result<Option<u64>, ContractError> {
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+        Ok(get_corridor_chargeback_window(&env, &currency, &country))
+    }
+
+    pub fn get_corridor_fee_mode(env: Env, currency: String, country: String) -> Result<NettingFeeMode, ContractError> {
+        let currency = normalize_currency(&env, &currency)?;
+        let country = normalize_country(&env, &country)?;
+        Ok(get_corridor_fee_mode(&env, &currency, &country))
+    }
+
+    pub fn get_sla_penalty_bps(env: Env) -> u32 {
+        get_sla_penalty_bps(&env)
+    }
+
+    /// Number of sender-signed operations `address` has performed. It grows
+    /// by one on every successful create, quote, tip, confirmation,
+    /// cancellation and escrow call the address authorizes.
+    pub fn get_nonce(env: Env, address: Address) -> u64 {
+        get_nonce(&env, &address)
+    }
+
+    /// Sequence number of the most recent event, 0 if none was emitted.
+    pub fn get_event_sequence(env: Env) -> u64 {
+        get_event_sequence(&env)
+    }
+
+    pub fn get_event_verbosity(env: Env) -> EventVerbosity {
+        get_event_verbosity(&env)
+    }
+
+    /// Lifetime number of settled remittances.
+    pub fn get_total_settlements_count(env: Env) -> u64 {
+        get_total_settlements(&env)
+    }
+
+    /// Lifetime number of cancelled remittances.
+    pub fn get_total_cancellations_count(env: Env) -> u64 {
+        get_total_cancellations(&env)
+    }
+
+    /// Lifetime number of remittances created with `tag`.
+    pub fn get_tag_count(env: Env, tag: Symbol) -> u64 {
+        get_tag_count(&env, &tag)
+    }
+
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_sender_exposure(env: Env, sender: Address) -> Result<i128, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_sender_exposure(&env, &sender))
+    }
+
+    /// Returns the cap enforced for `sender`: its own override if set,
+    /// otherwise the global cap. `None` means uncapped.
+    pub fn get_exposure_cap(env: Env, sender: Address) -> Option<i128> {
+        effective_exposure_cap(&env, &sender)
+    }
+
+    pub fn are_exposure_caps_enabled(env: Env) -> bool {
+        are_exposure_caps_enabled(&env)
+    }
+
+    pub fn get_total_locked(env: Env) -> i128 {
+        get_total_locked(&env)
+    }
+
+    /// Network health in one read for public dashboards: lifetime
+    /// settlements, their mean creation-to-settlement time, registered
+    /// agents, corridors in use and the value currently locked.
+    pub fn get_network_metrics(env: Env) -> Result<NetworkMetrics, ContractError> {
+        let counters = get_counters(&env)?;
+        let tally = get_network_tally(&env);
+        let average_settlement_seconds = tally
+            .settlement_seconds
+            .checked_div(counters.total_settlements)
+            .unwrap_or(0);
+
+        Ok(NetworkMetrics {
+            settlements: counters.total_settlements,
+            average_settlement_seconds,
+            active_agents: tally.active_agents,
+            corridors: tally.corridors,
+            total_value_locked: counters.total_locked,
+        })
+    }
+
+    pub fn get_max_total_locked(env: Env) -> Option<i128> {
+        get_max_total_locked(&env)
+    }
+
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_agent_workload(env: Env, agent: Address) -> Result<AgentWorkload, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_agent_workload(&env, &agent))
+    }
+
+    pub fn get_agent_reputation(env: Env, agent: Address) -> AgentReputation {
+        get_agent_reputation(&env, &agent)
+    }
+
+    /// Net value of Pending remittances between two parties.
+    ///
+    /// Positive when more is flowing from `party_a` to `party_b` than the
+    /// other way round, negative when the balance favours `party_a`.
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_net_position(env: Env, party_a: Address, party_b: Address) -> Result<i128, ContractError> {
+        require_public_book(&env)?;
+        get_pair_pending(&env, &party_a, &party_b)
+            .checked_sub(get_pair_pending(&env, &party_b, &party_a))
+            .ok_or(ContractError::Overflow)
+    }
+
+    pub fn get_accumulated_fees(env: Env) -> Result<i128, ContractError> {
+        get_accumulated_fees(&env)
+    }
+
+    /// Fee schedule in force at `ledger_sequence`, for checking the rates a
+    /// past remittance should have been charged. `None` before the first
+    /// recorded schedule.
+    pub fn get_fee_schedule_at(env: Env, ledger_sequence: u32) -> Option<FeeSchedule> {
+        find_fee_schedule_at(&env, ledger_sequence)
+    }
+
+    pub fn get_reserve_bps(env: Env) -> u32 {
+        get_reserve_bps(&env)
+    }
+
+    /// Token balance held beyond the total locked value and the other
+    /// balances the contract owes (agent floats, unclaimed rebates and
+    /// referral rewards, priority tips, the insurance fund, challenge bonds
+    /// and held payouts), in basis points of the locked value. `None` while
+    /// nothing is locked.
+    pub fn get_reserve_ratio_bps(env: Env) -> Result<Option<i128>, ContractError> {
+        let total_locked = get_counters(&env)?.total_locked;
+        if total_locked <= 0 {
+            return Ok(None);
+        }
+
+        let usdc_token = get_usdc_token(&env)?;
+        let balance = token::Client::new(&env, &usdc_token).balance(&env.current_contract_address());
+        let owed = owed_balances(&env)?;
+        let ratio_bps = balance
+            .checked_sub(total_locked)
+            .and_then(|excess| excess.checked_sub(owed))
+            .and_then(|excess| excess.checked_mul(10000))
+            .and_then(|excess| excess.checked_div(total_locked))
+            .ok_or(ContractError::Overflow)?;
+
+        Ok(Some(ratio_bps))
+    }
+
+    pub fn is_agent_registered(env: Env, agent: Address) -> bool {
+        is_agent_registered(&env, &agent)
+    }
+
+    /// Cached symbol and decimals of a whitelisted token; `None` when the
+    /// token is not whitelisted.
+    pub fn get_token_info(env: Env, token: Address) -> Option<TokenInfo> {
+        get_token_info(&env, &token)
+    }
+
+    pub fn get_contract_metadata(env: Env) -> Option<ContractMetadata> {
+        get_contract_metadata(&env)
+    }
+
+    pub fn get_admin_recovery(env: Env) -> Option<Address> {
+        get_admin_recovery(&env)
+    }
+
+    /// Earliest time the recovery address may claim the admin role.
+    pub fn get_admin_recovery_deadline(env: Env) -> u64 {
+        admin_recovery_deadline(&env)
+    }
+
+    pub fn is_corridor_registry_enabled(env: Env) -> bool {
+        is_corridor_registry_enabled(&env)
+    }
+
+    pub fn get_allowed_currencies(env: Env) -> Vec<String> {
+        get_allowed_currencies(&env)
+    }
+
+    pub fn get_allowed_countries(env: Env) -> Vec<String> {
+        get_allowed_countries(&env)
+    }
+
+    pub fn get_rate_limit(env: Env) -> Option<RateLimitConfig> {
+        get_rate_limit_config(&env)
+    }
+
+    /// Time of the sender's most recent remittance, if any.
+    pub fn get_last_settlement_time(env: Env, sender: Address) -> Option<u64> {
+        get_last_settlement_time(&env, &sender)
+    }
+
+    /// Seconds until the sender's cooldown ends; zero when it may create now.
+    pub fn get_cooldown_remaining(env: Env, sender: Address) -> u64 {
+        cooldown_remaining(&env, &sender)
+    }
+
+    /// Seconds until the address's exhausted rate-limit window resets; zero
+    /// when the window still has room.
+    pub fn get_window_remaining(env: Env, address: Address) -> u64 {
+        window_remaining(&env, &address)
+    }
+
+    pub fn get_default_expiry_seconds(env: Env) -> Option<u64> {
+        get_default_expiry_seconds(&env)
+    }
+
+    pub fn get_dual_confirm_timeout(env: Env) -> u64 {
+        get_dual_confirm_timeout(&env)
+    }
+
+    pub fn has_role(env: Env, role: Role, address: Address) -> bool {
+        has_role(&env, &role, &address)
+    }
+
+    pub fn is_authorization_required(env: Env) -> bool {
+        is_authorization_required(&env)
+    }
+
+    pub fn is_private_mode(env: Env) -> bool {
+        is_private_mode(&env)
+    }
+
+    /// Bitmask of enabled features; bit `n` is the `Feature` with value `n`.
+    pub fn get_features(env: Env) -> u32 {
+        get_features(&env)
+    }
+
+    pub fn is_feature_enabled(env: Env, feature: Feature) -> bool {
+        is_feature_enabled(&env, feature)
+    }
+
+    /// Authorizer that approved the remittance for payout, if any.
+    /// Fails with `MissingRole` while private mode is on.
+    pub fn get_remittance_authorizer(env: Env, remittance_id: u64) -> Result<Option<Address>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_remittance_authorizer(&env, remittance_id))
+    }
+
+    pub fn get_agent_removal_reason(env: Env, agent: Address) -> Option<Symbol> {
+        get_agent_removal_reason(&env, &agent)
+    }
+
+    pub fn get_platform_fee_bps(env: Env) -> Result<u32, ContractError> {
+        get_platform_fee_bps(&env)
+    }
+
+    pub fn get_token_fee_bps(env: Env, token: Address) -> Option<u32> {
+        get_token_fee_bps(&env, &token)
+    }
+
+    pub fn get_fee_strategy(env: Env) -> Option<FeeStrategy> {
+        get_fee_strategy(&env)
+    }
+
+    pub fn get_agent_rebate_bps(env: Env) -> u32 {
+        get_agent_rebate_bps(&env)
+    }
+
+    pub fn get_agent_rebate_balance(env: Env, agent: Address) -> i128 {
+        get_agent_rebate_balance(&env, &agent)
+    }
+
+    pub fn get_agent_surcharge_bps(env: Env, agent: Address) -> u32 {
+        get_agent_surcharge_bps(&env, &agent)
+    }
+
+    pub fn get_insurance_premium_bps(env: Env) -> u32 {
+        get_insurance_premium_bps(&env)
+    }
+
+    pub fn get_insurance_fund(env: Env) -> InsuranceFund {
+        get_insurance_fund(&env)
+    }
+
+    /// Compensation paid on an insured remittance and the ruling that paid
+    /// it, if any. Fails with `MissingRole` while private mode is on.
+    pub fn get_insurance_claim(env: Env, remittance_id: u64) -> Result<Option<InsuranceClaim>, ContractError> {
+        require_public_book(&env)?;
+        Ok(get_insurance_claim(&env, remittance_id))
+    }
+
+    /// A sender's own daily cap, with any raise that has matured applied.
+    pub fn get_my_daily_cap(env: Env, sender: Address) -> SelfCap {
+        matured_self_cap(&env, get_self_daily_cap(&env, &sender))
+    }
+
+    /// A sender's own per-remittance cap, with any raise that has matured
+    /// applied.
+    pub fn get_my_max_tx(env: Env, sender: Address) -> SelfCap {
+        matured_self_cap(&env, get_self_max_tx(&env, &sender))
+    }
+
+    pub fn get_referral_config(env: Env) -> Option<ReferralConfig> {
+        get_referral_config(&env)
+    }
+
+    pub fn get_referrer(env: Env, sender: Address) -> Option<Address> {
+        get_referral(&env, &sender).map(|referral| referral.referrer)
+    }
+
+    pub fn get_referral_balance(env: Env, referrer: Address) -> i128 {
+        get_referral_balance(&env, &referrer)
+    }
+
+    /// Pauses settlements. The pause lifts on its own at `pause_until` or,
+    /// when a maximum pause duration is configured, no later than that
+    /// duration from now. Calling `pause` again while paused renews it.
+    pub fn pause(
+        env: Env,
+        reason: Option<Symbol>,
+        pause_until: Option<u64>,
+    ) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let now = env.ledger().timestamp();
+        if matches!(pause_until, Some(until) if until <= now) {
+            return Err(ContractError::InvalidPauseWindow);
+        }
+
+        let pause_until = match get_max_pause_duration(&env) {
+            Some(max_duration) => {
+                let latest = now.checked_add(max_duration).ok_or(ContractError::Overflow)?;
+                Some(pause_until.map_or(latest, |until| until.min(latest)))
+            }
+            None => pause_until,
+        };
+
+        set_paused(&env, true);
+        set_pause_info(
+            &env,
+            Some(&PauseInfo {
+                paused_by: admin.clone(),
+                reason: reason.clone(),
+                paused_at: now,
+                pause_until,
+            }),
+        );
+        emit_paused(&env, admin, reason, pause_until);
+
+        Ok(())
+    }
+
+    /// Caps how long any pause may last. `None` allows indefinite pauses.
+    pub fn set_max_pause_duration(env: Env, seconds: Option<u64>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if seconds == Some(0) {
+            return Err(ContractError::InvalidPauseWindow);
+        }
+
+        let previous = get_max_pause_duration(&env);
+        set_max_pause_duration(&env, seconds);
+        record_config_change(&env, "max_pause_duration", admin.clone(), previous, seconds);
+        emit_max_pause_updated(&env, admin, seconds);
+
+        Ok(())
+    }
+
+    pub fn unpause(env: Env) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if is_evacuated(&env) {
+            return Err(ContractError::ContractEvacuated);
+        }
+
+        set_paused(&env, false);
+        set_pause_info(&env, None);
+        emit_unpaused(&env, admin);
+
+        Ok(())
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        is_paused(&env)
+    }
+
+    /// Returns details of the active pause, or `None` if the contract is not
+    /// paused or the pause has expired.
+    pub fn get_pause_info(env: Env) -> Option<PauseInfo> {
+        if !is_paused(&env) {
+            return None;
+        }
+        get_pause_info(&env)
+    }
+
+    pub fn get_max_pause_duration(env: Env) -> Option<u64> {
+        get_max_pause_duration(&env)
+    }
+
+    /// Grants or revokes a delegated role for an address.
+    pub fn set_role(env: Env, role: Role, address: Address, granted: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let previous = has_role(&env, &role, &address);
+        set_role(&env, &role, &address, granted);
+        record_config_change(&env, "role", admin.clone(), (role.clone(), address.clone(), previous), (role.clone(), address.clone(), granted));
+        emit_role_updated(&env, admin, role, address, granted);
+
+        Ok(())
+    }
+
+    /// Grants or revokes `role` for up to `MAX_ROLE_BATCH` addresses.
+    pub fn set_roles(env: Env, role: Role, addresses: Vec<Address>, granted: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if addresses.is_empty() || addresses.len() > MAX_ROLE_BATCH {
+            return Err(ContractError::InvalidBatch);
+        }
+
+        for address in addresses.iter() {
+            let previous = has_role(&env, &role, &address);
+            set_role(&env, &role, &address, granted);
+            record_config_change(&env, "role", admin.clone(), (role.clone(), address.clone(), previous), (role.clone(), address.clone(), granted));
+            emit_role_updated(&env, admin.clone(), role.clone(), address, granted);
+        }
+
+        Ok(())
+    }
+
+    /// Holders of `role` in grant order, skipping `start` entries.
+    pub fn get_role_members(env: Env, role: Role, start: u32, limit: u32) -> Vec<Address> {
+        let members = get_role_members(&env, &role);
+        let end = start.saturating_add(limit).min(members.len());
+        if start >= end {
+            return Vec::new(&env);
+        }
+        members.slice(start..end)
+    }
+
+    /// Closes remittance creation to holders of the Sender role when
+    /// `true`. Off by default, so any address can send.
+    pub fn set_sender_allowlist_enabled(env: Env, enabled: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let previous = is_sender_allowlist_enabled(&env);
+        set_sender_allowlist_enabled(&env, enabled);
+        record_config_change(&env, "sender_allowlist_enabled", admin.clone(), previous, enabled);
+        emit_sender_allowlist_toggled(&env, admin, enabled);
+
+        Ok(())
+    }
+
+    pub fn is_sender_allowlist_enabled(env: Env) -> bool {
+        is_sender_allowlist_enabled(&env)
+    }
+
+    /// Captures fees, limits, rate limiting, the pause duration cap and
+    /// feature toggles under a new snapshot ID; whether the contract is
+    /// paused is not captured. Only the latest `MAX_CONFIG_SNAPSHOTS`
+    /// snapshots are kept.
+    pub fn snapshot_config(env: Env) -> Result<u64, ContractError> {
+        let admin = require_admin(&env)?;
+
+        let snapshot_id = save_config_snapshot(&env);
+        emit_config_snapshot_taken(&env, admin, snapshot_id);
+
+        Ok(snapshot_id)
+    }
+
+    /// Restores every value captured by `snapshot_id` in one step. Settings
+    /// that were unset at snapshot time return to their defaults. Fails with
+    /// InvalidStatus if the snapshot does not exist or has been pruned, or
+    /// if a timelock delay is set and the snapshot would change a setting
+    /// held back by it (platform fee, default treasury, fee strategy, agent
+    /// rebate, insurance premium or SLA penalty); queue those changes first.
+    pub fn rollback_config(env: Env, snapshot_id: u64) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if get_timelock_delay(&env).is_some() && snapshot_changes_timelocked(&env, snapshot_id)? {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        let previous = get_config_state(&env);
+        restore_config_snapshot(&env, snapshot_id)?;
+        archive_fee_schedule(&env)?;
+        record_config_change(&env, "snapshot", admin.clone(), previous, get_config_state(&env));
+        emit_config_rolled_back(&env, admin, snapshot_id);
+
+        Ok(())
+    }
+
+    /// IDs of the retained configuration snapshots, oldest first.
+    pub fn get_config_snapshot_ids(env: Env) -> Vec<u64> {
+        get_config_snapshot_ids(&env)
+    }
+
+    /// Turns a contract-wide feature on or off. The dedicated setters such
+    /// as `set_private_mode` write the same bits; this is the generic entry
+    /// point for tooling that manages every toggle in one place.
+    pub fn set_feature(env: Env, feature: Feature, enabled: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let previous = get_features(&env);
+        set_feature_enabled(&env, feature, enabled);
+        let features = get_features(&env);
+        record_config_change(&env, "features", admin.clone(), previous, features);
+        emit_feature_updated(&env, admin, feature, enabled, features);
+
+        Ok(())
+    }
+
+    /// Turns private mode on or off. While on, the remittance detail and
+    /// book getters fail with `MissingRole`; parties read through
+    /// `get_remittance_as` and auditors through `export_remittance_page_as`.
+    /// Aggregate stats stay public.
+    pub fn set_private_mode(env: Env, enabled: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let previous = is_private_mode(&env);
+        set_private_mode(&env, enabled);
+        record_config_change(&env, "private_mode", admin.clone(), previous, enabled);
+        emit_private_mode_updated(&env, admin, enabled);
+
+        Ok(())
+    }
+
+    /// Turns the pre-authorization step on or off. While on, `confirm_payout`
+    /// only succeeds for remittances an Authorizer has approved.
+    pub fn set_authorization_required(env: Env, required: bool) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let previous = is_authorization_required(&env);
+        set_authorization_required(&env, required);
+        record_config_change(&env, "authorization_required", admin.clone(), previous, required);
+        emit_authorization_required_updated(&env, admin, required);
+
+        Ok(())
+    }
+
+    /// Sets how long after creation a dual-confirmation remittance can be
+    /// settled on the agent's confirmation alone.
+    pub fn set_dual_confirm_timeout(env: Env, timeout_seconds: u64) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let previous = get_dual_confirm_timeout(&env);
+        set_dual_confirm_timeout(&env, timeout_seconds);
+        record_config_change(&env, "dual_confirm_timeout", admin.clone(), previous, timeout_seconds);
+        emit_dual_confirm_timeout_updated(&env, admin, timeout_seconds);
+
+        Ok(())
+    }
+
+    /// Records the deployment descriptor for this instance.
+    pub fn set_contract_metadata(
+        env: Env,
+        name: String,
+        environment: Symbol,
+        operator_url_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let metadata = ContractMetadata {
+            name,
+            environment,
+            operator_url_hash,
+        };
+        let previous = get_contract_metadata(&env);
+        set_contract_metadata(&env, &metadata);
+        record_config_change(&env, "contract_metadata", admin.clone(), previous, Some(metadata.clone()));
+        emit_contract_metadata_updated(&env, admin, metadata);
+
+        Ok(())
+    }
+
+    /// Sets or clears the address that can claim the admin role after
+    /// prolonged admin inactivity.
+    pub fn set_admin_recovery(env: Env, recovery: Option<Address>) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        let previous = get_admin_recovery(&env);
+        set_admin_recovery(&env, recovery.as_ref());
+        record_config_change(&env, "admin_recovery", admin.clone(), previous, recovery.clone());
+        emit_admin_recovery_updated(&env, admin, recovery);
+
+        Ok(())
+    }
+
+    /// Returns the seconds left before the recovery address may claim the
+    /// admin role, emitting a warning event once inside the warning window.
+    /// Callable by anyone so monitors can surface an approaching deadline.
+    pub fn check_admin_recovery(env: Env) -> Result<u64, ContractError> {
+        let recovery = get_admin_recovery(&env).ok_or(ContractError::AdminRecoveryNotSet)?;
+        let remaining = admin_recovery_deadline(&env).saturating_sub(env.ledger().timestamp());
+
+        if remaining <= ADMIN_RECOVERY_WARNING_SECONDS {
+            emit_admin_recovery_warning(&env, get_admin(&env)?, recovery, remaining);
+        }
+
+        Ok(remaining)
+    }
+
+    /// Hands the admin role to the recovery address once the admin has been
+    /// inactive for `ADMIN_RECOVERY_INACTIVITY_SECONDS`.
+    pub fn claim_admin(env: Env) -> Result<(), ContractError> {
+        let recovery = get_admin_recovery(&env).ok_or(ContractError::AdminRecoveryNotSet)?;
+        recovery.require_auth();
+
+        if env.ledger().timestamp() < admin_recovery_deadline(&env) {
+            return Err(ContractError::AdminStillActive);
+        }
+
+        let previous_admin = get_admin(&env)?;
+        set_admin(&env, &recovery);
+        set_last_admin_activity(&env, env.ledger().timestamp());
+        record_config_change(&env, "admin", recovery.clone(), previous_admin.clone(), recovery.clone());
+
+        emit_admin_recovered(&env, previous_admin, recovery);
+
+        Ok(())
+    }
+
+    /// Sets the guardian that co-signs emergency operations. Replacing an
+    /// existing guardian also requires its approval.
+    pub fn set_guardian(env: Env, guardian: Address) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+
+        if let Ok(current) = get_guardian(&env) {
+            current.require_auth();
+        }
+
+        let previous = get_guardian(&env).ok();
+        set_guardian(&env, &guardian);
+        record_config_change(&env, "guardian", admin.clone(), previous, Some(guardian.clone()));
+        emit_guardian_set(&env, admin, guardian);
+
+        Ok(())
+    }
+
+    /// Registers the vault that receives emergency evacuations. The vault only
+    /// becomes usable `RECOVERY_VAULT_DELAY_SECONDS` after registration.
+    pub fn set_recovery_vault(env: Env, vault: Address) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+        get_guardian(&env)?.require_auth();
+
+        let active_at = env
+            .ledger()
+            .timestamp()
+            .checked_add(RECOVERY_VAULT_DELAY_SECONDS)
+            .ok_or(ContractError::Overflow)?;
+
+        let recovery_vault = RecoveryVault {
+            address: vault.clone(),
+            active_at,
+        };
+        let previous = get_recovery_vault(&env);
+        set_recovery_vault(&env, &recovery_vault);
+        record_config_change(&env, "recovery_vault", admin.clone(), previous, Some(recovery_vault));
+        emit_recovery_vault_set(&env, admin, vault, active_at);
+
+        Ok(())
+    }
+
+    /// Moves the contract's entire balance of `token` to the registered
+    /// recovery vault and locks the contract until `resume_after_evacuation`.
+    /// Requires both the admin and the guardian.
+    pub fn emergency_evacuate(
+        env: Env,
+        token: Address,
+        recovery_vault: Address,
+    ) -> Result<i128, ContractError> {
+        let admin = require_admin(&env)?;
+        let guardian = get_guardian(&env)?;
+        guardian.require_auth();
+
+        let vault = get_recovery_vault(&env).ok_or(ContractError::InvalidRecoveryVault)?;
+        if vault.address != recovery_vault || env.ledger().timestamp() < vault.active_at {
+            return Err(ContractError::InvalidRecoveryVault);
+        }
+
+        let token_client = token::Client::new(&env, &token);
+        let amount = token_client.balance(&env.current_contract_address());
+        if amount > 0 {
+            transfer_token(&env, &token, &env.current_contract_address(), &recovery_vault, amount, TokenTransferStage::Withdrawal)?;
+        }
+
+        set_paused(&env, true);
+        set_pause_info(
+            &env,
+            Some(&PauseInfo {
+                paused_by: admin.clone(),
+                reason: Some(symbol_short!("evacuate")),
+                paused_at: env.ledger().timestamp(),
+                pause_until: None,
+            }),
+        );
+        set_evacuated(&env, true);
+
+        emit_evacuated(&env, admin, guardian, token, recovery_vault, amount);
+
+        Ok(amount)
+    }
+
+    /// Post-mortem step that lifts the evacuation lock. The contract stays
+    /// paused; the admin must still call `unpause` to resume settlements.
+    pub fn resume_after_evacuation(env: Env) -> Result<(), ContractError> {
+        let admin = require_admin(&env)?;
+        let guardian = get_guardian(&env)?;
+        guardian.require_auth();
+
+        if !is_evacuated(&env) {
+            return Err(ContractError::InvalidStatus);
+        }
+
+        set_evacuated(&env, false);
+        emit_evacuation_resumed(&env, admin, guardian);
+
+        Ok(())
+    }
+
+    pub fn get_recovery_vault(env: Env) -> Option<RecoveryVault> {
+        get_recovery_vault(&env)
+    }
+
+    pub fn is_evacuated(env: Env) -> bool {
+        is_evacuated(&env)
+    }
+}
+
+/// Reads the token metadata through fallible calls so a non-token address
+/// surfaces as `NotATokenContract` instead of trapping.
+fn probe_token(env: &Env, token: &Address) -> Result<TokenInfo, ContractError> {
+    let token_client = token::Client::new(env, token);
+
+    let name = token_client.try_name().ok().and_then(Result::ok);
+    let symbol = token_client.try_symbol().ok().and_then(Result::ok);
+    let decimals = token_client.try_decimals().ok().and_then(Result::ok);
+
+    match (name, symbol, decimals) {
+        (Some(name), Some(symbol), Some(decimals)) => Ok(TokenInfo {
+            name,
+            symbol,
+            decimals,
+            probed_at: env.ledger().sequence(),
+        }),
+        _ => Err(ContractError::NotATokenContract),
+    }
+}
+
+/// Fee rate for remittances in `token`: its override if set, otherwise the
+/// global platform fee.
+fn effective_fee_bps(env: &Env, token: &Address) -> Result<u32, ContractError> {
+    match get_token_fee_bps(env, token) {
+        Some(fee_bps) => Ok(fee_bps),
+        None => get_platform_fee_bps(env),
+    }
+}
+
+/// Fee charged on `amount`: from the fee strategy contract when one is
+/// configured, otherwise at the effective fee rate.
+fn compute_fee(
+    env: &Env,
+    sender: &Address,
+    agent: Option<&Address>,
+    amount: i128,
+    currency: &String,
+    country: &String,
+) -> Result<i128, ContractError> {
+    let usdc_token = get_usdc_token(env)?;
+
+    let Some(strategy) = get_fee_strategy(env) else {
+        return calculate_fee(amount, effective_fee_bps(env, &usdc_token)?);
+    };
+
+    let args = soroban_sdk::vec![
+        env,
+        sender.into_val(env),
+        agent.cloned().into_val(env),
+        amount.into_val(env),
+        currency.into_val(env),
+        country.into_val(env),
+        usdc_token.into_val(env),
+    ];
+    let breakdown = match env.try_invoke_contract::<FeeBreakdown, soroban_sdk::Error>(
+        &strategy.contract,
+        &Symbol::new(env, FEE_STRATEGY_FUNCTION),
+        args,
+    ) {
+        Ok(Ok(breakdown)) => breakdown,
+        _ => return Err(ContractError::FeeStrategyFailed),
+    };
+
+    let max_fee = calculate_fee(amount, strategy.max_fee_bps)?;
+    if breakdown.fee < 0 || breakdown.fee > max_fee {
+        return Err(ContractError::FeeStrategyFailed);
+    }
+
+    Ok(breakdown.fee)
+}
+
+/// Basis-point share of `amount`, rounded down. Callers always derive the
+/// other side of a split by subtraction (payout = amount - fee, platform
+/// share = fee - rebate), so the rounding remainder stays with that side and
+/// no unowned dust accumulates in the contract.
+fn calculate_fee(amount: i128, fee_bps: u32) -> Result<i128, ContractError> {
+    amount
+        .checked_mul(fee_bps as i128)
+        .ok_or(ContractError::Overflow)?
+        .checked_div(10000)
+        .ok_or(ContractError::Overflow)
+}
+
+/// Publishes a `config/changed` event for a configuration setter so drift
+/// can be watched on one topic. The values are hashed over their XDR
+/// encoding; keyed settings pass the key alongside the value. Nothing is
+/// emitted when the value is unchanged.
+fn record_config_change<T: IntoVal<Env, Val>>(env: &Env, field: &str, actor: Address, previous: T, current: T) {
+    let old_hash: BytesN<32> = env.crypto().sha256(&previous.to_xdr(env)).into();
+    let new_hash: BytesN<32> = env.crypto().sha256(&current.to_xdr(env)).into();
+    if old_hash != new_hash {
+        emit_config_changed(env, Symbol::new(env, field), actor, old_hash, new_hash);
+    }
+}
+
+/// Stores a remittance after a state change and runs the expiring-soon
+/// check on it, so every interaction that updates a Pending remittance
+/// inside the warning window emits the warning.
+fn save_remittance(env: &Env, remittance: &Remittance) {
+    set_remittance(env, remittance.id, remittance);
+    warn_if_expiring(env, remittance);
+}
+
+/// Emits the expiring-soon warning for a Pending remittance inside the
+/// configured warning window, once per remittance. Returns whether it did.
+fn warn_if_expiring(env: &Env, remittance: &Remittance) -> bool {
+    let (Some(warning_seconds), Some(expiry)) = (get_expiry_warning_seconds(env), remittance.expiry) else {
+        return false;
+    };
+    let now = env.ledger().timestamp();
+    if remittance.status != RemittanceStatus::Pending
+        || now > expiry
+        || expiry - now > warning_seconds
+        || is_expiry_warned(env, remittance.id)
+    {
+        return false;
+    }
+
+    set_expiry_warned(env, remittance.id, expiry - now + 1);
+    emit_remittance_expiring_soon(env, remittance.id, remittance.sender.clone(), remittance.agent.clone(), expiry, expiry - now);
+    true
+}
+
+/// Scales down the fees of batch remittances on `Net` corridors that have
+/// an opposing flow in the same batch, so each direction between a sender
+/// and an agent pays `fee * net / gross` of the pair's flows. The waived
+/// fees stay in the payout. Returns the adjusted remittances and the total
+/// waived.
+fn apply_net_fees(env: &Env, remittances: Vec<Remittance>) -> Result<(Vec<Remittance>, i128), ContractError> {
+    let mut flows: Map<(Address, Address), i128> = Map::new(env);
+    for remittance in remittances.iter() {
+        if remittance.sender == remittance.agent
+            || get_corridor_fee_mode(env, &remittance.currency, &remittance.country) != NettingFeeMode::Net
+        {
+            continue;
+        }
+        let pair = (remittance.sender.clone(), remittance.agent.clone());
+        let flow = flows
+            .get(pair.clone())
+            .unwrap_or(0)
+            .checked_add(remittance.amount)
+            .ok_or(ContractError::Overflow)?;
+        flows.set(pair, flow);
+    }
+
+    let mut adjusted = Vec::new(env);
+    let mut fee_discount: i128 = 0;
+    for mut remittance in remittances.iter() {
+        let forward = flows
+            .get((remittance.sender.clone(), remittance.agent.clone()))
+            .unwrap_or(0);
+        let reverse = flows
+            .get((remittance.agent.clone(), remittance.sender.clone()))
+            .unwrap_or(0);
+        if forward > 0 && reverse > 0 {
+            let gross = forward.checked_add(reverse).ok_or(ContractError::Overflow)?;
+            let net = forward.abs_diff(reverse) as i128;
+            let fee = remittance
+                .fee
+                .checked_mul(net)
+                .ok_or(ContractError::Overflow)?
+                / gross;
+            fee_discount = fee_discount
+                .checked_add(remittance.fee - fee)
+                .ok_or(ContractError::Overflow)?;
+            remittance.fee = fee;
+        }
+        adjusted.push_back(remittance);
+    }
+
+    Ok((adjusted, fee_discount))
+}
+
+/// Checks an offline payout approval for `remittance` against `public_key`
+/// and consumes its nonce. The signature check traps on mismatch.
+fn verify_payout_approval(
+    env: &Env,
+    remittance: &Remittance,
+    public_key: &BytesN<32>,
+    approval_payload: &PayoutApproval,
+    signature: &BytesN<64>,
+) -> Result<(), ContractError> {
+    if approval_payload.contract != env.current_contract_address() || approval_payload.remittance_id != remittance.id {
+        return Err(ContractError::AgentApprovalMissing);
+    }
+    let now = env.ledger().timestamp();
+    if now > approval_payload.deadline {
+        return Err(ContractError::SettlementExpired);
+    }
+    if is_payout_nonce_used(env, &remittance.agent, approval_payload.nonce) {
+        return Err(ContractError::DuplicateSettlement);
+    }
+
+    env.crypto()
+        .ed25519_verify(public_key, &approval_payload.clone().to_xdr(env), signature);
+    set_payout_nonce_used(env, &remittance.agent, approval_payload.nonce, approval_payload.deadline - now + 1);
+
+    Ok(())
+}
+
+/// Checks a new remittance's `expiry` against its corridor's bounds. An
+/// open-ended remittance only passes when there is no maximum.
+fn check_expiry_bounds(env: &Env, bounds: &ExpiryBounds, expiry: Option<u64>) -> Result<(), ContractError> {
+    let Some(expiry) = expiry else {
+        return match bounds.max_seconds {
+            Some(_) => Err(ContractError::ExpiryTooLong),
+            None => Ok(()),
+        };
+    };
+
+    let duration = expiry.saturating_sub(env.ledger().timestamp());
+    if matches!(bounds.min_seconds, Some(min) if duration < min) {
+        return Err(ContractError::ExpiryTooShort);
+    }
+    if matches!(bounds.max_seconds, Some(max) if duration > max) {
+        return Err(ContractError::ExpiryTooLong);
+    }
+    Ok(())
+}
+
+/// Fails with `InvalidStatus` for Pull mode remittances, which only their
+/// recipient may settle.
+fn require_push_payout(remittance: &Remittance) -> Result<(), ContractError> {
+    if remittance.payout_mode == PayoutMode::Pull {
+        return Err(ContractError::InvalidStatus);
+    }
+    Ok(())
+}
+
+/// Pays out and records a settlement, to the agent in Push mode and to the
+/// recipient in Pull mode. Callers check the payee's auth.
+fn settle_payout(env: &Env, context: &SettlementContext, remittance: Remittance) -> Result<(), ContractError> {
+    check_settleable(env, context, &remittance)?;
+
+    // Validate the agent address before transfer
+    validate_address(&remittance.agent)?;
+
+    let remittance_id = remittance.id;
+    let external_ref = get_external_ref(env, remittance_id);
+
+    let payout_amount = net_amount(&remittance)?;
+
+    check_liquidity(env, &context.usdc_token, remittance.amount)?;
+
+    let recipient = match remittance.payout_mode {
+        PayoutMode::Pull => remittance.recipient.clone(),
+        PayoutMode::Push => None,
+    };
+    let payee = recipient.clone().unwrap_or_else(|| remittance.agent.clone());
+    if !hold_if_instant(env, &remittance, &payee, payout_amount)? {
+        match &recipient {
+            Some(recipient) => transfer_token(env, &context.usdc_token, &env.current_contract_address(), recipient, payout_amount, TokenTransferStage::Payout)?,
+            None => pay_agent(env, &context.usdc_token, &remittance.agent, payout_amount)?,
+        }
+    }
+
+    let mut counters = get_counters(env)?;
+    record_settlement(env, context, remittance, payout_amount, external_ref, &mut counters)?;
+    set_counters(env, &counters);
+
+    log_confirm_payout(env, remittance_id, payout_amount);
+
+    Ok(())
+}
+
+/// `token_amount` converted to fiat minor units at `rate`, rounded down.
+fn fiat_amount(token_amount: i128, rate: i128) -> Result<i128, ContractError> {
+    token_amount
+        .checked_mul(rate)
+        .map(|scaled| scaled / FX_RATE_SCALE)
+        .ok_or(ContractError::Overflow)
+}
+
+/// What the agent pays out for a remittance: its amount less the platform
+/// fee and the agent surcharge.
+fn net_amount(remittance: &Remittance) -> Result<i128, ContractError> {
+    remittance
+        .amount
+        .checked_sub(remittance.fee)
+        .and_then(|net| net.checked_sub(remittance.agent_surcharge))
+        .ok_or(ContractError::Overflow)
+}
+
+/// Credits the sender's referrer with the configured share of
+/// `platform_share` while the referral period lasts. Returns the reward.
+fn accrue_referral_reward(env: &Env, remittance: &Remittance, platform_share: i128) -> Result<i128, ContractError> {
+    let (Some(config), Some(referral)) = (get_referral_config(env), get_referral(env, &remittance.sender)) else {
+        return Ok(0);
+    };
+
+    let elapsed = env.ledger().timestamp().saturating_sub(referral.referred_at);
+    if elapsed > config.period_seconds {
+        return Ok(0);
+    }
+
+    let reward = calculate_fee(platform_share, config.share_bps)?;
+    if reward <= 0 {
+        return Ok(0);
+    }
+
+    let balance = get_referral_balance(env, &referral.referrer)
+        .checked_add(reward)
+        .ok_or(ContractError::Overflow)?;
+    set_referral_balance(env, &referral.referrer, balance);
+    emit_referral_accrued(env, remittance.id, referral.referrer, reward);
+    Ok(reward)
+}
+
+/// Credits a remittance's agent surcharge to the agent's claimable balance.
+fn accrue_agent_surcharge(env: &Env, remittance: &Remittance) -> Result<(), ContractError> {
+    if remittance.agent_surcharge <= 0 {
+        return Ok(());
+    }
+
+    let balance = get_agent_rebate_balance(env, &remittance.agent)
+        .checked_add(remittance.agent_surcharge)
+        .ok_or(ContractError::Overflow)?;
+    set_agent_rebate_balance(env, &remittance.agent, balance);
+    emit_agent_surcharge_accrued(env, remittance.id, remittance.agent.clone(), remittance.agent_surcharge);
+    Ok(())
+}
+
+/// Escrows `amount` from `sender` and records a new Pending remittance
+/// charged `fee` and `agent_surcharge`. Callers are responsible for sender
+/// authorization.
+fn open_remittance(
+    env: &Env,
+    sender: Address,
+    agent: Address,
+    amount: i128,
+    fee: i128,
+    agent_surcharge: i128,
+    currency: String,
+    country: String,
+    expiry: Option<u64>,
+    external_ref: Option<BytesN<32>>,
+    tags: Vec<Symbol>,
+    flags: u32,
+    priority_tip: i128,
+) -> Result<u64, ContractError> {
+    if is_evacuated(env) {
+        return Err(ContractError::ContractEvacuated);
+    }
+
+    if is_sender_allowlist_enabled(env) && !has_role(env, &Role::Sender, &sender) {
+        return Err(ContractError::MissingRole);
+    }
+
+    let currency = normalize_currency(env, &currency)?;
+    let country = normalize_country(env, &country)?;
+    check_corridor_open(env, &currency, &country)?;
+    validate_tags(&tags)?;
+    validate_flags(flags)?;
+    consume_rate_limit(env, &sender)?;
+
+    let expiry = match (expiry, get_default_expiry_seconds(env)) {
+        (None, Some(window)) if flags & FLAG_NO_DEFAULT_EXPIRY == 0 => Some(
+            env.ledger()
+                .timestamp()
+                .checked_add(window)
+                .ok_or(ContractError::Overflow)?,
+        ),
+        (expiry, _) => expiry,
+    };
+    if let Some(bounds) = get_corridor_expiry_bounds(env, &currency, &country) {
+        check_expiry_bounds(env, &bounds, expiry)?;
+    }
+
+    if !is_agent_registered(env, &agent) {
+        return Err(ContractError::AgentNotRegistered);
+    }
+
+    if fee.checked_add(agent_surcharge).ok_or(ContractError::Overflow)? > amount || priority_tip < 0 {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    let insurance_premium = if flags & FLAG_INSURED != 0 {
+        let premium_bps = get_insurance_premium_bps(env);
+        if premium_bps == 0 {
+            return Err(ContractError::InvalidFlags);
+        }
+        calculate_fee(amount, premium_bps)?
+    } else {
+        0
+    };
+
+    let exposure = get_sender_exposure(env, &sender)
+        .checked_add(amount)
+        .ok_or(ContractError::Overflow)?;
+    if are_exposure_caps_enabled(env) {
+        if let Some(cap) = effective_exposure_cap(env, &sender) {
+            if exposure > cap {
+                return Err(ContractError::ExposureCapExceeded);
+            }
+            emit_limit_thresholds(env, &sender, symbol_short!("exposure"), exposure - amount, exposure, cap, None);
+        }
+    }
+
+    check_self_caps(env, &sender, amount)?;
+
+    let mut counters = get_counters(env)?;
+    counters.total_locked = counters
+        .total_locked
+        .checked_add(amount)
+        .ok_or(ContractError::Overflow)?;
+    if let Some(max_total_locked) = get_max_total_locked(env) {
+        if counters.total_locked > max_total_locked {
+            return Err(ContractError::TvlCapExceeded);
+        }
+    }
+
+    let usdc_token = get_usdc_token(env)?;
+    let contract_address = env.current_contract_address();
+    let deposit = amount
+        .checked_add(insurance_premium)
+        .and_then(|deposit| deposit.checked_add(priority_tip))
+        .ok_or(ContractError::Overflow)?;
+    if flags & FLAG_PULL_FUNDING != 0 {
+        pull_token(env, &usdc_token, &sender, deposit, TokenTransferStage::Deposit)?;
+    } else {
+        transfer_token(env, &usdc_token, &sender, &contract_address, deposit, TokenTransferStage::Deposit)?;
+    }
+
+    if insurance_premium > 0 {
+        let mut fund = get_insurance_fund(env);
+        fund.premiums_collected = fund
+            .premiums_collected
+            .checked_add(insurance_premium)
+            .ok_or(ContractError::Overflow)?;
+        fund.balance = fund
+            .balance
+            .checked_add(insurance_premium)
+            .ok_or(ContractError::Overflow)?;
+        set_insurance_fund(env, &fund);
+    }
+
+    let remittance_id = counters
+        .remittance_counter
+        .checked_add(1)
+        .ok_or(ContractError::Overflow)?;
+    counters.remittance_counter = remittance_id;
+
+    let held_for_review = matches!(get_review_threshold(env), Some(threshold) if amount > threshold);
+    let status = if held_for_review {
+        RemittanceStatus::PendingReview
+    } else {
+        RemittanceStatus::Pending
+    };
+
+    let remittance = Remittance {
+        id: remittance_id,
+        sender: sender.clone(),
+        agent: agent.clone(),
+        amount,
+        fee,
+        status,
+        expiry,
+        currency: currency.clone(),
+        country: country.clone(),
+        tags: tags.clone(),
+        flags,
+        created_at: env.ledger().timestamp(),
+        sender_confirmed: false,
+        priority_tip,
+        agent_surcharge,
+        insurance_premium,
+        fiat_amount: None,
+        recipient: None,
+        payout_mode: PayoutMode::Push,
+    };
+
+    set_remittance(env, remittance_id, &remittance);
+    set_counters(env, &counters);
+    add_open_remittance(env, remittance_id);
+    index_sender_remittance(env, &sender, remittance_id);
+
+    set_sender_exposure(env, &sender, exposure);
+
+    let mut workload = get_agent_workload(env, &agent);
+    workload.pending_count = workload.pending_count.checked_add(1).ok_or(ContractError::Overflow)?;
+    workload.pending_value = workload
+        .pending_value
+        .checked_add(amount)
+        .ok_or(ContractError::Overflow)?;
+    set_agent_workload(env, &agent, &workload);
+
+    let pair_pending = get_pair_pending(env, &sender, &agent)
+        .checked_add(amount)
+        .ok_or(ContractError::Overflow)?;
+    set_pair_pending(env, &sender, &agent, pair_pending);
+
+    let mut stats = get_corridor_stats(env, &currency, &country);
+    if stats.created_count == 0 {
+        let mut tally = get_network_tally(env);
+        tally.corridors = tally.corridors.checked_add(1).ok_or(ContractError::Overflow)?;
+        set_network_tally(env, &tally);
+    }
+    stats.created_count = stats.created_count.checked_add(1).ok_or(ContractError::Overflow)?;
+    stats.created_volume = stats
+        .created_volume
+        .checked_add(amount)
+        .ok_or(ContractError::Overflow)?;
+    set_corridor_stats(env, &currency, &country, &stats);
+
+    for tag in tags.iter() {
+        let count = get_tag_count(env, &tag)
+            .checked_add(1)
+            .ok_or(ContractError::Overflow)?;
+        set_tag_count(env, &tag, count);
+    }
+
+    if let Some(external_ref) = &external_ref {
+        set_external_ref(env, remittance_id, &sender, external_ref);
+    }
+
+    emit_remittance_created(env, remittance_id, sender.clone(), agent.clone(), usdc_token.clone(), amount, fee, currency, country, tags, external_ref);
+
+    if priority_tip > 0 {
+        let tips = get_pending_tip_total(env)
+            .checked_add(priority_tip)
+            .ok_or(ContractError::Overflow)?;
+        set_pending_tip_total(env, tips);
+        emit_priority_tip_added(env, remittance_id, sender.clone(), priority_tip, priority_tip);
+    }
+
+    if held_for_review {
+        emit_review_held(env, remittance_id, amount);
+    }
+
+    log_create_remittance(env, remittance_id, &sender, &agent, amount, fee);
+
+    Ok(remittance_id)
+}
+
+/// Removes a remittance that is leaving the Pending state from the running
+/// totals kept for its agent and sender. `counters` is saved by the caller.
+fn release_pending_totals(env: &Env, remittance: &Remittance, counters: &mut Counters) -> Result<(), ContractError> {
+    remove_open_remittance(env, remittance.id, counters.remittance_counter);
+
+    let exposure = get_sender_exposure(env, &remittance.sender)
+        .checked_sub(remittance.amount)
+        .ok_or(ContractError::Overflow)?;
+    set_sender_exposure(env, &remittance.sender, exposure);
+
+    counters.total_locked = counters
+        .total_locked
+        .checked_sub(remittance.amount)
+        .ok_or(ContractError::Overflow)?;
+
+    if remittance.priority_tip > 0 {
+        let tips = get_pending_tip_total(env)
+            .checked_sub(remittance.priority_tip)
+            .ok_or(ContractError::Overflow)?;
+        set_pending_tip_total(env, tips);
+    }
+
+    let mut workload = get_agent_workload(env, &remittance.agent);
+    workload.pending_count = workload.pending_count.checked_sub(1).ok_or(ContractError::Overflow)?;
+    workload.pending_value = workload
+        .pending_value
+        .checked_sub(remittance.amount)
+        .ok_or(ContractError::Overflow)?;
+    set_agent_workload(env, &remittance.agent, &workload);
+
+    let pair_pending = get_pair_pending(env, &remittance.sender, &remittance.agent)
+        .checked_sub(remittance.amount)
+        .ok_or(ContractError::Overflow)?;
+    set_pair_pending(env, &remittance.sender, &remittance.agent, pair_pending);
+    Ok(())
+}
+
+/// Checks that new remittances may be opened on a corridor: it has not been
+/// switched off and, when the registry is enforced, both codes are allowed.
+/// Codes must already be normalized.
+fn check_corridor_open(env: &Env, currency: &String, country: &String) -> Result<(), ContractError> {
+    if !is_corridor_enabled(env, currency, country) {
+        return Err(ContractError::CorridorDisabled);
+    }
+    if !is_corridor_registry_enabled(env) {
+        return Ok(());
+    }
+    if !get_allowed_currencies(env).contains(currency) {
+        return Err(ContractError::CorridorDisabled);
+    }
+    if !get_allowed_countries(env).contains(country) {
+        return Err(ContractError::CorridorDisabled);
+    }
+    Ok(())
+}
+
+fn update_registry(mut codes: Vec<String>, code: &String, allowed: bool) -> Vec<String> {
+    match (codes.first_index_of(code), allowed) {
+        (None, true) => codes.push_back(code.clone()),
+        (Some(index), false) => {
+            codes.remove(index);
+        }
+        _ => {}
+    }
+    codes
+}
+
+/// See `SwiftRemitContract::is_settlement_final`.
+fn is_settlement_final(env: &Env, remittance: &Remittance) -> Result<bool, ContractError> {
+    if remittance.status != RemittanceStatus::Completed {
+        return Ok(false);
+    }
+
+    if let Some(challenge) = get_settlement_challenge(env, remittance.id) {
+        return Ok(challenge.resolved && !challenge.upheld);
+    }
+
+    let Some(config) = get_challenge_config(env) else {
+        return Ok(true);
+    };
+    let proof = get_settlement_proof(env, remittance.id).ok_or(ContractError::InvalidStatus)?;
+    Ok(env.ledger().timestamp() > proof.settled_at.saturating_add(config.period_seconds))
+}
+
+fn cooldown_remaining(env: &Env, sender: &Address) -> u64 {
+    let (Some(config), Some(last)) = (get_rate_limit_config(env), get_last_settlement_time(env, sender)) else {
+        return 0;
+    };
+    last.saturating_add(config.cooldown_seconds)
+        .saturating_sub(env.ledger().timestamp())
+}
+
+fn window_remaining(env: &Env, address: &Address) -> u64 {
+    let Some(config) = get_rate_limit_config(env) else {
+        return 0;
+    };
+    if config.max_per_window == 0 {
+        return 0;
+    }
+
+    let window = get_rate_limit_window(env, address);
+    let resets_at = window.started_at.saturating_add(config.window_seconds);
+    let now = env.ledger().timestamp();
+    if window.count < config.max_per_window || now >= resets_at {
+        return 0;
+    }
+    resets_at - now
+}
+
+/// Enforces the rate limit for `sender` and records the new remittance
+/// against its cooldown and window.
+fn consume_rate_limit(env: &Env, sender: &Address) -> Result<(), ContractError> {
+    let Some(config) = get_rate_limit_config(env) else {
+        return Ok(());
+    };
+
+    if cooldown_remaining(env, sender) > 0 || window_remaining(env, sender) > 0 {
+        return Err(ContractError::RateLimited);
+    }
+
+    let now = env.ledger().timestamp();
+    set_last_settlement_time(env, sender, now, config.cooldown_seconds);
+
+    if config.max_per_window > 0 {
+        let mut window = get_rate_limit_window(env, sender);
+        if window.count == 0 || now >= window.started_at.saturating_add(config.window_seconds) {
+            window = RateLimitWindow { started_at: now, count: 0 };
+        }
+        window.count = window.count.checked_add(1).ok_or(ContractError::Overflow)?;
+        set_rate_limit_window(env, sender, &window, config.window_seconds);
+        emit_limit_thresholds(
+            env,
+            sender,
+            symbol_short!("rate"),
+            (window.count - 1).into(),
+            window.count.into(),
+            config.max_per_window.into(),
+            Some(window.started_at.saturating_add(config.window_seconds)),
+        );
+    }
+
+    Ok(())
+}
+
+/// `cap` with its pending raise applied once `raise_at` has passed.
+fn matured_self_cap(env: &Env, cap: SelfCap) -> SelfCap {
+    if cap.raise_at == 0 || env.ledger().timestamp() < cap.raise_at {
+        return cap;
+    }
+    SelfCap {
+        limit: cap.pending,
+        pending: None,
+        raise_at: 0,
+    }
+}
+
+/// Applies a sender's requested change to one of their caps: at once when it
+/// is at least as strict as the current limit, otherwise as a pending raise.
+fn update_self_cap(env: &Env, cap: SelfCap, requested: Option<i128>) -> Result<SelfCap, ContractError> {
+    if matches!(requested, Some(limit) if limit <= 0) {
+        return Err(ContractError::InvalidAmount);
+    }
+
+    let cap = matured_self_cap(env, cap);
+    let lowering = match (requested, cap.limit) {
+        (Some(requested), Some(current)) => requested <= current,
+        (Some(_), None) => true,
+        (None, current) => current.is_none(),
+    };
+
+    if lowering {
+        return Ok(SelfCap {
+            limit: requested,
+            pending: None,
+            raise_at: 0,
+        });
+    }
+
+    let raise_at = env
+        .ledger()
+        .timestamp()
+        .checked_add(SELF_CAP_RAISE_DELAY_SECONDS)
+        .ok_or(ContractError::Overflow)?;
+    Ok(SelfCap {
+        limit: cap.limit,
+        pending: requested,
+        raise_at,
+    })
+}
+
+/// Enforces the sender's own per-remittance and daily caps on a new
+/// remittance of `amount` and records it against today's spend. Fails with
+/// ExposureCapExceeded.
+fn check_self_caps(env: &Env, sender: &Address, amount: i128) -> Result<(), ContractError> {
+    if let Some(max_tx) = matured_self_cap(env, get_self_max_tx(env, sender)).limit {
+        if amount > max_tx {
+            return Err(ContractError::ExposureCapExceeded);
+        }
+    }
+
+    let Some(daily_cap) = matured_self_cap(env, get_self_daily_cap(env, sender)).limit else {
+        return Ok(());
+    };
+
+    let now = env.ledger().timestamp();
+    let day = now / SECONDS_PER_DAY;
+    let mut spend = get_daily_spend(env, sender);
+    if spend.day != day {
+        spend = DailySpend { day, spent: 0 };
+    }
+
+    let before = spend.spent;
+    spend.spent = spend.spent.checked_add(amount).ok_or(ContractError::Overflow)?;
+    if spend.spent > daily_cap {
+        return Err(ContractError::ExposureCapExceeded);
+    }
+
+    let resets_at = (day + 1) * SECONDS_PER_DAY;
+    set_daily_spend(env, sender, &spend, resets_at - now);
+    emit_limit_thresholds(env, sender, symbol_short!("daily"), before, spend.spent, daily_cap, Some(resets_at));
+    Ok(())
+}
+
+/// Days of `agent`'s receipts from the corridor still inside the receive
+/// window, and their total.
+fn received_in_window(env: &Env, agent: &Address, currency: &String, country: &String) -> Result<(Vec<DailySpend>, i128), ContractError> {
+    let today = env.ledger().timestamp() / SECONDS_PER_DAY;
+    let mut days = Vec::new(env);
+    let mut total: i128 = 0;
+
+    for receipt in get_agent_received(env, agent, currency, country).iter() {
+        if receipt.day + RECEIVE_LIMIT_WINDOW_DAYS <= today {
+            continue;
+        }
+        total = total.checked_add(receipt.spent).ok_or(ContractError::Overflow)?;
+        days.push_back(receipt);
+    }
+
+    Ok((days, total))
+}
+
+/// Records a settlement payout against the agent's rolling receive usage
+/// in the remittance's corridor, if the corridor has a receive limit. Fails
+/// with ExposureCapExceeded when the limit would be exceeded.
+fn record_agent_receipt(env: &Env, remittance: &Remittance, payout_amount: i128) -> Result<(), ContractError> {
+    let (agent, currency, country) = (&remittance.agent, &remittance.currency, &remittance.country);
+    let Some(limit) = get_corridor_receive_limit(env, currency, country) else {
+        return Ok(());
+    };
+    let (mut days, before) = received_in_window(env, agent, currency, country)?;
+
+    let after = before.checked_add(payout_amount).ok_or(ContractError::Overflow)?;
+    if after > limit {
+        return Err(ContractError::ExposureCapExceeded);
+    }
+
+    let today = env.ledger().timestamp() / SECONDS_PER_DAY;
+    let today_index = days.len().checked_sub(1).filter(|index| days.get_unchecked(*index).day == today);
+    match today_index {
+        Some(index) => {
+            let mut receipt = days.get_unchecked(index);
+            receipt.spent = receipt.spent.checked_add(payout_amount).ok_or(ContractError::Overflow)?;
+            days.set(index, receipt);
+        }
+        None => days.push_back(DailySpend { day: today, spent: payout_amount }),
+    }
+
+    let window_ends_at = (today + RECEIVE_LIMIT_WINDOW_DAYS) * SECONDS_PER_DAY;
+    set_agent_received(env, agent, currency, country, &days, window_ends_at - env.ledger().timestamp());
+    emit_limit_thresholds(env, agent, symbol_short!("receive"), before, after, limit, None);
+    Ok(())
+}
+
+/// Emits a `limit` event for each alert threshold that usage crossed going
+/// from `before` to `after`.
+fn emit_limit_thresholds(
+    env: &Env,
+    subject: &Address,
+    limit_type: Symbol,
+    before: i128,
+    after: i128,
+    limit: i128,
+    resets_at: Option<u64>,
+) {
+    if limit <= 0 {
+        return;
+    }
+    for threshold_pct in LIMIT_ALERT_THRESHOLDS_PCT {
+        let threshold = limit.saturating_mul(threshold_pct as i128);
+        if before.saturating_mul(100) < threshold && after.saturating_mul(100) >= threshold {
+            emit_limit_threshold_crossed(env, subject.clone(), limit_type.clone(), threshold_pct, after, limit, resets_at);
+        }
+    }
+}
+
+/// Requires the admin's auth and records the admin as active, deferring
+/// admin recovery.
+fn require_admin(env: &Env) -> Result<Address, ContractError> {
+    let admin = get_admin(env)?;
+    admin.require_auth();
+    set_last_admin_activity(env, env.ledger().timestamp());
+    Ok(admin)
+}
+
+/// Requires `caller`'s auth and that it is either the admin or holds `role`.
+fn require_admin_or_role(env: &Env, caller: &Address, role: &Role) -> Result<(), ContractError> {
+    caller.require_auth();
+
+    if *caller == get_admin(env)? {
+        set_last_admin_activity(env, env.ledger().timestamp());
+    } else if !has_role(env, role, caller) {
+        return Err(ContractError::MissingRole);
+    }
+    Ok(())
+}
+
+/// Fails with `MissingRole` while private mode is on, for getters that do
+/// not know who is reading.
+fn require_public_book(env: &Env) -> Result<(), ContractError> {
+    match is_private_mode(env) {
+        true => Err(ContractError::MissingRole),
+        false => Ok(()),
+    }
+}
+
+/// Requires `viewer`'s auth and, while private mode is on, that it is a
+/// party to `remittance`, the admin or an Auditor.
+fn require_remittance_reader(env: &Env, viewer: &Address, remittance: &Remittance) -> Result<(), ContractError> {
+    viewer.require_auth();
+
+    if !is_private_mode(env)
+        || *viewer == remittance.sender
+        || *viewer == remittance.agent
+        || *viewer == get_admin(env)?
+        || has_role(env, &Role::Auditor, viewer)
+    {
+        return Ok(());
+    }
+    Err(ContractError::MissingRole)
+}
+
+fn export_remittance_page(
+    env: &Env,
+    start_id: u64,
+    limit: u32,
+    status_filter: Option<RemittanceStatus>,
+) -> Result<RemittanceExportPage, ContractError> {
+    let last_id = get_counters(env)?.remittance_counter;
+    let mut rows = Vec::new(env);
+    let mut next_id = start_id.max(1);
+    let scan_end = next_id.saturating_add(MAX_EXPORT_SCAN);
+
+    while next_id <= last_id && next_id < scan_end && rows.len() < limit {
+        let remittance_id = next_id;
+        next_id += 1;
+
+        let Ok(remittance) = get_remittance(env, remittance_id) else {
+            continue;
+        };
+        if matches!(&status_filter, Some(status) if *status != remittance.status) {
+            continue;
+        }
+
+        rows.push_back(RemittanceRow {
+            id: remittance.id,
+            payout_amount: net_amount(&remittance)?,
+            fiat_amount: remittance.fiat_amount.unwrap_or(0),
+            settled_at: get_settlement_proof(env, remittance_id).map_or(0, |proof| proof.settled_at),
+            sender: remittance.sender,
+            agent: remittance.agent,
+            currency: remittance.currency,
+            country: remittance.country,
+            status: remittance.status,
+            amount: remittance.amount,
+            fee: remittance.fee,
+            agent_surcharge: remittance.agent_surcharge,
+            insurance_premium: remittance.insurance_premium,
+            priority_tip: remittance.priority_tip,
+            created_at: remittance.created_at,
+            expiry: remittance.expiry.unwrap_or(0),
+        });
+    }
+
+    Ok(RemittanceExportPage {
+        rows,
+        next_start_id: (next_id <= last_id).then_some(next_id),
+    })
+}
+
+fn admin_recovery_deadline(env: &Env) -> u64 {
+    get_last_admin_activity(env).saturating_add(ADMIN_RECOVERY_INACTIVITY_SECONDS)
+}
+
+/// Books a settlement whose payout has been, or is about to be, transferred
+/// to the agent: fees and rebates, status, pending totals, stats, the
+/// settlement proof and completion events. `counters` is saved by the caller
+/// so batches write it once.
+fn record_settlement(
+    env: &Env,
+    context: &SettlementContext,
+    mut remittance: Remittance,
+    payout_amount: i128,
+    external_ref: Option<BytesN<32>>,
+    counters: &mut Counters,
+) -> Result<(), ContractError> {
+    let remittance_id = remittance.id;
+
+    let usdc_token = &context.usdc_token;
+    let rebate = calculate_fee(remittance.fee, context.agent_rebate_bps)?;
+    let platform_share = remittance
+        .fee
+        .checked_sub(rebate)
+        .ok_or(ContractError::Overflow)?;
+
+    // Late settlement compensates the sender out of the platform's share
+    let sla_penalty = sla_penalty(env, context, &remittance, platform_share)?;
+    let platform_share = platform_share
+        .checked_sub(sla_penalty)
+        .ok_or(ContractError::Overflow)?;
+    if sla_penalty > 0 {
+        transfer_token(env, usdc_token, &env.current_contract_address(), &remittance.sender, sla_penalty, TokenTransferStage::Refund)?;
+    }
+
+    let platform_share = platform_share
+        .checked_sub(accrue_referral_reward(env, &remittance, platform_share)?)
+        .ok_or(ContractError::Overflow)?;
+
+    counters.accumulated_fees = counters
+        .accumulated_fees
+        .checked_add(platform_share)
+        .and_then(|fees| fees.checked_add(remittance.priority_tip))
+        .ok_or(ContractError::Overflow)?;
+    accrue_epoch_fees(env, remittance.fee, platform_share, remittance.priority_tip, rebate)?;
+
+    if rebate > 0 {
+        let balance = get_agent_rebate_balance(env, &remittance.agent)
+            .checked_add(rebate)
+            .ok_or(ContractError::Overflow)?;
+        set_agent_rebate_balance(env, &remittance.agent, balance);
+        emit_agent_rebate_accrued(env, remittance_id, remittance.agent.clone(), rebate);
+    }
+    accrue_agent_surcharge(env, &remittance)?;
+    record_agent_receipt(env, &remittance, payout_amount)?;
+
+    // Netting can lower the fee after the rate was locked; keep the fiat
+    // side in step with the payout actually made
+    if let Some(fx_lock) = get_fx_lock(env, remittance_id) {
+        remittance.fiat_amount = Some(fiat_amount(payout_amount, fx_lock.rate)?);
+    }
+    remittance.status = RemittanceStatus::Completed;
+    save_remittance(env, &remittance);
+    emit_watch_events(env, &remittance);
+
+    release_pending_totals(env, &remittance, counters)?;
+
+    let mut stats = get_corridor_stats(env, &remittance.currency, &remittance.country);
+    stats.completed_count = stats.completed_count.checked_add(1).ok_or(ContractError::Overflow)?;
+    stats.completed_volume = stats
+        .completed_volume
+        .checked_add(remittance.amount)
+        .ok_or(ContractError::Overflow)?;
+    set_corridor_stats(env, &remittance.currency, &remittance.country, &stats);
+
+    let mut reputation = get_agent_reputation(env, &remittance.agent);
+    reputation.completed_count = reputation.completed_count.checked_add(1).ok_or(ContractError::Overflow)?;
+    set_agent_reputation(env, &remittance.agent, &reputation);
+
+    // Mark settlement as executed to prevent duplicates
+    let proof = build_settlement_proof(env, &remittance);
+    set_settlement_hash(env, remittance_id, &proof.hash);
+    set_settlement_proof(env, remittance_id, &proof);
+
+    counters.total_settlements = counters
+        .total_settlements
+        .checked_add(1)
+        .ok_or(ContractError::Overflow)?;
+
+    let mut tally = get_network_tally(env);
+    tally.settlement_seconds = tally
+        .settlement_seconds
+        .checked_add(env.ledger().timestamp().saturating_sub(remittance.created_at))
+        .ok_or(ContractError::Overflow)?;
+    set_network_tally(env, &tally);
+
+    emit_remittance_completed(env, remittance_id, remittance.sender.clone(), remittance.agent.clone(), usdc_token.clone(), payout_amount, remittance.currency.clone(), remittance.country.clone(), remittance.fiat_amount, external_ref.clone());
+
+    // Emit settlement completed event with final executed values
+    emit_settlement_completed(env, remittance.sender.clone(), remittance.agent.clone(), usdc_token.clone(), payout_amount, remittance.amount, remittance.fee, sla_penalty, external_ref);
+
+    if proof.fx_rate > 0 {
+        emit_fx_settled(env, remittance_id, usdc_token.clone(), payout_amount, remittance.currency.clone(), proof.fx_destination_amount, proof.fx_rate);
+    }
+
+    notify_sender_contract(env, &remittance);
+
+    Ok(())
+}
+
+/// Publishes the remittance's new status once per watcher tag.
+fn emit_watch_events(env: &Env, remittance: &Remittance) {
+    for watcher_tag in get_watch_tags(env, remittance.id).iter() {
+        emit_watched_status(env, remittance.id, watcher_tag, remittance.status.clone());
+    }
+}
+
+/// Best-effort call to the sender's notification contract, if any. A failing
+/// or missing callee never fails the calling operation; the failure is only
+/// reported through a `notify/failed` event.
+fn notify_sender_contract(env: &Env, remittance: &Remittance) {
+    let Some(target) = get_notification_contract(env, &remittance.sender) else {
+        return;
+    };
+
+    let notice = RemittanceNotice {
+        remittance_id: remittance.id,
+        status: remittance.status.clone(),
+        sender: remittance.sender.clone(),
+        agent: remittance.agent.clone(),
+        amount: remittance.amount,
+        fee: remittance.fee,
+    };
+    let args = soroban_sdk::vec![env, notice.into_val(env)];
+
+    let result = env.try_invoke_contract::<(), soroban_sdk::Error>(
+        &target,
+        &Symbol::new(env, NOTIFICATION_FUNCTION),
+        args,
+    );
+    if result.is_err() {
+        emit_notification_failed(env, remittance.id, target);
+    }
+}
+
+/// Portion of `platform_share` owed to the sender because `remittance` is
+/// settling later than its corridor SLA allows.
+fn sla_penalty(env: &Env, context: &SettlementContext, remittance: &Remittance, platform_share: i128) -> Result<i128, ContractError> {
+    let Some(max_seconds) = get_corridor_sla(env, &remittance.currency, &remittance.country) else {
+        return Ok(0);
+    };
+
+    let elapsed = env.ledger().timestamp().saturating_sub(remittance.created_at);
+    if elapsed <= max_seconds {
+        return Ok(0);
+    }
+
+    calculate_fee(platform_share, context.sla_penalty_bps)
+}
+
+/// Refunds a remittance's amount and tip to its sender, moves it to
+/// `status` and drops it from the pending totals. Returns the token and the
+/// refunded amount.
+fn refund_sender(env: &Env, remittance: &mut Remittance, status: RemittanceStatus) -> Result<(Address, i128), ContractError> {
+    let refund = remittance
+        .amount
+        .checked_add(remittance.priority_tip)
+        .ok_or(ContractError::Overflow)?;
+
+    let usdc_token = get_usdc_token(env)?;
+    transfer_token(env, &usdc_token, &env.current_contract_address(), &remittance.sender, refund, TokenTransferStage::Refund)?;
+
+    remittance.status = status;
+    save_remittance(env, remittance);
+    emit_watch_events(env, remittance);
+
+    let mut counters = get_counters(env)?;
+    release_pending_totals(env, remittance, &mut counters)?;
+    set_counters(env, &counters);
+
+    notify_sender_contract(env, remittance);
+
+    Ok((usdc_token, refund))
+}
+
+/// Books the fee of a Pending remittance and moves its net amount into a
+/// milestone escrow. Sender auth is checked by callers.
+fn convert_pending_to_escrow(env: &Env, mut remittance: Remittance, milestones: Vec<i128>) -> Result<(), ContractError> {
+    let remittance_id = remittance.id;
+
+    if remittance.status != RemittanceStatus::Pending {
+        return Err(ContractError::InvalidStatus);
+    }
+
+    let net_amount = net_amount(&remittance)?;
+    validate_milestones(&milestones, net_amount)?;
+
+    let mut counters = get_counters(env)?;
+    counters.accumulated_fees = counters
+        .accumulated_fees
+        .checked_add(remittance.fee)
+        .and_then(|fees| fees.checked_add(remittance.priority_tip))
+        .ok_or(ContractError::Overflow)?;
+    accrue_epoch_fees(env, remittance.fee, remittance.fee, remittance.priority_tip, 0)?;
+    accrue_agent_surcharge(env, &remittance)?;
+
+    remittance.status = RemittanceStatus::Converted;
+    save_remittance(env, &remittance);
+    emit_watch_events(env, &remittance);
+
+    release_pending_totals(env, &remittance, &mut counters)?;
+    set_counters(env, &counters);
+
+    let escrow = Escrow {
+        remittance_id,
+        sender: remittance.sender.clone(),
+        agent: remittance.agent.clone(),
+        milestones: milestones.clone(),
+        released_count: 0,
+        released_amount: 0,
+        status: EscrowStatus::Active,
+        proposed_amount: None,
+        sender_share_bps: None,
+    };
+    set_escrow(env, remittance_id, &escrow);
+    index_escrow(env, &escrow);
+
+    let usdc_token = get_usdc_token(env)?;
+    emit_escrow_converted(env, remittance_id, remittance.sender, remittance.agent, usdc_token, milestones, EscrowStatus::Active);
+
+    Ok(())
+}
+
+/// Stores the compliance decision on a held remittance, with the time it
+/// was held (its creation) and the time it was decided.
+fn record_review(env: &Env, remittance: &Remittance, reviewer: Address, approved: bool) -> ReviewRecord {
+    let review = ReviewRecord {
+        reviewer,
+        approved,
+        requested_at: remittance.created_at,
+        reviewed_at: env.ledger().timestamp(),
+    };
+    set_review(env, remittance.id, &review);
+    review
+}
+
+/// Refunds a Pending or PayoutFailed remittance's amount and tip to
+/// `refund_to`, marks it Cancelled and returns the refund. Sender auth is checked by callers.
+/// While paused this fails unless paused refunds are allowed.
+fn cancel_pending_remittance(
+    env: &Env,
+    context: &SettlementContext,
+    mut remittance: Remittance,
+    refund_to: &Address,
+) -> Result<i128, ContractError> {
+    let remittance_id = remittance.id;
+
+    if context.paused && !are_paused_refunds_allowed(env) {
+        return Err(ContractError::ContractPaused);
+    }
+    if !matches!(remittance.status, RemittanceStatus::Pending | RemittanceStatus::PayoutFailed) {
+        return Err(ContractError::InvalidStatus);
+    }
+
+    let refund = remittance
+        .amount
+        .checked_add(remittance.priority_tip)
+        .ok_or(ContractError::Overflow)?;
+
+    let usdc_token = &context.usdc_token;
+    transfer_token(env, usdc_token, &env.current_contract_address(), refund_to, refund, TokenTransferStage::Refund)?;
+
+    remittance.status = RemittanceStatus::Cancelled;
+    save_remittance(env, &remittance);
+    emit_watch_events(env, &remittance);
+
+    let mut counters = get_counters(env)?;
+    release_pending_totals(env, &remittance, &mut counters)?;
+
+    let mut stats = get_corridor_stats(env, &remittance.currency, &remittance.country);
+    stats.cancelled_count = stats.cancelled_count.checked_add(1).ok_or(ContractError::Overflow)?;
+    set_corridor_stats(env, &remittance.currency, &remittance.country, &stats);
+
+    counters.total_cancellations = counters
+        .total_cancellations
+        .checked_add(1)
+        .ok_or(ContractError::Overflow)?;
+    set_counters(env, &counters);
+
+    emit_remittance_cancelled(env, remittance_id, remittance.sender.clone(), remittance.agent.clone(), usdc_token.clone(), remittance.amount, remittance.currency.clone(), remittance.country.clone(), get_external_ref(env, remittance_id));
+
+    log_cancel_remittance(env, remittance_id);
+
+    if context.paused {
+        emit_refund_under_pause(env, remittance_id, remittance.sender.clone(), refund_to.clone(), refund);
+    }
+
+    notify_sender_contract(env, &remittance);
+
+    Ok(refund)
+}
+
+/// Transfers `amount` of `token`, turning a failed transfer into a contract
+/// error instead of a host trap. See `fail_transfer`.
+fn transfer_token(env: &Env, token: &Address, from: &Address, to: &Address, amount: i128, stage: TokenTransferStage) -> Result<(), ContractError> {
+    let token_client = token::Client::new(env, token);
+    if matches!(token_client.try_transfer(from, to, &amount), Ok(Ok(()))) {
+        return Ok(());
+    }
+    Err(fail_transfer(env, token, from, to, amount, stage, None))
+}
+
+/// Pulls `amount` of `token` from `from` into the contract under an
+/// allowance `from` granted it, with the same failure handling as
+/// `transfer_token`.
+fn pull_token(env: &Env, token: &Address, from: &Address, amount: i128, stage: TokenTransferStage) -> Result<(), ContractError> {
+    let token_client = token::Client::new(env, token);
+    let contract_address = env.current_contract_address();
+    if matches!(token_client.try_transfer_from(&contract_address, from, &contract_address, &amount), Ok(Ok(()))) {
+        return Ok(());
+    }
+    let allowance = token_client.try_allowance(from, &contract_address);
+    let short_allowance = !matches!(allowance, Ok(Ok(allowance)) if allowance >= amount);
+    Err(fail_transfer(env, token, from, &contract_address, amount, stage, Some(short_allowance)))
+}
+
+/// Works out why a transfer failed, publishes a `token/xfer_fail` event
+/// and returns TokenTransferFailed. The reason is `allowance`, `balance`,
+/// `frozen` (the token reports either side as deauthorized) or `rejected`.
+/// The failed call is rolled back, so the stage and reason are only
+/// available among its diagnostic events.
+fn fail_transfer(
+    env: &Env,
+    token: &Address,
+    from: &Address,
+    to: &Address,
+    amount: i128,
+    stage: TokenTransferStage,
+    short_allowance: Option<bool>,
+) -> ContractError {
+    let token_client = token::Client::new(env, token);
+    let deauthorized = |address: &Address| {
+        let args: Vec<Val> = Vec::from_array(env, [address.into_val(env)]);
+        matches!(
+            env.try_invoke_contract::<bool, soroban_sdk::Error>(token, &Symbol::new(env, "authorized"), args),
+            Ok(Ok(false))
+        )
+    };
+
+    let reason = if short_allowance == Some(true) {
+        symbol_short!("allowance")
+    } else if !matches!(token_client.try_balance(from), Ok(Ok(balance)) if balance >= amount) {
+        symbol_short!("balance")
+    } else if deauthorized(from) || deauthorized(to) {
+        symbol_short!("frozen")
+    } else {
+        symbol_short!("rejected")
+    };
+    emit_token_transfer_failed(env, stage, token.clone(), from.clone(), to.clone(), amount, reason);
+
+    ContractError::TokenTransferFailed
+}
+
+/// Pays a settlement payout to `agent`: credited to its float when it
+/// settles from float, transferred to its payout route when it has one, and
+/// transferred to the agent otherwise.
+fn pay_agent(env: &Env, token: &Address, agent: &Address, amount: i128) -> Result<(), ContractError> {
+    if settles_from_float(env, agent) {
+        let float = get_agent_float(env, agent)
+            .checked_add(amount)
+            .ok_or(ContractError::Overflow)?;
+        set_agent_float(env, agent, float);
+        emit_float_credited(env, agent.clone(), amount, float);
+    } else if let Some(route) = get_payout_route(env, agent) {
+        transfer_token(env, token, &env.current_contract_address(), &route.destination, amount, TokenTransferStage::Payout)?;
+        emit_payout_routed(env, agent.clone(), route.destination, route.memo, token.clone(), amount);
+    } else {
+        transfer_token(env, token, &env.current_contract_address(), agent, amount, TokenTransferStage::Payout)?;
+    }
+    Ok(())
+}
+
+/// Stored report for `epoch`, or an empty one for the initial epoch before
+/// anything was attributed to it.
+fn current_epoch_report(env: &Env, epoch: u64) -> EpochReport {
+    get_epoch_report(env, epoch).unwrap_or(EpochReport {
+        epoch,
+        started_at: 0,
+        closed_at: 0,
+        remittances: 0,
+        fees: 0,
+        protocol_fees: 0,
+        rebates: 0,
+    })
+}
+
+/// Attributes a remittance's fee split to the current epoch when its fee
+/// is booked, on settlement or escrow conversion.
+fn accrue_epoch_fees(env: &Env, fee: i128, platform_share: i128, priority_tip: i128, rebate: i128) -> Result<(), ContractError> {
+    let mut report = current_epoch_report(env, get_current_epoch(env));
+    report.remittances = report.remittances.checked_add(1).ok_or(ContractError::Overflow)?;
+    report.fees = report.fees.checked_add(fee).ok_or(ContractError::Overflow)?;
+    report.protocol_fees = report
+        .protocol_fees
+        .checked_add(platform_share)
+        .and_then(|fees| fees.checked_add(priority_tip))
+        .ok_or(ContractError::Overflow)?;
+    report.rebates = report.rebates.checked_add(rebate).ok_or(ContractError::Overflow)?;
+    set_epoch_report(env, &report);
+    Ok(())
+}
+
+/// Pause, submitter, settlement window and size checks for a netting batch.
+fn authorize_batch(env: &Env, context: &SettlementContext, submitter: &Address, remittance_ids: &Vec<u64>) -> Result<(), ContractError> {
+    if context.paused {
+        return Err(ContractError::ContractPaused);
+    }
+
+    if is_batch_permissionless(env) {
+        submitter.require_auth();
+    } else {
+        require_admin_or_role(env, submitter, &Role::BatchSettler)?;
+    }
+
+    if !in_settlement_window(env)
+        && *submitter != get_admin(env)?
+        && !has_role(env, &Role::WindowOverride, submitter)
+    {
+        return Err(ContractError::OutsideSettlementWindow);
+    }
+
+    if remittance_ids.is_empty() || remittance_ids.len() > MAX_BATCH_SIZE {
+        return Err(ContractError::InvalidBatch);
+    }
+    Ok(())
+}
+
+/// Loads and checks every entry of a netting batch and that the contract
+/// can cover it. A failure carries the failing remittance ID, or `None`
+/// when the batch as a whole cannot be covered.
+fn validate_batch(
+    env: &Env,
+    context: &SettlementContext,
+    remittance_ids: &Vec<u64>,
+) -> Result<Vec<Remittance>, (Option<u64>, ContractError)> {
+    let approval_required = is_batch_agent_approval_required(env);
+    let mut remittances: Vec<Remittance> = Vec::new(env);
+    let mut required: i128 = 0;
+
+    for remittance_id in remittance_ids.iter() {
+        let check = || -> Result<Remittance, ContractError> {
+            let remittance = get_remittance(env, remittance_id)?;
+            check_settleable(env, context, &remittance)?;
+            require_push_payout(&remittance)?;
+            validate_address(&remittance.agent)?;
+
+            if approval_required && !is_settlement_approved(env, remittance_id) {
+                return Err(ContractError::AgentApprovalMissing);
+            }
+            Ok(remittance)
+        };
+        let remittance = check().map_err(|error| (Some(remittance_id), error))?;
+
+        required = required
+            .checked_add(remittance.amount)
+            .ok_or((Some(remittance_id), ContractError::Overflow))?;
+        remittances.push_back(remittance);
+    }
+
+    check_liquidity(env, &context.usdc_token, required).map_err(|error| (None, error))?;
+    Ok(remittances)
+}
+
+/// Settles a validated netting batch, paying each agent once, and records
+/// it. Returns the batch ID.
+fn execute_batch(
+    env: &Env,
+    context: &SettlementContext,
+    submitter: Address,
+    remittance_ids: Vec<u64>,
+    remittances: Vec<Remittance>,
+) -> Result<u64, ContractError> {
+    let mut payouts: Map<Address, i128> = Map::new(env);
+
+    let (remittances, fee_discount) = apply_net_fees(env, remittances)?;
+    let fee_mode = match fee_discount > 0 {
+        true => NettingFeeMode::Net,
+        false => NettingFeeMode::Gross,
+    };
+
+    let mut counters = get_counters(env)?;
+    for remittance in remittances.iter() {
+        let remittance_id = remittance.id;
+        let payout_amount = net_amount(&remittance)?;
+        if !hold_if_instant(env, &remittance, &remittance.agent, payout_amount)? {
+            let agent_total = payouts
+                .get(remittance.agent.clone())
+                .unwrap_or(0)
+                .checked_add(payout_amount)
+                .ok_or(ContractError::Overflow)?;
+            payouts.set(remittance.agent.clone(), agent_total);
+        }
+
+        let external_ref = get_external_ref(env, remittance_id);
+        record_settlement(env, context, remittance, payout_amount, external_ref, &mut counters)?;
+    }
+    set_counters(env, &counters);
+
+    for (agent, amount) in payouts.iter() {
+        pay_agent(env, &context.usdc_token, &agent, amount)?;
+    }
+
+    let batch_id = get_batch_counter(env)
+        .checked_add(1)
+        .ok_or(ContractError::Overflow)?;
+    set_batch_counter(env, batch_id);
+
+    let batch = SettlementBatch {
+        id: batch_id,
+        submitter: submitter.clone(),
+        remittance_ids: remittance_ids.clone(),
+        settled_at: env.ledger().timestamp(),
+        fee_mode: fee_mode.clone(),
+        fee_discount,
+    };
+    set_batch(env, &batch);
+
+    emit_batch_settled(env, batch_id, submitter, remittance_ids, fee_mode, fee_discount);
+
+    Ok(batch_id)
+}
+
+/// Holds the payout of a remittance settling in an instant corridor until
+/// the corridor's chargeback window closes. Returns false, holding nothing,
+/// for other corridors.
+fn hold_if_instant(env: &Env, remittance: &Remittance, payee: &Address, payout_amount: i128) -> Result<bool, ContractError> {
+    let Some(window_seconds) = get_corridor_chargeback_window(env, &remittance.currency, &remittance.country) else {
+        return Ok(false);
+    };
+
+    let held = HeldPayout {
+        payee: payee.clone(),
+        amount: payout_amount,
+        release_at: env.ledger().timestamp().saturating_add(window_seconds),
+        disputed: false,
+    };
+    let total = get_held_payout_total(env)
+        .checked_add(payout_amount)
+        .ok_or(ContractError::Overflow)?;
+    set_held_payout_total(env, total);
+    set_held_payout(env, remittance.id, Some(&held));
+    emit_payout_held(env, remittance.id, held.payee, payout_amount, held.release_at);
+
+    Ok(true)
+}
+
+/// Compensates the sender of an insured remittance after `arbitrator`
+/// upheld a challenge to its settlement: the agent was paid but the
+/// recipient was not, so the sender lost the remittance amount. The claim
+/// is capped at that amount and at the fund balance. Does nothing for
+/// uninsured remittances or an empty fund.
+fn pay_insurance_claim(env: &Env, remittance: &Remittance, arbitrator: &Address) -> Result<(), ContractError> {
+    let mut fund = get_insurance_fund(env);
+    let amount = remittance.amount.min(fund.balance);
+    if remittance.insurance_premium <= 0 || amount <= 0 || get_insurance_claim(env, remittance.id).is_some() {
+        return Ok(());
+    }
+
+    fund.balance -= amount;
+    fund.claims_paid = fund.claims_paid.checked_add(amount).ok_or(ContractError::Overflow)?;
+    fund.claim_count = fund.claim_count.checked_add(1).ok_or(ContractError::Overflow)?;
+    set_insurance_fund(env, &fund);
+    set_insurance_claim(env, remittance.id, &InsuranceClaim {
+        amount,
+        arbitrator: arbitrator.clone(),
+        ruled_at: env.ledger().timestamp(),
+    });
+
+    let usdc_token = get_usdc_token(env)?;
+    transfer_token(env, &usdc_token, &env.current_contract_address(), &remittance.sender, amount, TokenTransferStage::Refund)?;
+
+    emit_insurance_claim_paid(env, remittance.id, remittance.sender.clone(), arbitrator.clone(), usdc_token, amount);
+
+    Ok(())
+}
+
+/// Removes a held payout ahead of paying it out to `held`'s recipient.
+fn release_held_payout(env: &Env, remittance_id: u64, held: &HeldPayout) -> Result<(), ContractError> {
+    let total = get_held_payout_total(env)
+        .checked_sub(held.amount)
+        .ok_or(ContractError::Overflow)?;
+    set_held_payout_total(env, total);
+    set_held_payout(env, remittance_id, None);
+    Ok(())
+}
+
+/// Pays a released held payout: through `pay_agent` when the payee is the
+/// remittance's agent, and as a plain transfer to a Pull mode recipient,
+/// whose payout is not subject to the agent's float or payout route.
+fn pay_held_payout(env: &Env, token: &Address, remittance: &Remittance, held: &HeldPayout) -> Result<(), ContractError> {
+    if held.payee == remittance.agent {
+        pay_agent(env, token, &held.payee, held.amount)
+    } else {
+        transfer_token(env, token, &env.current_contract_address(), &held.payee, held.amount, TokenTransferStage::Payout)
+    }
+}
+
+/// Whether the current ledger time falls in a configured settlement window.
+/// Always true when no windows are configured.
+fn in_settlement_window(env: &Env) -> bool {
+    let Some(windows) = get_settlement_windows(env) else {
+        return true;
+    };
+
+    let now = (env.ledger().timestamp() % SECONDS_PER_DAY) as u32;
+    windows.iter().any(|window| {
+        if window.start_seconds < window.end_seconds {
+            now >= window.start_seconds && now < window.end_seconds
+        } else {
+            now >= window.start_seconds || now < window.end_seconds
+        }
+    })
+}
+
+/// Fails with `InsufficientContractLiquidity` if the contract holds less
+/// than `required` of `token`, rather than letting the transfer trap.
+fn check_liquidity(env: &Env, token: &Address, required: i128) -> Result<(), ContractError> {
+    let balance = token::Client::new(env, token).balance(&env.current_contract_address());
+    if balance < required {
+        return Err(ContractError::InsufficientContractLiquidity);
+    }
+    Ok(())
+}
+
+/// Checks a fee or treasury change before it is applied or queued.
+fn validate_timelocked_update(update: &TimelockedUpdate) -> Result<(), ContractError> {
+    match update {
+        TimelockedUpdate::PlatformFee(fee_bps) if *fee_bps > 10000 => Err(ContractError::InvalidFeeBps),
+        TimelockedUpdate::Treasury(Some(treasury)) => validate_address(treasury),
+        TimelockedUpdate::TokenFee(_, Some(fee_bps)) if *fee_bps > 10000 => Err(ContractError::InvalidFeeBps),
+        TimelockedUpdate::TokenTreasury(_, Some(treasury)) => validate_address(treasury),
+        TimelockedUpdate::Delay(Some(0)) => Err(ContractError::InvalidAmount),
+        TimelockedUpdate::FeeStrategy(strategy) if strategy.max_fee_bps > 10000 => Err(ContractError::InvalidFeeBps),
+        TimelockedUpdate::AgentRebate(bps)
+        | TimelockedUpdate::AgentSurcharge(_, bps)
+        | TimelockedUpdate::InsurancePremium(bps)
+        | TimelockedUpdate::SlaPenalty(bps)
+            if *bps > 10000 =>
+        {
+            Err(ContractError::InvalidFeeBps)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn apply_platform_fee(env: &Env, admin: Address, fee_bps: u32) -> Result<(), ContractError> {
+    let old_fee = get_platform_fee_bps(env)?;
+    set_platform_fee_bps(env, fee_bps);
+    archive_fee_schedule(env)?;
+    record_config_change(env, "platform_fee_bps", admin.clone(), old_fee, fee_bps);
+    emit_fee_updated(env, admin, old_fee, fee_bps);
+
+    log_update_fee(env, fee_bps);
+
+    Ok(())
+}
+
+fn apply_treasury(env: &Env, admin: Address, treasury: Option<Address>) {
+    let previous = get_treasury(env);
+    set_treasury(env, treasury.as_ref());
+    record_config_change(env, "treasury", admin.clone(), previous, treasury.clone());
+    emit_treasury_updated(env, admin, None, treasury);
+}
+
+fn apply_token_fee_bps(env: &Env, admin: Address, token: Address, fee_bps: Option<u32>) {
+    let previous = (token.clone(), get_token_fee_bps(env, &token));
+    set_token_fee_bps(env, &token, fee_bps);
+    record_config_change(env, "token_fee_bps", admin.clone(), previous, (token.clone(), fee_bps));
+    emit_token_fee_updated(env, admin, token, fee_bps);
+}
+
+fn apply_token_treasury(env: &Env, admin: Address, token: Address, treasury: Option<Address>) {
+    let previous = (token.clone(), get_token_treasury(env, &token));
+    set_token_treasury(env, &token, treasury.as_ref());
+    record_config_change(env, "token_treasury", admin.clone(), previous, (token.clone(), treasury.clone()));
+    emit_treasury_updated(env, admin, Some(token), treasury);
+}
+
+fn apply_timelock_delay(env: &Env, admin: Address, delay_seconds: Option<u64>) {
+    let previous = get_timelock_delay(env);
+    set_timelock_delay(env, delay_seconds);
+    record_config_change(env, "timelock_delay", admin.clone(), previous, delay_seconds);
+    emit_timelock_delay_updated(env, admin, delay_seconds);
+}
+
+fn apply_fee_strategy(env: &Env, admin: Address, strategy: Option<FeeStrategy>) {
+    let previous = get_fee_strategy(env);
+    set_fee_strategy(env, strategy.clone());
+    record_config_change(env, "fee_strategy", admin.clone(), previous, strategy.clone());
+    emit_fee_strategy_updated(env, admin, strategy);
+}
+
+fn apply_agent_rebate(env: &Env, admin: Address, rebate_bps: u32) -> Result<(), ContractError> {
+    let old_rebate_bps = get_agent_rebate_bps(env);
+    set_agent_rebate_bps(env, rebate_bps);
+    archive_fee_schedule(env)?;
+    record_config_change(env, "agent_rebate_bps", admin.clone(), old_rebate_bps, rebate_bps);
+    emit_agent_rebate_updated(env, admin, old_rebate_bps, rebate_bps);
+    Ok(())
+}
+
+/// Fails with `AgentNotRegistered` when `agent` is not registered, which a
+/// queued surcharge is checked against again when executed.
+fn apply_agent_surcharge(env: &Env, admin: Address, agent: Address, surcharge_bps: u32) -> Result<(), ContractError> {
+    if !is_agent_registered(env, &agent) {
+        return Err(ContractError::AgentNotRegistered);
+    }
+
+    let old_surcharge_bps = get_agent_surcharge_bps(env, &agent);
+    set_agent_surcharge_bps(env, &agent, surcharge_bps);
+    record_config_change(env, "agent_surcharge_bps", admin.clone(), (agent.clone(), old_surcharge_bps), (agent.clone(), surcharge_bps));
+    emit_agent_surcharge_updated(env, admin, agent, old_surcharge_bps, surcharge_bps);
+    Ok(())
+}
+
+fn apply_insurance_premium(env: &Env, admin: Address, premium_bps: u32) {
+    let old_premium_bps = get_insurance_premium_bps(env);
+    set_insurance_premium_bps(env, premium_bps);
+    record_config_change(env, "insurance_premium_bps", admin.clone(), old_premium_bps, premium_bps);
+    emit_insurance_premium_updated(env, admin, old_premium_bps, premium_bps);
+}
+
+fn apply_sla_penalty(env: &Env, admin: Address, penalty_bps: u32) -> Result<(), ContractError> {
+    let old_penalty_bps = get_sla_penalty_bps(env);
+    set_sla_penalty_bps(env, penalty_bps);
+    archive_fee_schedule(env)?;
+    record_config_change(env, "sla_penalty_bps", admin.clone(), old_penalty_bps, penalty_bps);
+    emit_sla_penalty_updated(env, admin, old_penalty_bps, penalty_bps);
+    Ok(())
+}
+
+/// Sends all accumulated platform fees in the settlement token to `to`,
+/// keeping the reserve intact. Caller auth is checked by callers.
+fn send_accumulated_fees(env: &Env, caller: Address, to: Address) -> Result<(), ContractError> {
+    let mut counters = get_counters(env)?;
+    let fees = counters.accumulated_fees;
+
+    if fees <= 0 {
+        return Err(ContractError::NoFeesToWithdraw);
+    }
+
+    let usdc_token = get_usdc_token(env)?;
+    check_reserve(env, &usdc_token, &counters, fees)?;
+
+    transfer_token(env, &usdc_token, &env.current_contract_address(), &to, fees, TokenTransferStage::Withdrawal)?;
+
+    counters.accumulated_fees = 0;
+    set_counters(env, &counters);
+
+    emit_fees_withdrawn(env, caller, to.clone(), usdc_token, fees);
+
+    log_withdraw_fees(env, &to, fees);
+
+    Ok(())
+}
+
+/// Fee destination for `token`: its own treasury, else the default one.
+fn resolve_treasury(env: &Env, token: &Address) -> Option<Address> {
+    get_token_treasury(env, token).or_else(|| get_treasury(env))
+}
+
+/// Checks that moving `outflow` out of the contract leaves its balance
+/// covering the total locked value plus the configured reserve on it and
+/// every other balance the contract owes, see `owed_balances`.
+fn check_reserve(env: &Env, token: &Address, counters: &Counters, outflow: i128) -> Result<(), ContractError> {
+    let reserve = calculate_fee(counters.total_locked, get_reserve_bps(env))?;
+    let owed = owed_balances(env)?;
+    let required = counters
+        .total_locked
+        .checked_add(reserve)
+        .and_then(|required| required.checked_add(owed))
+        .and_then(|required| required.checked_add(outflow))
+        .ok_or(ContractError::Overflow)?;
+    check_liquidity(env, token, required)
+}
+
+/// Balances held for someone other than the platform beyond the locked
+/// total: the insurance fund, challenge bonds awaiting a ruling, payouts held
+/// in chargeback windows, agent floats, unclaimed rebate, surcharge and
+/// referral balances and priority tips on Pending remittances.
+fn owed_balances(env: &Env) -> Result<i128, ContractError> {
+    get_insurance_fund(env)
+        .balance
+        .checked_add(get_held_challenge_bonds(env))
+        .and_then(|owed| owed.checked_add(get_held_payout_total(env)))
+        .and_then(|owed| owed.checked_add(get_agent_float_total(env)))
+        .and_then(|owed| owed.checked_add(get_claimable_balance_total(env)))
+        .and_then(|owed| owed.checked_add(get_pending_tip_total(env)))
+        .ok_or(ContractError::Overflow)
+}
+
+/// Checks that `remittance` can be settled now. Pause state and agent auth
+/// are checked by callers.
+fn check_settleable(env: &Env, context: &SettlementContext, remittance: &Remittance) -> Result<(), ContractError> {
+    if remittance.status != RemittanceStatus::Pending {
+        return Err(ContractError::InvalidStatus);
+    }
+
+    // Check for duplicate settlement execution
+    if has_settlement_hash(env, remittance.id) {
+        return Err(ContractError::DuplicateSettlement);
+    }
+
+    // Check if settlement has expired
+    if let Some(expiry_time) = remittance.expiry {
+        let current_time = env.ledger().timestamp();
+        if current_time > expiry_time {
+            return Err(ContractError::SettlementExpired);
+        }
+    }
+
+    if context.authorization_required && get_remittance_authorizer(env, remittance.id).is_none() {
+        return Err(ContractError::RemittanceNotAuthorized);
+    }
+
+    // Instant corridors protect the sender with a chargeback window instead
+    let instant = get_corridor_chargeback_window(env, &remittance.currency, &remittance.country).is_some();
+    if remittance.flags & FLAG_DUAL_CONFIRM != 0 && !remittance.sender_confirmed && !instant {
+        let timeout_at = remittance
+            .created_at
+            .saturating_add(context.dual_confirm_timeout);
+        if env.ledger().timestamp() < timeout_at {
+            return Err(ContractError::SenderConfirmationRequired);
+        }
+    }
+
+    if let Some(parent_id) = get_remittance_parent(env, remittance.id) {
+        if get_remittance(env, parent_id)?.status != RemittanceStatus::Completed {
+            return Err(ContractError::InvalidStatus);
+        }
+    }
+
+    Ok(())
+}
+
+/// Settlement proof for a remittance settling in the current ledger. The
+/// hash binds the contract, the settled remittance and the ledger.
+fn build_settlement_proof(env: &Env, remittance: &Remittance) -> SettlementProof {
+    let contract = env.current_contract_address();
+    let fx_lock = get_fx_lock(env, remittance.id);
+    let ledger_sequence = env.ledger().sequence();
+    let preimage = (
+        contract.clone(),
+        remittance.id,
+        remittance.sender.clone(),
+        remittance.agent.clone(),
+        remittance.amount,
+        remittance.fee,
+        ledger_sequence,
+    );
+
+    SettlementProof {
+        hash: env.crypto().sha256(&preimage.to_xdr(env)).into(),
+        contract,
+        remittance_id: remittance.id,
+        sender: remittance.sender.clone(),
+        agent: remittance.agent.clone(),
+        amount: remittance.amount,
+        fee: remittance.fee,
+        ledger_sequence,
+        settled_at: env.ledger().timestamp(),
+        fx_destination_amount: remittance.fiat_amount.unwrap_or(0),
+        fx_rate: fx_lock.map_or(0, |fx_lock| fx_lock.rate),
+    }
+}
+
+fn effective_exposure_cap(env: &Env, sender: &Address) -> Option<i128> {
+    get_sender_exposure_cap(env, sender).or_else(|| get_global_exposure_cap(env))
+}
+
+/// Escrowed value not yet released to the agent: the milestone total less
+/// what has already been released.
+fn escrow_remaining(escrow: &Escrow) -> Result<i128, ContractError> {
+    let mut total: i128 = 0;
+    for milestone in escrow.milestones.iter() {
+        total = total.checked_add(milestone).ok_or(ContractError::Overflow)?;
+    }
+    total
+        .checked_sub(escrow.released_amount)
+        .ok_or(ContractError::Overflow)
+}
+
+/// Loads the escrows at index positions `start..start + limit` of a
+/// `count`-long index, looking each ID up with `id_at`.
+fn load_escrow_page(env: &Env, count: u32, start: u32, limit: u32, id_at: impl Fn(u32) -> Option<u64>) -> Vec<Escrow> {
+    let mut page = Vec::new(env);
+    let end = start.saturating_add(limit).min(count);
+
+    for index in start..end {
+        if let Some(Ok(escrow)) = id_at(index).map(|escrow_id| get_escrow(env, escrow_id)) {
+            page.push_back(escrow);
+        }
+    }
+
+    page
 }
@@ -1,14 +1,20 @@
-use soroban_sdk::{contracttype, Address, Env};
+use soroban_sdk::{contracttype, Address, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec};
 
-use crate::{ContractError, Remittance};
+use crate::{
+    AgentReputation, AgentWorkload, BatchAbort, ChallengeConfig, ContractError, ContractMetadata, CorridorStats, Counters, DailySpend, EpochReport, Escrow, EventVerbosity, ExpiryBounds, Feature, FeeSchedule, FeeStrategy, FxLock, HeldPayout, InsuranceClaim, InsuranceFund, NettingFeeMode, NetworkTally, PauseInfo, PayoutRoute, QueuedUpdate, Quote, RateLimitConfig, RateLimitWindow, Referral, ReferralConfig,
+    RecoveryVault, Remittance, ReviewRecord, Role, SelfCap, SessionKey, SettlementBatch, SettlementChallenge, SettlementProof, SettlementWindow, TokenInfo,
+    DEFAULT_DUAL_CONFIRM_TIMEOUT_SECONDS, MAX_CONFIG_SNAPSHOTS, MAX_OPEN_HEAD_ADVANCE, QUOTE_VALIDITY_SECONDS,
+};
 
 /// Storage keys for the SwiftRemit contract.
 ///
 /// Storage Layout:
 /// - Instance storage: Contract-level configuration and state (Admin, UsdcToken, PlatformFeeBps,
-///   RemittanceCounter, AccumulatedFees)
+///   Counters)
 /// - Persistent storage: Per-entity data that needs long-term retention (Remittance records,
 ///   AgentRegistered status)
+/// - Temporary storage: Short-lived entries that may be dropped once stale (Quote records,
+///   per-sender rate-limit counters)
 #[contracttype]
 #[derive(Clone)]
 enum DataKey {
@@ -23,32 +29,409 @@ enum DataKey {
     /// Platform fee in basis points (1 bps = 0.01%)
     PlatformFeeBps,
 
+    /// External fee strategy contract and its fee cap, if configured
+    FeeStrategy,
+
+    /// Fee override in basis points for remittances in a given token (persistent storage)
+    TokenFeeBps(Address),
+
+    /// Default destination for forwarded platform fees
+    Treasury,
+
+    /// Seconds fee and treasury changes must wait in the queue, if timelocked
+    TimelockDelay,
+
+    /// Number of updates queued behind the timelock so far
+    QueuedUpdateCounter,
+
+    /// Fee or treasury change waiting out the timelock (persistent storage)
+    QueuedUpdate(u64),
+
+    /// Fee destination overriding the default treasury for a token (persistent storage)
+    TokenTreasury(Address),
+
+    /// Cached metadata of a whitelisted token; present only while whitelisted (persistent storage)
+    TokenInfo(Address),
+
+    /// Deployment name, environment and operator for this instance
+    ContractMetadata,
+
+    /// Address that can claim the admin role after prolonged admin inactivity
+    AdminRecovery,
+
+    /// Time of the admin's most recent authorized action
+    LastAdminActivity,
+
     // === Remittance Management ===
     // Keys for tracking and storing remittance transactions
-    /// Global counter for generating unique remittance IDs
-    RemittanceCounter,
+    /// Hot counters packed into one entry: remittance ID counter, accumulated
+    /// fees, total locked and lifetime settlement/cancellation totals
+    Counters,
 
     /// Individual remittance record indexed by ID (persistent storage)
     Remittance(u64),
 
+    /// Present while a remittance is Pending or PendingReview (persistent storage)
+    OpenRemittance(u64),
+
+    /// Lowest remittance ID that may still be open
+    OpenRemittanceHead,
+
+    /// Integrator-supplied off-chain reference indexed by remittance ID (persistent storage)
+    ExternalRef(u64),
+
+    /// Remittance IDs a sender created with a given external reference (persistent storage)
+    ExternalRefIndex(Address, BytesN<32>),
+
+    /// Expiry window applied when a remittance is created without one
+    DefaultExpirySeconds,
+
+    /// How long before expiry a Pending remittance triggers an expiring-soon warning
+    ExpiryWarningSeconds,
+
+    /// Set once a remittance's expiring-soon warning has been emitted (temporary storage)
+    ExpiryWarned(u64),
+
+    /// Amount above which new remittances are held for compliance review
+    ReviewThreshold,
+
+    /// Compliance review decision for a held remittance (persistent storage)
+    Review(u64),
+
+    /// Exchange rate locked for a remittance's fiat payout (persistent storage)
+    FxLock(u64),
+
+    /// Parent of a linked remittance (persistent storage)
+    RemittanceParent(u64),
+
+    /// Linked remittances created under a parent, in creation order (persistent storage)
+    RemittanceChildren(u64),
+
+    /// Watcher tags published as topics on a remittance's status changes (persistent storage)
+    WatchTags(u64),
+
+    /// Milestone escrow a remittance was converted into (persistent storage)
+    Escrow(u64),
+
+    /// Hash of the evidence filed with an escrow dispute (persistent storage)
+    EscrowDisputeEvidence(u64),
+
+    /// Number of escrows created so far
+    EscrowCount,
+
+    /// Number of escrows funded by a sender (persistent storage)
+    SenderEscrowCount(Address),
+
+    /// ID of a sender's n-th escrow, counting from 0 in creation order (persistent storage)
+    SenderEscrow(Address, u32),
+
+    /// Number of remittances created by a sender (persistent storage)
+    SenderRemittanceCount(Address),
+
+    /// ID of a sender's n-th remittance, counting from 0 in creation order (persistent storage)
+    SenderRemittance(Address, u32),
+
+    /// Number of escrows paying out to an agent (persistent storage)
+    RecipientEscrowCount(Address),
+
+    /// ID of an agent's n-th escrow, counting from 0 in creation order (persistent storage)
+    RecipientEscrow(Address, u32),
+
+    /// Total value of a sender's Pending remittances (persistent storage)
+    SenderExposure(Address),
+
+    /// Contract-wide ceiling on the total value held for Pending remittances
+    MaxTotalLocked,
+
+    /// Buffer the contract must hold beyond total locked value, in basis points of it
+    ReserveBps,
+
+    // === Insurance ===
+    // Keys for optional remittance insurance
+    /// Premium charged on insured remittances, in basis points of the amount
+    InsurancePremiumBps,
+
+    /// Premiums collected, claims paid and the fund balance
+    InsuranceFund,
+
+    /// Compensation paid on an insured remittance (persistent storage)
+    InsuranceClaim(u64),
+
+    /// Bitmask of enabled contract-wide features, indexed by Feature
+    Features,
+
+    // === Corridor Registry ===
+    // Keys for the admin-managed list of allowed ISO codes
+
+    /// Registered ISO 4217 currency codes
+    AllowedCurrencies,
+
+    /// Registered ISO 3166-1 alpha-2 country codes
+    AllowedCountries,
+
+    // === Rate Limiting ===
+    // Keys for throttling how often a sender creates remittances
+    /// Active rate-limit configuration
+    RateLimitConfig,
+
+    /// Time of a sender's most recent remittance (temporary storage)
+    LastSettlementTime(Address),
+
+    /// A sender's usage of the current rate-limit window (temporary storage)
+    RateLimitWindow(Address),
+
+    // === Exposure Caps ===
+    // Keys for limiting outstanding Pending value per sender
+
+    /// Cap applied to senders without an individual override
+    GlobalExposureCap,
+
+    /// Per-sender cap overriding the global cap (persistent storage)
+    SenderExposureCap(Address),
+
+    // === Sender Self-Limits ===
+    // Keys for caps senders place on their own remittances
+    /// Sender-set cap on value sent per UTC day (persistent storage)
+    SelfDailyCap(Address),
+
+    /// Sender-set cap on a single remittance's amount (persistent storage)
+    SelfMaxTx(Address),
+
+    /// Value a sender has sent today (temporary storage)
+    DailySpend(Address),
+
+    /// Rolling per-agent receive limit for a corridor (persistent storage)
+    CorridorReceiveLimit(String, String),
+
+    /// Daily payouts an agent received in a corridor over the receive window (temporary storage)
+    AgentReceived(Address, String, String),
+
     // === Agent Management ===
     // Keys for tracking registered agents
     /// Agent registration status indexed by agent address (persistent storage)
     AgentRegistered(Address),
 
-    // === Fee Tracking ===
-    // Keys for managing platform fees
-    /// Total accumulated platform fees awaiting withdrawal
-    AccumulatedFees,
-    
+    /// Reason code recorded when an agent was removed (persistent storage)
+    AgentRemovalReason(Address),
+
+    /// Pending remittance totals assigned to an agent (persistent storage)
+    AgentWorkload(Address),
+
+    /// Completed and declined payout counts for an agent (persistent storage)
+    AgentReputation(Address),
+
+    /// Pending value flowing from a sender to an agent (persistent storage)
+    PairPending(Address, Address),
+
+    /// Share of the platform fee rebated to the settling agent, in basis points
+    AgentRebateBps,
+
+    /// Rebates and surcharges accrued to an agent and not yet claimed (persistent storage)
+    AgentRebateBalance(Address),
+
+    /// Share of referred senders' fees paid to referrers, and for how long
+    ReferralConfig,
+
+    /// Referrer of a sender, set once (persistent storage)
+    Referral(Address),
+
+    /// Referral rewards accrued to a referrer and not yet claimed (persistent storage)
+    ReferralBalance(Address),
+
+    /// Surcharge added to the fee of remittances paid out by an agent, in basis points (persistent storage)
+    AgentSurchargeBps(Address),
+
+    /// Agent-owned tokens held by the contract as float (persistent storage)
+    AgentFloat(Address),
+
+    /// Whether an agent's payouts are credited to its float (persistent storage)
+    AgentSettlesFromFloat(Address),
+
+    /// Destination and memo an agent's transferred payouts are routed to (persistent storage)
+    PayoutRoute(Address),
+
+    // === Pause State ===
+    // Keys for emergency halts
     /// Contract pause status for emergency halts
     Paused,
+
+    /// Details of the current pause, including when it lifts on its own
+    PauseInfo,
+
+    /// Longest a single pause may last before it expires, in seconds
+    MaxPauseDuration,
+
+    // === Roles & Pre-Authorization ===
+    // Keys for delegated roles and the optional compliance approval step
+    /// Whether an address holds a role (persistent storage)
+    RoleMember(Role, Address),
+
+    /// Addresses holding a role, in grant order (persistent storage)
+    RoleMembers(Role),
+
+
+
+
+    /// Authorizer that approved a remittance for payout (persistent storage)
+    RemittanceAuthorizer(u64),
+
+    /// Seconds after creation when agent-only confirmation suffices for
+    /// dual-confirmation remittances
+    DualConfirmTimeout,
+
+    // === Emergency Recovery ===
+    // Keys for guardian-approved evacuation of contract funds
+    /// Guardian address that co-signs emergency operations
+    Guardian,
+
+    /// Timelocked destination for emergency evacuations
+    RecoveryVault,
+
+    /// Set once funds are evacuated; cleared by the post-mortem resume
+    Evacuated,
     
 
+    // === Corridors ===
+    // Keys for per-corridor accounting
+    /// Running totals indexed by (currency, country) (persistent storage)
+    CorridorStats(String, String),
+
+    /// Network-wide totals behind get_network_metrics
+    NetworkTally,
+
+    /// Present when a corridor is closed to new remittances (persistent storage)
+    CorridorDisabled(String, String),
+
+    /// Max seconds from creation to settlement for a corridor (persistent storage)
+    CorridorSla(String, String),
+
+    /// Allowed range of remittance expiry for a corridor (persistent storage)
+    CorridorExpiryBounds(String, String),
+
+    /// Chargeback window of an instant corridor, in seconds (persistent storage)
+    CorridorChargebackWindow(String, String),
+
+    /// Fee mode for a corridor's remittances in netting batches; absent means Gross (persistent storage)
+    CorridorFeeMode(String, String),
+
+    /// Share of the platform fee paid to the sender on an SLA breach, in basis points of the fee
+    SlaPenaltyBps,
+
+    // === Tags ===
+    // Keys for cohort analytics
+    /// Lifetime number of remittances created with a tag (persistent storage)
+    TagCount(Symbol),
+
+    // === Batch Settlement ===
+    // Keys for netting batch settlement
+
+    /// Daily UTC windows in which netting batches may execute
+    SettlementWindows,
+
+    /// Contract notified of a sender's settlements and refunds (persistent storage)
+    NotificationContract(Address),
+
+    /// Count of sender-signed operations, for client-side idempotency (persistent storage)
+    Nonce(Address),
+
+
+
+    /// Agent pre-approval of a remittance for batch settlement (persistent storage)
+    SettlementApproval(u64),
+
+    /// ed25519 public key an agent signs offline payout approvals with (persistent storage)
+    AgentSigningKey(Address),
+
+    /// Offline payout approval nonce already used by an agent (temporary storage)
+    PayoutApprovalNonce(Address, u64),
+
+    /// Session key an agent authorized for settlement (persistent storage)
+    AgentSessionKey(Address, BytesN<32>),
+
+    /// Global counter for generating unique batch IDs
+    BatchCounter,
+
+    /// Settlement batch record indexed by ID (persistent storage)
+    Batch(u64),
+
+    /// Submitter's most recent aborted batch attempt (temporary storage)
+    BatchAbort(Address),
+
+    // === Quotes ===
+    // Keys for fee quotes locked ahead of remittance creation
+    /// Global counter for generating unique quote IDs
+    QuoteCounter,
+
+    /// Individual quote indexed by ID (temporary storage)
+    Quote(u64),
+
     // === Settlement Deduplication ===
     // Keys for preventing duplicate settlement execution
     /// Settlement hash for duplicate detection (persistent storage)
     SettlementHash(u64),
+
+    /// Sequence number of the last event emitted by the contract
+    EventSequence,
+
+    /// Highest event level published; defaults to Verbose
+    EventVerbosity,
+
+    /// Remittance ID indexed by its settlement hash (persistent storage)
+    SettlementHashIndex(BytesN<32>),
+
+    /// Hash preimage and ledger details of a settlement (persistent storage)
+    SettlementProof(u64),
+
+    // === Settlement Challenges ===
+    // Keys for challenging settlements before they become final
+    /// Challenge period and bond; settlements are final on settlement while unset
+    ChallengeConfig,
+
+    /// Challenge posted against a settlement (persistent storage)
+    SettlementChallenge(u64),
+
+    /// Total challenge bonds held pending a ruling
+    HeldChallengeBonds,
+
+    /// Accounting epoch settlements are currently attributed to
+    CurrentEpoch,
+
+    /// Fee accounting of an epoch (persistent storage)
+    EpochReport(u64),
+
+    /// Payout held for an instant corridor's chargeback window (persistent storage)
+    HeldPayout(u64),
+
+    /// Total payouts held in chargeback windows
+    HeldPayoutTotal,
+
+    /// Total of all agent floats
+    AgentFloatTotal,
+
+    /// Total unclaimed agent rebate, surcharge and referral balances
+    ClaimableBalanceTotal,
+
+    /// Total priority tips held with Pending remittances
+    PendingTipTotal,
+
+    // === Fee Schedule Archive ===
+    // Keys for the append-only history of contract-wide fee rates
+    /// Number of fee schedule versions recorded
+    FeeScheduleCount,
+
+    /// Fee schedule version indexed by number (persistent storage)
+    FeeSchedule(u32),
+
+    // === Config Snapshots ===
+    // Keys for capturing and rolling back instance configuration
+    /// Global counter for generating unique snapshot IDs
+    ConfigSnapshotCounter,
+
+    /// IDs of retained snapshots, oldest first
+    ConfigSnapshotIds,
+
+    /// Configuration values captured by a snapshot (persistent storage)
+    ConfigSnapshot(u64),
 }
 
 pub fn has_admin(env: &Env) -> bool {
@@ -90,19 +473,121 @@ pub fn get_platform_fee_bps(env: &Env) -> Result<u32, ContractError> {
         .ok_or(ContractError::NotInitialized)
 }
 
-pub fn set_remittance_counter(env: &Env, counter: u64) {
+pub fn get_fee_strategy(env: &Env) -> Option<FeeStrategy> {
+    env.storage().instance().get(&DataKey::FeeStrategy)
+}
+
+pub fn set_fee_strategy(env: &Env, strategy: Option<FeeStrategy>) {
+    match strategy {
+        Some(strategy) => env.storage().instance().set(&DataKey::FeeStrategy, &strategy),
+        None => env.storage().instance().remove(&DataKey::FeeStrategy),
+    }
+}
+
+pub fn get_timelock_delay(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&DataKey::TimelockDelay)
+}
+
+pub fn set_timelock_delay(env: &Env, delay_seconds: Option<u64>) {
+    match delay_seconds {
+        Some(delay_seconds) => env.storage().instance().set(&DataKey::TimelockDelay, &delay_seconds),
+        None => env.storage().instance().remove(&DataKey::TimelockDelay),
+    }
+}
+
+pub fn get_queued_update_counter(env: &Env) -> u64 {
     env.storage()
         .instance()
-        .set(&DataKey::RemittanceCounter, &counter);
+        .get(&DataKey::QueuedUpdateCounter)
+        .unwrap_or(0)
 }
 
-pub fn get_remittance_counter(env: &Env) -> Result<u64, ContractError> {
+pub fn set_queued_update_counter(env: &Env, counter: u64) {
+    env.storage().instance().set(&DataKey::QueuedUpdateCounter, &counter);
+}
+
+pub fn get_queued_update(env: &Env, id: u64) -> Option<QueuedUpdate> {
+    env.storage().persistent().get(&DataKey::QueuedUpdate(id))
+}
+
+pub fn set_queued_update(env: &Env, id: u64, queued: Option<&QueuedUpdate>) {
+    let key = DataKey::QueuedUpdate(id);
+    match queued {
+        Some(queued) => env.storage().persistent().set(&key, queued),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_treasury(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Treasury)
+}
+
+pub fn set_treasury(env: &Env, treasury: Option<&Address>) {
+    match treasury {
+        Some(treasury) => env.storage().instance().set(&DataKey::Treasury, treasury),
+        None => env.storage().instance().remove(&DataKey::Treasury),
+    }
+}
+
+pub fn get_token_treasury(env: &Env, token: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TokenTreasury(token.clone()))
+}
+
+pub fn set_token_treasury(env: &Env, token: &Address, treasury: Option<&Address>) {
+    let key = DataKey::TokenTreasury(token.clone());
+    match treasury {
+        Some(treasury) => env.storage().persistent().set(&key, treasury),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_token_fee_bps(env: &Env, token: &Address) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TokenFeeBps(token.clone()))
+}
+
+pub fn set_token_fee_bps(env: &Env, token: &Address, fee_bps: Option<u32>) {
+    let key = DataKey::TokenFeeBps(token.clone());
+    match fee_bps {
+        Some(fee_bps) => env.storage().persistent().set(&key, &fee_bps),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_counters(env: &Env) -> Result<Counters, ContractError> {
     env.storage()
         .instance()
-        .get(&DataKey::RemittanceCounter)
+        .get(&DataKey::Counters)
         .ok_or(ContractError::NotInitialized)
 }
 
+pub fn set_counters(env: &Env, counters: &Counters) {
+    env.storage().instance().set(&DataKey::Counters, counters);
+}
+
+pub fn get_remittance_counter(env: &Env) -> Result<u64, ContractError> {
+    Ok(get_counters(env)?.remittance_counter)
+}
+
+pub fn get_accumulated_fees(env: &Env) -> Result<i128, ContractError> {
+    Ok(get_counters(env)?.accumulated_fees)
+}
+
+pub fn get_total_locked(env: &Env) -> i128 {
+    get_counters(env).unwrap_or_default().total_locked
+}
+
+pub fn get_total_settlements(env: &Env) -> u64 {
+    get_counters(env).unwrap_or_default().total_settlements
+}
+
+pub fn get_total_cancellations(env: &Env) -> u64 {
+    get_counters(env).unwrap_or_default().total_cancellations
+}
+
 pub fn set_remittance(env: &Env, id: u64, remittance: &Remittance) {
     env.storage()
         .persistent()
@@ -116,51 +601,1742 @@ pub fn get_remittance(env: &Env, id: u64) -> Result<Remittance, ContractError> {
         .ok_or(ContractError::RemittanceNotFound)
 }
 
-pub fn set_agent_registered(env: &Env, agent: &Address, registered: bool) {
+/// Attaches `external_ref` to a remittance and indexes it under its
+/// sender, so only that sender's own remittances can grow the entry.
+pub fn set_external_ref(env: &Env, id: u64, sender: &Address, external_ref: &BytesN<32>) {
     env.storage()
         .persistent()
-        .set(&DataKey::AgentRegistered(agent.clone()), &registered);
+        .set(&DataKey::ExternalRef(id), external_ref);
+
+    let mut ids = get_remittances_by_external_ref(env, sender, external_ref);
+    ids.push_back(id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::ExternalRefIndex(sender.clone(), external_ref.clone()), &ids);
 }
 
-pub fn is_agent_registered(env: &Env, agent: &Address) -> bool {
+pub fn get_remittances_by_external_ref(env: &Env, sender: &Address, external_ref: &BytesN<32>) -> Vec<u64> {
     env.storage()
         .persistent()
-        .get(&DataKey::AgentRegistered(agent.clone()))
-        .unwrap_or(false)
+        .get(&DataKey::ExternalRefIndex(sender.clone(), external_ref.clone()))
+        .unwrap_or(Vec::new(env))
 }
 
-pub fn set_accumulated_fees(env: &Env, fees: i128) {
+pub fn get_external_ref(env: &Env, id: u64) -> Option<BytesN<32>> {
+    env.storage().persistent().get(&DataKey::ExternalRef(id))
+}
+
+pub fn get_sender_exposure(env: &Env, sender: &Address) -> i128 {
     env.storage()
-        .instance()
-        .set(&DataKey::AccumulatedFees, &fees);
+        .persistent()
+        .get(&DataKey::SenderExposure(sender.clone()))
+        .unwrap_or(0)
 }
 
-pub fn get_accumulated_fees(env: &Env) -> Result<i128, ContractError> {
+pub fn set_sender_exposure(env: &Env, sender: &Address, exposure: i128) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SenderExposure(sender.clone()), &exposure);
+}
+
+pub fn get_review_threshold(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&DataKey::ReviewThreshold)
+}
+
+pub fn set_review_threshold(env: &Env, threshold: Option<i128>) {
+    match threshold {
+        Some(threshold) => env.storage().instance().set(&DataKey::ReviewThreshold, &threshold),
+        None => env.storage().instance().remove(&DataKey::ReviewThreshold),
+    }
+}
+
+pub fn get_fx_lock(env: &Env, remittance_id: u64) -> Option<FxLock> {
+    env.storage().persistent().get(&DataKey::FxLock(remittance_id))
+}
+
+pub fn set_fx_lock(env: &Env, remittance_id: u64, fx_lock: &FxLock) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::FxLock(remittance_id), fx_lock);
+}
+
+pub fn get_remittance_parent(env: &Env, remittance_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RemittanceParent(remittance_id))
+}
+
+pub fn get_remittance_children(env: &Env, remittance_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RemittanceChildren(remittance_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Records `child_id` as linked under `parent_id`.
+pub fn link_remittance(env: &Env, parent_id: u64, child_id: u64) {
+    let mut children = get_remittance_children(env, parent_id);
+    children.push_back(child_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::RemittanceChildren(parent_id), &children);
+    env.storage()
+        .persistent()
+        .set(&DataKey::RemittanceParent(child_id), &parent_id);
+}
+
+/// Lowest remittance ID that may still be open. IDs and creation times
+/// both increase, so scanning up from here visits open remittances oldest
+/// first.
+pub fn get_open_remittance_head(env: &Env) -> u64 {
     env.storage()
         .instance()
-        .get(&DataKey::AccumulatedFees)
-        .ok_or(ContractError::NotInitialized)
+        .get(&DataKey::OpenRemittanceHead)
+        .unwrap_or(1)
 }
 
-pub fn has_settlement_hash(env: &Env, remittance_id: u64) -> bool {
+pub fn is_open_remittance(env: &Env, remittance_id: u64) -> bool {
     env.storage()
         .persistent()
-        .has(&DataKey::SettlementHash(remittance_id))
+        .has(&DataKey::OpenRemittance(remittance_id))
 }
 
-pub fn set_settlement_hash(env: &Env, remittance_id: u64) {
+pub fn add_open_remittance(env: &Env, remittance_id: u64) {
     env.storage()
         .persistent()
-        .set(&DataKey::SettlementHash(remittance_id), &true);
+        .set(&DataKey::OpenRemittance(remittance_id), &true);
 }
 
-pub fn is_paused(env: &Env) -> bool {
+/// Clears a remittance's open marker and, while the head points at a
+/// closed ID, moves it forward by at most `MAX_OPEN_HEAD_ADVANCE` per call
+/// and never beyond `last_id`; later closes carry on from there.
+pub fn remove_open_remittance(env: &Env, remittance_id: u64, last_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::OpenRemittance(remittance_id));
+
+    let mut head = get_open_remittance_head(env);
+    if is_open_remittance(env, head) {
+        return;
+    }
+    let mut steps = 0;
+    while head <= last_id && steps < MAX_OPEN_HEAD_ADVANCE && !is_open_remittance(env, head) {
+        head += 1;
+        steps += 1;
+    }
+    env.storage().instance().set(&DataKey::OpenRemittanceHead, &head);
+}
+
+pub fn get_watch_tags(env: &Env, remittance_id: u64) -> Vec<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::WatchTags(remittance_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_watch_tags(env: &Env, remittance_id: u64, tags: &Vec<Symbol>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::WatchTags(remittance_id), tags);
+}
+
+pub fn get_review(env: &Env, remittance_id: u64) -> Option<ReviewRecord> {
+    env.storage().persistent().get(&DataKey::Review(remittance_id))
+}
+
+pub fn set_review(env: &Env, remittance_id: u64, review: &ReviewRecord) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Review(remittance_id), review);
+}
+
+pub fn get_escrow(env: &Env, remittance_id: u64) -> Result<Escrow, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Escrow(remittance_id))
+        .ok_or(ContractError::EscrowNotFound)
+}
+
+pub fn set_escrow(env: &Env, remittance_id: u64, escrow: &Escrow) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Escrow(remittance_id), escrow);
+}
+
+pub fn get_escrow_dispute_evidence(env: &Env, remittance_id: u64) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EscrowDisputeEvidence(remittance_id))
+}
+
+pub fn set_escrow_dispute_evidence(env: &Env, remittance_id: u64, evidence_hash: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::EscrowDisputeEvidence(remittance_id), evidence_hash);
+}
+
+pub fn get_escrow_count(env: &Env) -> u64 {
     env.storage()
         .instance()
-        .get(&DataKey::Paused)
-        .unwrap_or(false)
+        .get(&DataKey::EscrowCount)
+        .unwrap_or(0)
 }
 
-pub fn set_paused(env: &Env, paused: bool) {
-    env.storage().instance().set(&DataKey::Paused, &paused);
+pub fn get_sender_escrow_count(env: &Env, sender: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SenderEscrowCount(sender.clone()))
+        .unwrap_or(0)
+}
+
+/// ID of `sender`'s `index`-th escrow, oldest first.
+pub fn get_sender_escrow_id(env: &Env, sender: &Address, index: u32) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SenderEscrow(sender.clone(), index))
+}
+
+pub fn get_recipient_escrow_count(env: &Env, recipient: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RecipientEscrowCount(recipient.clone()))
+        .unwrap_or(0)
+}
+
+/// ID of `recipient`'s `index`-th escrow, oldest first.
+pub fn get_recipient_escrow_id(env: &Env, recipient: &Address, index: u32) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RecipientEscrow(recipient.clone(), index))
+}
+
+pub fn get_sender_remittance_count(env: &Env, sender: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SenderRemittanceCount(sender.clone()))
+        .unwrap_or(0)
+}
+
+/// ID of `sender`'s `index`-th remittance, oldest first.
+pub fn get_sender_remittance_id(env: &Env, sender: &Address, index: u32) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SenderRemittance(sender.clone(), index))
+}
+
+/// Appends a newly created remittance to its sender's index. Each entry
+/// is its own key, so the write cost stays flat however many remittances
+/// the sender has created.
+pub fn index_sender_remittance(env: &Env, sender: &Address, remittance_id: u64) {
+    let count = get_sender_remittance_count(env, sender);
+    env.storage()
+        .persistent()
+        .set(&DataKey::SenderRemittance(sender.clone(), count), &remittance_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::SenderRemittanceCount(sender.clone()), &(count + 1));
+}
+
+/// Appends a newly created escrow to the sender and recipient indices and
+/// bumps the escrow count. Like the sender remittance index, each entry is
+/// its own key so the write cost does not grow with history.
+pub fn index_escrow(env: &Env, escrow: &Escrow) {
+    let by_sender = get_sender_escrow_count(env, &escrow.sender);
+    env.storage()
+        .persistent()
+        .set(&DataKey::SenderEscrow(escrow.sender.clone(), by_sender), &escrow.remittance_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::SenderEscrowCount(escrow.sender.clone()), &(by_sender + 1));
+
+    let by_recipient = get_recipient_escrow_count(env, &escrow.agent);
+    env.storage()
+        .persistent()
+        .set(&DataKey::RecipientEscrow(escrow.agent.clone(), by_recipient), &escrow.remittance_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::RecipientEscrowCount(escrow.agent.clone()), &(by_recipient + 1));
+
+    env.storage()
+        .instance()
+        .set(&DataKey::EscrowCount, &(get_escrow_count(env) + 1));
+}
+
+pub fn get_token_info(env: &Env, token: &Address) -> Option<TokenInfo> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TokenInfo(token.clone()))
+}
+
+pub fn set_token_info(env: &Env, token: &Address, info: Option<&TokenInfo>) {
+    let key = DataKey::TokenInfo(token.clone());
+    match info {
+        Some(info) => env.storage().persistent().set(&key, info),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_contract_metadata(env: &Env) -> Option<ContractMetadata> {
+    env.storage().instance().get(&DataKey::ContractMetadata)
+}
+
+pub fn set_contract_metadata(env: &Env, metadata: &ContractMetadata) {
+    env.storage()
+        .instance()
+        .set(&DataKey::ContractMetadata, metadata);
+}
+
+pub fn get_admin_recovery(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::AdminRecovery)
+}
+
+pub fn set_admin_recovery(env: &Env, recovery: Option<&Address>) {
+    match recovery {
+        Some(recovery) => env.storage().instance().set(&DataKey::AdminRecovery, recovery),
+        None => env.storage().instance().remove(&DataKey::AdminRecovery),
+    }
+}
+
+pub fn get_last_admin_activity(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::LastAdminActivity)
+        .unwrap_or(0)
+}
+
+pub fn set_last_admin_activity(env: &Env, timestamp: u64) {
+    env.storage()
+        .instance()
+        .set(&DataKey::LastAdminActivity, &timestamp);
+}
+
+pub fn get_default_expiry_seconds(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&DataKey::DefaultExpirySeconds)
+}
+
+pub fn set_default_expiry_seconds(env: &Env, seconds: Option<u64>) {
+    match seconds {
+        Some(seconds) => env.storage().instance().set(&DataKey::DefaultExpirySeconds, &seconds),
+        None => env.storage().instance().remove(&DataKey::DefaultExpirySeconds),
+    }
+}
+
+pub fn get_expiry_warning_seconds(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&DataKey::ExpiryWarningSeconds)
+}
+
+pub fn set_expiry_warning_seconds(env: &Env, seconds: Option<u64>) {
+    match seconds {
+        Some(seconds) => env.storage().instance().set(&DataKey::ExpiryWarningSeconds, &seconds),
+        None => env.storage().instance().remove(&DataKey::ExpiryWarningSeconds),
+    }
+}
+
+pub fn is_expiry_warned(env: &Env, remittance_id: u64) -> bool {
+    env.storage()
+        .temporary()
+        .has(&DataKey::ExpiryWarned(remittance_id))
+}
+
+/// Records that a remittance's expiry warning went out. The marker only
+/// needs to last until the remittance expires.
+pub fn set_expiry_warned(env: &Env, remittance_id: u64, ttl_seconds: u64) {
+    let key = DataKey::ExpiryWarned(remittance_id);
+    env.storage().temporary().set(&key, &true);
+    extend_temporary_ttl(env, &key, ttl_seconds);
+}
+
+pub fn get_max_total_locked(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&DataKey::MaxTotalLocked)
+}
+
+pub fn set_max_total_locked(env: &Env, max: Option<i128>) {
+    match max {
+        Some(max) => env.storage().instance().set(&DataKey::MaxTotalLocked, &max),
+        None => env.storage().instance().remove(&DataKey::MaxTotalLocked),
+    }
+}
+
+fn feature_bit(feature: Feature) -> u32 {
+    1 << feature as u32
+}
+
+pub fn get_features(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::Features)
+        .unwrap_or(0)
+}
+
+pub fn is_feature_enabled(env: &Env, feature: Feature) -> bool {
+    get_features(env) & feature_bit(feature) != 0
+}
+
+pub fn set_feature_enabled(env: &Env, feature: Feature, enabled: bool) {
+    let features = if enabled {
+        get_features(env) | feature_bit(feature)
+    } else {
+        get_features(env) & !feature_bit(feature)
+    };
+    env.storage().instance().set(&DataKey::Features, &features);
+}
+
+pub fn are_paused_refunds_allowed(env: &Env) -> bool {
+    is_feature_enabled(env, Feature::PausedRefunds)
+}
+
+pub fn set_paused_refunds_allowed(env: &Env, allowed: bool) {
+    set_feature_enabled(env, Feature::PausedRefunds, allowed);
+}
+
+pub fn is_corridor_registry_enabled(env: &Env) -> bool {
+    is_feature_enabled(env, Feature::CorridorRegistry)
+}
+
+pub fn set_corridor_registry_enabled(env: &Env, enabled: bool) {
+    set_feature_enabled(env, Feature::CorridorRegistry, enabled);
+}
+
+pub fn get_allowed_currencies(env: &Env) -> Vec<String> {
+    env.storage()
+        .instance()
+        .get(&DataKey::AllowedCurrencies)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_allowed_currencies(env: &Env, currencies: &Vec<String>) {
+    env.storage()
+        .instance()
+        .set(&DataKey::AllowedCurrencies, currencies);
+}
+
+pub fn get_allowed_countries(env: &Env) -> Vec<String> {
+    env.storage()
+        .instance()
+        .get(&DataKey::AllowedCountries)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_allowed_countries(env: &Env, countries: &Vec<String>) {
+    env.storage()
+        .instance()
+        .set(&DataKey::AllowedCountries, countries);
+}
+
+pub fn get_rate_limit_config(env: &Env) -> Option<RateLimitConfig> {
+    env.storage().instance().get(&DataKey::RateLimitConfig)
+}
+
+pub fn set_rate_limit_config(env: &Env, config: Option<RateLimitConfig>) {
+    match config {
+        Some(config) => env.storage().instance().set(&DataKey::RateLimitConfig, &config),
+        None => env.storage().instance().remove(&DataKey::RateLimitConfig),
+    }
+}
+
+/// Approximate ledger close time, used to convert rate-limit periods and
+/// the quote window into storage TTLs.
+const LEDGER_CLOSE_SECONDS: u64 = 5;
+
+/// Keeps a temporary entry alive for at least `seconds`. Rate-limit entries
+/// only matter for the cooldown or window they enforce, so letting them
+/// expire afterwards keeps storage from growing with every one-time sender.
+fn extend_temporary_ttl(env: &Env, key: &DataKey, seconds: u64) {
+    let ledgers = seconds
+        .div_ceil(LEDGER_CLOSE_SECONDS)
+        .min(env.storage().max_ttl() as u64) as u32;
+    env.storage().temporary().extend_ttl(key, ledgers, ledgers);
+}
+
+/// Lifetime a nonce entry is extended to on each use. A nonce idle for
+/// longer is archived, not dropped: it has to be restored before the
+/// sender's next operation and keeps its value, so nonces never repeat.
+const NONCE_TTL_SECONDS: u64 = 90 * 24 * 60 * 60;
+
+pub fn get_notification_contract(env: &Env, sender: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::NotificationContract(sender.clone()))
+}
+
+pub fn set_notification_contract(env: &Env, sender: &Address, contract: Option<Address>) {
+    let key = DataKey::NotificationContract(sender.clone());
+    match contract {
+        Some(contract) => env.storage().persistent().set(&key, &contract),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_nonce(env: &Env, address: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Nonce(address.clone()))
+        .unwrap_or(0)
+}
+
+/// Bumps the address's nonce after a signed operation and returns the new
+/// value.
+pub fn increment_nonce(env: &Env, address: &Address) -> u64 {
+    let key = DataKey::Nonce(address.clone());
+    let nonce = get_nonce(env, address) + 1;
+    env.storage().persistent().set(&key, &nonce);
+
+    let ledgers = NONCE_TTL_SECONDS
+        .div_ceil(LEDGER_CLOSE_SECONDS)
+        .min(env.storage().max_ttl() as u64) as u32;
+    env.storage().persistent().extend_ttl(&key, ledgers, ledgers);
+
+    nonce
+}
+
+pub fn get_last_settlement_time(env: &Env, sender: &Address) -> Option<u64> {
+    env.storage()
+        .temporary()
+        .get(&DataKey::LastSettlementTime(sender.clone()))
+}
+
+pub fn set_last_settlement_time(env: &Env, sender: &Address, timestamp: u64, ttl_seconds: u64) {
+    let key = DataKey::LastSettlementTime(sender.clone());
+    env.storage().temporary().set(&key, &timestamp);
+    extend_temporary_ttl(env, &key, ttl_seconds);
+}
+
+pub fn get_rate_limit_window(env: &Env, sender: &Address) -> RateLimitWindow {
+    env.storage()
+        .temporary()
+        .get(&DataKey::RateLimitWindow(sender.clone()))
+        .unwrap_or_default()
+}
+
+pub fn set_rate_limit_window(env: &Env, sender: &Address, window: &RateLimitWindow, ttl_seconds: u64) {
+    let key = DataKey::RateLimitWindow(sender.clone());
+    env.storage().temporary().set(&key, window);
+    extend_temporary_ttl(env, &key, ttl_seconds);
+}
+
+pub fn get_self_daily_cap(env: &Env, sender: &Address) -> SelfCap {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SelfDailyCap(sender.clone()))
+        .unwrap_or_default()
+}
+
+pub fn set_self_daily_cap(env: &Env, sender: &Address, cap: &SelfCap) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SelfDailyCap(sender.clone()), cap);
+}
+
+pub fn get_self_max_tx(env: &Env, sender: &Address) -> SelfCap {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SelfMaxTx(sender.clone()))
+        .unwrap_or_default()
+}
+
+pub fn set_self_max_tx(env: &Env, sender: &Address, cap: &SelfCap) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SelfMaxTx(sender.clone()), cap);
+}
+
+pub fn get_daily_spend(env: &Env, sender: &Address) -> DailySpend {
+    env.storage()
+        .temporary()
+        .get(&DataKey::DailySpend(sender.clone()))
+        .unwrap_or_default()
+}
+
+pub fn set_daily_spend(env: &Env, sender: &Address, spend: &DailySpend, ttl_seconds: u64) {
+    let key = DataKey::DailySpend(sender.clone());
+    env.storage().temporary().set(&key, spend);
+    extend_temporary_ttl(env, &key, ttl_seconds);
+}
+
+pub fn get_corridor_receive_limit(env: &Env, currency: &String, country: &String) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CorridorReceiveLimit(currency.clone(), country.clone()))
+}
+
+pub fn set_corridor_receive_limit(env: &Env, currency: &String, country: &String, limit: Option<i128>) {
+    let key = DataKey::CorridorReceiveLimit(currency.clone(), country.clone());
+    match limit {
+        Some(limit) => env.storage().persistent().set(&key, &limit),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_agent_received(env: &Env, agent: &Address, currency: &String, country: &String) -> Vec<DailySpend> {
+    env.storage()
+        .temporary()
+        .get(&DataKey::AgentReceived(agent.clone(), currency.clone(), country.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn set_agent_received(env: &Env, agent: &Address, currency: &String, country: &String, received: &Vec<DailySpend>, ttl_seconds: u64) {
+    let key = DataKey::AgentReceived(agent.clone(), currency.clone(), country.clone());
+    env.storage().temporary().set(&key, received);
+    extend_temporary_ttl(env, &key, ttl_seconds);
+}
+
+pub fn are_exposure_caps_enabled(env: &Env) -> bool {
+    is_feature_enabled(env, Feature::ExposureCaps)
+}
+
+pub fn set_exposure_caps_enabled(env: &Env, enabled: bool) {
+    set_feature_enabled(env, Feature::ExposureCaps, enabled);
+}
+
+pub fn get_global_exposure_cap(env: &Env) -> Option<i128> {
+    env.storage().instance().get(&DataKey::GlobalExposureCap)
+}
+
+pub fn set_global_exposure_cap(env: &Env, cap: Option<i128>) {
+    match cap {
+        Some(cap) => env.storage().instance().set(&DataKey::GlobalExposureCap, &cap),
+        None => env.storage().instance().remove(&DataKey::GlobalExposureCap),
+    }
+}
+
+pub fn get_sender_exposure_cap(env: &Env, sender: &Address) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SenderExposureCap(sender.clone()))
+}
+
+pub fn set_sender_exposure_cap(env: &Env, sender: &Address, cap: Option<i128>) {
+    let key = DataKey::SenderExposureCap(sender.clone());
+    match cap {
+        Some(cap) => env.storage().persistent().set(&key, &cap),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Records an agent's registration and keeps the network's active agent
+/// count in step with it.
+pub fn set_agent_registered(env: &Env, agent: &Address, registered: bool) {
+    if is_agent_registered(env, agent) != registered {
+        let mut tally = get_network_tally(env);
+        tally.active_agents = if registered {
+            tally.active_agents.saturating_add(1)
+        } else {
+            tally.active_agents.saturating_sub(1)
+        };
+        set_network_tally(env, &tally);
+    }
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::AgentRegistered(agent.clone()), &registered);
+}
+
+pub fn is_agent_registered(env: &Env, agent: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AgentRegistered(agent.clone()))
+        .unwrap_or(false)
+}
+
+pub fn get_agent_removal_reason(env: &Env, agent: &Address) -> Option<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AgentRemovalReason(agent.clone()))
+}
+
+pub fn set_agent_removal_reason(env: &Env, agent: &Address, reason: Option<&Symbol>) {
+    let key = DataKey::AgentRemovalReason(agent.clone());
+    match reason {
+        Some(reason) => env.storage().persistent().set(&key, reason),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_agent_workload(env: &Env, agent: &Address) -> AgentWorkload {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AgentWorkload(agent.clone()))
+        .unwrap_or_default()
+}
+
+pub fn set_agent_workload(env: &Env, agent: &Address, workload: &AgentWorkload) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::AgentWorkload(agent.clone()), workload);
+}
+
+pub fn get_agent_reputation(env: &Env, agent: &Address) -> AgentReputation {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AgentReputation(agent.clone()))
+        .unwrap_or_default()
+}
+
+pub fn set_agent_reputation(env: &Env, agent: &Address, reputation: &AgentReputation) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::AgentReputation(agent.clone()), reputation);
+}
+
+pub fn get_pair_pending(env: &Env, from: &Address, to: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PairPending(from.clone(), to.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_pair_pending(env: &Env, from: &Address, to: &Address, value: i128) {
+    let key = DataKey::PairPending(from.clone(), to.clone());
+    if value == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &value);
+    }
+}
+
+pub fn get_agent_rebate_bps(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::AgentRebateBps)
+        .unwrap_or(0)
+}
+
+pub fn set_agent_rebate_bps(env: &Env, rebate_bps: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::AgentRebateBps, &rebate_bps);
+}
+
+pub fn get_agent_rebate_balance(env: &Env, agent: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AgentRebateBalance(agent.clone()))
+        .unwrap_or(0)
+}
+
+/// Stores an agent's rebate balance and keeps the claimable balance total in
+/// step with it.
+pub fn set_agent_rebate_balance(env: &Env, agent: &Address, balance: i128) {
+    adjust_claimable_balance_total(env, get_agent_rebate_balance(env, agent), balance);
+    env.storage()
+        .persistent()
+        .set(&DataKey::AgentRebateBalance(agent.clone()), &balance);
+}
+
+pub fn get_referral_config(env: &Env) -> Option<ReferralConfig> {
+    env.storage().instance().get(&DataKey::ReferralConfig)
+}
+
+pub fn set_referral_config(env: &Env, config: Option<ReferralConfig>) {
+    match config {
+        Some(config) => env.storage().instance().set(&DataKey::ReferralConfig, &config),
+        None => env.storage().instance().remove(&DataKey::ReferralConfig),
+    }
+}
+
+pub fn get_referral(env: &Env, sender: &Address) -> Option<Referral> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Referral(sender.clone()))
+}
+
+pub fn set_referral(env: &Env, sender: &Address, referral: &Referral) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Referral(sender.clone()), referral);
+}
+
+pub fn get_referral_balance(env: &Env, referrer: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ReferralBalance(referrer.clone()))
+        .unwrap_or(0)
+}
+
+/// Stores a referrer's reward balance and keeps the claimable balance total
+/// in step with it.
+pub fn set_referral_balance(env: &Env, referrer: &Address, balance: i128) {
+    adjust_claimable_balance_total(env, get_referral_balance(env, referrer), balance);
+    env.storage()
+        .persistent()
+        .set(&DataKey::ReferralBalance(referrer.clone()), &balance);
+}
+
+pub fn get_agent_surcharge_bps(env: &Env, agent: &Address) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AgentSurchargeBps(agent.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_agent_surcharge_bps(env: &Env, agent: &Address, surcharge_bps: u32) {
+    let key = DataKey::AgentSurchargeBps(agent.clone());
+    if surcharge_bps == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &surcharge_bps);
+    }
+}
+
+pub fn get_agent_float(env: &Env, agent: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AgentFloat(agent.clone()))
+        .unwrap_or(0)
+}
+
+/// Stores an agent's float and keeps the float total in step with it.
+pub fn set_agent_float(env: &Env, agent: &Address, float: i128) {
+    let total = get_agent_float_total(env)
+        .saturating_sub(get_agent_float(env, agent))
+        .saturating_add(float);
+    env.storage().instance().set(&DataKey::AgentFloatTotal, &total);
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::AgentFloat(agent.clone()), &float);
+}
+
+pub fn get_agent_float_total(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::AgentFloatTotal)
+        .unwrap_or(0)
+}
+
+pub fn get_claimable_balance_total(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ClaimableBalanceTotal)
+        .unwrap_or(0)
+}
+
+fn adjust_claimable_balance_total(env: &Env, previous: i128, current: i128) {
+    let total = get_claimable_balance_total(env)
+        .saturating_sub(previous)
+        .saturating_add(current);
+    env.storage()
+        .instance()
+        .set(&DataKey::ClaimableBalanceTotal, &total);
+}
+
+pub fn settles_from_float(env: &Env, agent: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AgentSettlesFromFloat(agent.clone()))
+        .unwrap_or(false)
+}
+
+pub fn set_settles_from_float(env: &Env, agent: &Address, enabled: bool) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::AgentSettlesFromFloat(agent.clone()), &enabled);
+}
+
+pub fn get_payout_route(env: &Env, agent: &Address) -> Option<PayoutRoute> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PayoutRoute(agent.clone()))
+}
+
+pub fn set_payout_route(env: &Env, agent: &Address, route: Option<&PayoutRoute>) {
+    let key = DataKey::PayoutRoute(agent.clone());
+    match route {
+        Some(route) => env.storage().persistent().set(&key, route),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn has_settlement_hash(env: &Env, remittance_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::SettlementHash(remittance_id))
+}
+
+pub fn get_settlement_hash(env: &Env, remittance_id: u64) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SettlementHash(remittance_id))
+}
+
+pub fn set_settlement_hash(env: &Env, remittance_id: u64, hash: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SettlementHash(remittance_id), hash);
+    env.storage()
+        .persistent()
+        .set(&DataKey::SettlementHashIndex(hash.clone()), &remittance_id);
+}
+
+pub fn get_event_sequence(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::EventSequence)
+        .unwrap_or(0)
+}
+
+/// Advances and returns the contract-wide event sequence. The first event
+/// gets sequence 1.
+pub fn get_event_verbosity(env: &Env) -> EventVerbosity {
+    env.storage()
+        .instance()
+        .get(&DataKey::EventVerbosity)
+        .unwrap_or(EventVerbosity::Verbose)
+}
+
+pub fn set_event_verbosity(env: &Env, verbosity: EventVerbosity) {
+    env.storage()
+        .instance()
+        .set(&DataKey::EventVerbosity, &verbosity);
+}
+
+pub fn next_event_sequence(env: &Env) -> u64 {
+    let sequence = get_event_sequence(env) + 1;
+    env.storage().instance().set(&DataKey::EventSequence, &sequence);
+    sequence
+}
+
+pub fn get_settlement_proof(env: &Env, remittance_id: u64) -> Option<SettlementProof> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SettlementProof(remittance_id))
+}
+
+pub fn set_settlement_proof(env: &Env, remittance_id: u64, proof: &SettlementProof) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SettlementProof(remittance_id), proof);
+}
+
+pub fn get_remittance_id_by_settlement_hash(env: &Env, hash: &BytesN<32>) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SettlementHashIndex(hash.clone()))
+}
+
+/// Returns true while the contract is paused and the pause has not expired.
+/// Configuration the settlement, cancellation and batch paths consult,
+/// loaded once per invocation by `load_settlement_context` instead of
+/// reading each setting where it is used.
+pub struct SettlementContext {
+    pub usdc_token: Address,
+    pub paused: bool,
+    pub authorization_required: bool,
+    pub dual_confirm_timeout: u64,
+    pub agent_rebate_bps: u32,
+    pub sla_penalty_bps: u32,
+}
+
+pub fn load_settlement_context(env: &Env) -> Result<SettlementContext, ContractError> {
+    Ok(SettlementContext {
+        usdc_token: get_usdc_token(env)?,
+        paused: is_paused(env),
+        authorization_required: is_authorization_required(env),
+        dual_confirm_timeout: get_dual_confirm_timeout(env),
+        agent_rebate_bps: get_agent_rebate_bps(env),
+        sla_penalty_bps: get_sla_penalty_bps(env),
+    })
+}
+
+pub fn is_paused(env: &Env) -> bool {
+    let paused: bool = env
+        .storage()
+        .instance()
+        .get(&DataKey::Paused)
+        .unwrap_or(false);
+
+    if !paused {
+        return false;
+    }
+
+    match get_pause_info(env).and_then(|info| info.pause_until) {
+        Some(pause_until) => env.ledger().timestamp() < pause_until,
+        None => true,
+    }
+}
+
+pub fn set_paused(env: &Env, paused: bool) {
+    env.storage().instance().set(&DataKey::Paused, &paused);
+}
+
+pub fn has_role(env: &Env, role: &Role, address: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoleMember(role.clone(), address.clone()))
+        .unwrap_or(false)
+}
+
+pub fn set_role(env: &Env, role: &Role, address: &Address, granted: bool) {
+    if has_role(env, role, address) == granted {
+        return;
+    }
+
+    let key = DataKey::RoleMember(role.clone(), address.clone());
+    let mut members = get_role_members(env, role);
+    if granted {
+        env.storage().persistent().set(&key, &true);
+        members.push_back(address.clone());
+    } else {
+        env.storage().persistent().remove(&key);
+        if let Some(index) = members.first_index_of(address) {
+            members.remove(index);
+        }
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::RoleMembers(role.clone()), &members);
+}
+
+pub fn get_role_members(env: &Env, role: &Role) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoleMembers(role.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn is_sender_allowlist_enabled(env: &Env) -> bool {
+    is_feature_enabled(env, Feature::SenderAllowlist)
+}
+
+pub fn set_sender_allowlist_enabled(env: &Env, enabled: bool) {
+    set_feature_enabled(env, Feature::SenderAllowlist, enabled);
+}
+
+pub fn is_authorization_required(env: &Env) -> bool {
+    is_feature_enabled(env, Feature::AuthorizationRequired)
+}
+
+pub fn set_authorization_required(env: &Env, required: bool) {
+    set_feature_enabled(env, Feature::AuthorizationRequired, required);
+}
+
+pub fn is_private_mode(env: &Env) -> bool {
+    is_feature_enabled(env, Feature::PrivateMode)
+}
+
+pub fn set_private_mode(env: &Env, enabled: bool) {
+    set_feature_enabled(env, Feature::PrivateMode, enabled);
+}
+
+pub fn get_remittance_authorizer(env: &Env, remittance_id: u64) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RemittanceAuthorizer(remittance_id))
+}
+
+pub fn set_remittance_authorizer(env: &Env, remittance_id: u64, authorizer: &Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::RemittanceAuthorizer(remittance_id), authorizer);
+}
+
+pub fn get_dual_confirm_timeout(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::DualConfirmTimeout)
+        .unwrap_or(DEFAULT_DUAL_CONFIRM_TIMEOUT_SECONDS)
+}
+
+pub fn set_dual_confirm_timeout(env: &Env, timeout_seconds: u64) {
+    env.storage()
+        .instance()
+        .set(&DataKey::DualConfirmTimeout, &timeout_seconds);
+}
+
+pub fn get_pause_info(env: &Env) -> Option<PauseInfo> {
+    env.storage().instance().get(&DataKey::PauseInfo)
+}
+
+pub fn set_pause_info(env: &Env, info: Option<&PauseInfo>) {
+    match info {
+        Some(info) => env.storage().instance().set(&DataKey::PauseInfo, info),
+        None => env.storage().instance().remove(&DataKey::PauseInfo),
+    }
+}
+
+pub fn get_max_pause_duration(env: &Env) -> Option<u64> {
+    env.storage().instance().get(&DataKey::MaxPauseDuration)
+}
+
+pub fn set_max_pause_duration(env: &Env, seconds: Option<u64>) {
+    match seconds {
+        Some(seconds) => env
+            .storage()
+            .instance()
+            .set(&DataKey::MaxPauseDuration, &seconds),
+        None => env.storage().instance().remove(&DataKey::MaxPauseDuration),
+    }
+}
+
+pub fn get_network_tally(env: &Env) -> NetworkTally {
+    env.storage()
+        .instance()
+        .get(&DataKey::NetworkTally)
+        .unwrap_or_default()
+}
+
+pub fn set_network_tally(env: &Env, tally: &NetworkTally) {
+    env.storage().instance().set(&DataKey::NetworkTally, tally);
+}
+
+pub fn get_corridor_stats(env: &Env, currency: &String, country: &String) -> CorridorStats {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CorridorStats(currency.clone(), country.clone()))
+        .unwrap_or_default()
+}
+
+pub fn is_corridor_enabled(env: &Env, currency: &String, country: &String) -> bool {
+    !env.storage()
+        .persistent()
+        .has(&DataKey::CorridorDisabled(currency.clone(), country.clone()))
+}
+
+pub fn set_corridor_enabled(env: &Env, currency: &String, country: &String, enabled: bool) {
+    let key = DataKey::CorridorDisabled(currency.clone(), country.clone());
+    if enabled {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &true);
+    }
+}
+
+pub fn get_corridor_sla(env: &Env, currency: &String, country: &String) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CorridorSla(currency.clone(), country.clone()))
+}
+
+pub fn set_corridor_sla(env: &Env, currency: &String, country: &String, max_seconds: Option<u64>) {
+    let key = DataKey::CorridorSla(currency.clone(), country.clone());
+    match max_seconds {
+        Some(max_seconds) => env.storage().persistent().set(&key, &max_seconds),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_corridor_expiry_bounds(env: &Env, currency: &String, country: &String) -> Option<ExpiryBounds> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CorridorExpiryBounds(currency.clone(), country.clone()))
+}
+
+pub fn set_corridor_expiry_bounds(env: &Env, currency: &String, country: &String, bounds: Option<&ExpiryBounds>) {
+    let key = DataKey::CorridorExpiryBounds(currency.clone(), country.clone());
+    match bounds {
+        Some(bounds) => env.storage().persistent().set(&key, bounds),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_corridor_chargeback_window(env: &Env, currency: &String, country: &String) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CorridorChargebackWindow(currency.clone(), country.clone()))
+}
+
+pub fn set_corridor_chargeback_window(env: &Env, currency: &String, country: &String, window_seconds: Option<u64>) {
+    let key = DataKey::CorridorChargebackWindow(currency.clone(), country.clone());
+    match window_seconds {
+        Some(window_seconds) => env.storage().persistent().set(&key, &window_seconds),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_corridor_fee_mode(env: &Env, currency: &String, country: &String) -> NettingFeeMode {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CorridorFeeMode(currency.clone(), country.clone()))
+        .unwrap_or(NettingFeeMode::Gross)
+}
+
+pub fn set_corridor_fee_mode(env: &Env, currency: &String, country: &String, mode: &NettingFeeMode) {
+    let key = DataKey::CorridorFeeMode(currency.clone(), country.clone());
+    match mode {
+        NettingFeeMode::Gross => env.storage().persistent().remove(&key),
+        NettingFeeMode::Net => env.storage().persistent().set(&key, mode),
+    }
+}
+
+pub fn get_reserve_bps(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ReserveBps)
+        .unwrap_or(0)
+}
+
+pub fn set_reserve_bps(env: &Env, reserve_bps: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::ReserveBps, &reserve_bps);
+}
+
+pub fn get_challenge_config(env: &Env) -> Option<ChallengeConfig> {
+    env.storage().instance().get(&DataKey::ChallengeConfig)
+}
+
+pub fn set_challenge_config(env: &Env, config: Option<ChallengeConfig>) {
+    match config {
+        Some(config) => env.storage().instance().set(&DataKey::ChallengeConfig, &config),
+        None => env.storage().instance().remove(&DataKey::ChallengeConfig),
+    }
+}
+
+pub fn get_settlement_challenge(env: &Env, remittance_id: u64) -> Option<SettlementChallenge> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SettlementChallenge(remittance_id))
+}
+
+pub fn set_settlement_challenge(env: &Env, remittance_id: u64, challenge: &SettlementChallenge) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SettlementChallenge(remittance_id), challenge);
+}
+
+pub fn get_held_challenge_bonds(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::HeldChallengeBonds)
+        .unwrap_or(0)
+}
+
+pub fn set_held_challenge_bonds(env: &Env, amount: i128) {
+    env.storage()
+        .instance()
+        .set(&DataKey::HeldChallengeBonds, &amount);
+}
+
+pub fn get_current_epoch(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::CurrentEpoch)
+        .unwrap_or(0)
+}
+
+pub fn set_current_epoch(env: &Env, epoch: u64) {
+    env.storage()
+        .instance()
+        .set(&DataKey::CurrentEpoch, &epoch);
+}
+
+pub fn get_epoch_report(env: &Env, epoch: u64) -> Option<EpochReport> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EpochReport(epoch))
+}
+
+pub fn set_epoch_report(env: &Env, report: &EpochReport) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::EpochReport(report.epoch), report);
+}
+
+pub fn get_held_payout(env: &Env, remittance_id: u64) -> Option<HeldPayout> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::HeldPayout(remittance_id))
+}
+
+pub fn set_held_payout(env: &Env, remittance_id: u64, held: Option<&HeldPayout>) {
+    let key = DataKey::HeldPayout(remittance_id);
+    match held {
+        Some(held) => env.storage().persistent().set(&key, held),
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_held_payout_total(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::HeldPayoutTotal)
+        .unwrap_or(0)
+}
+
+pub fn set_held_payout_total(env: &Env, amount: i128) {
+    env.storage()
+        .instance()
+        .set(&DataKey::HeldPayoutTotal, &amount);
+}
+
+pub fn get_pending_tip_total(env: &Env) -> i128 {
+    env.storage()
+        .instance()
+        .get(&DataKey::PendingTipTotal)
+        .unwrap_or(0)
+}
+
+pub fn set_pending_tip_total(env: &Env, amount: i128) {
+    env.storage()
+        .instance()
+        .set(&DataKey::PendingTipTotal, &amount);
+}
+
+pub fn get_insurance_premium_bps(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::InsurancePremiumBps)
+        .unwrap_or(0)
+}
+
+pub fn set_insurance_premium_bps(env: &Env, premium_bps: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::InsurancePremiumBps, &premium_bps);
+}
+
+pub fn get_insurance_fund(env: &Env) -> InsuranceFund {
+    env.storage()
+        .instance()
+        .get(&DataKey::InsuranceFund)
+        .unwrap_or_default()
+}
+
+pub fn set_insurance_fund(env: &Env, fund: &InsuranceFund) {
+    env.storage().instance().set(&DataKey::InsuranceFund, fund);
+}
+
+pub fn get_insurance_claim(env: &Env, remittance_id: u64) -> Option<InsuranceClaim> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::InsuranceClaim(remittance_id))
+}
+
+pub fn set_insurance_claim(env: &Env, remittance_id: u64, claim: &InsuranceClaim) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::InsuranceClaim(remittance_id), claim);
+}
+
+pub fn get_sla_penalty_bps(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::SlaPenaltyBps)
+        .unwrap_or(0)
+}
+
+pub fn set_sla_penalty_bps(env: &Env, penalty_bps: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::SlaPenaltyBps, &penalty_bps);
+}
+
+pub fn set_corridor_stats(env: &Env, currency: &String, country: &String, stats: &CorridorStats) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::CorridorStats(currency.clone(), country.clone()), stats);
+}
+
+pub fn get_tag_count(env: &Env, tag: &Symbol) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TagCount(tag.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_tag_count(env: &Env, tag: &Symbol, count: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::TagCount(tag.clone()), &count);
+}
+
+pub fn is_batch_permissionless(env: &Env) -> bool {
+    is_feature_enabled(env, Feature::BatchPermissionless)
+}
+
+pub fn set_batch_permissionless(env: &Env, permissionless: bool) {
+    set_feature_enabled(env, Feature::BatchPermissionless, permissionless);
+}
+
+pub fn is_alternate_refund_enabled(env: &Env) -> bool {
+    is_feature_enabled(env, Feature::AlternateRefund)
+}
+
+pub fn set_alternate_refund_enabled(env: &Env, enabled: bool) {
+    set_feature_enabled(env, Feature::AlternateRefund, enabled);
+}
+
+pub fn get_settlement_windows(env: &Env) -> Option<Vec<SettlementWindow>> {
+    env.storage().instance().get(&DataKey::SettlementWindows)
+}
+
+pub fn set_settlement_windows(env: &Env, windows: Option<Vec<SettlementWindow>>) {
+    match windows {
+        Some(windows) => env.storage().instance().set(&DataKey::SettlementWindows, &windows),
+        None => env.storage().instance().remove(&DataKey::SettlementWindows),
+    }
+}
+
+pub fn is_batch_agent_approval_required(env: &Env) -> bool {
+    is_feature_enabled(env, Feature::BatchAgentApproval)
+}
+
+pub fn set_batch_agent_approval_required(env: &Env, required: bool) {
+    set_feature_enabled(env, Feature::BatchAgentApproval, required);
+}
+
+pub fn is_settlement_approved(env: &Env, remittance_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SettlementApproval(remittance_id))
+        .unwrap_or(false)
+}
+
+pub fn set_settlement_approved(env: &Env, remittance_id: u64) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::SettlementApproval(remittance_id), &true);
+}
+
+pub fn get_agent_signing_key(env: &Env, agent: &Address) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AgentSigningKey(agent.clone()))
+}
+
+pub fn set_agent_signing_key(env: &Env, agent: &Address, key: Option<&BytesN<32>>) {
+    let key_entry = DataKey::AgentSigningKey(agent.clone());
+    match key {
+        Some(key) => env.storage().persistent().set(&key_entry, key),
+        None => env.storage().persistent().remove(&key_entry),
+    }
+}
+
+pub fn get_session_key(env: &Env, agent: &Address, key: &BytesN<32>) -> Option<SessionKey> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AgentSessionKey(agent.clone(), key.clone()))
+}
+
+pub fn set_session_key(env: &Env, agent: &Address, key: &BytesN<32>, session: Option<&SessionKey>) {
+    let key_entry = DataKey::AgentSessionKey(agent.clone(), key.clone());
+    match session {
+        Some(session) => env.storage().persistent().set(&key_entry, session),
+        None => env.storage().persistent().remove(&key_entry),
+    }
+}
+
+pub fn is_payout_nonce_used(env: &Env, agent: &Address, nonce: u64) -> bool {
+    env.storage()
+        .temporary()
+        .has(&DataKey::PayoutApprovalNonce(agent.clone(), nonce))
+}
+
+/// Marks an approval nonce as used. It only needs to outlive the approval's
+/// deadline, after which the signature is rejected anyway.
+pub fn set_payout_nonce_used(env: &Env, agent: &Address, nonce: u64, ttl_seconds: u64) {
+    let key = DataKey::PayoutApprovalNonce(agent.clone(), nonce);
+    env.storage().temporary().set(&key, &true);
+    extend_temporary_ttl(env, &key, ttl_seconds);
+}
+
+pub fn get_batch_counter(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::BatchCounter)
+        .unwrap_or(0)
+}
+
+pub fn set_batch_counter(env: &Env, counter: u64) {
+    env.storage().instance().set(&DataKey::BatchCounter, &counter);
+}
+
+pub fn get_batch(env: &Env, batch_id: u64) -> Result<SettlementBatch, ContractError> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Batch(batch_id))
+        .ok_or(ContractError::BatchNotFound)
+}
+
+pub fn set_batch(env: &Env, batch: &SettlementBatch) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Batch(batch.id), batch);
+}
+
+pub fn get_batch_abort(env: &Env, submitter: &Address) -> Option<BatchAbort> {
+    env.storage()
+        .temporary()
+        .get(&DataKey::BatchAbort(submitter.clone()))
+}
+
+pub fn set_batch_abort(env: &Env, abort: &BatchAbort, ttl_seconds: u64) {
+    let key = DataKey::BatchAbort(abort.submitter.clone());
+    env.storage().temporary().set(&key, abort);
+    extend_temporary_ttl(env, &key, ttl_seconds);
+}
+
+pub fn get_quote_counter(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::QuoteCounter)
+        .unwrap_or(0)
+}
+
+pub fn set_quote_counter(env: &Env, counter: u64) {
+    env.storage().instance().set(&DataKey::QuoteCounter, &counter);
+}
+
+/// Extra lifetime given to a quote entry so ledgers closing faster
+/// than `LEDGER_CLOSE_SECONDS` cannot end it before the quote expires.
+const QUOTE_TTL_MARGIN_SECONDS: u64 = 60;
+
+/// Stores a quote and keeps its temporary entry alive for the whole
+/// validity window plus a margin.
+pub fn set_quote(env: &Env, id: u64, quote: &Quote) {
+    let key = DataKey::Quote(id);
+    env.storage().temporary().set(&key, quote);
+    extend_temporary_ttl(env, &key, QUOTE_VALIDITY_SECONDS + QUOTE_TTL_MARGIN_SECONDS);
+}
+
+pub fn get_quote(env: &Env, id: u64) -> Result<Quote, ContractError> {
+    env.storage()
+        .temporary()
+        .get(&DataKey::Quote(id))
+        .ok_or(ContractError::QuoteNotFound)
+}
+
+pub fn remove_quote(env: &Env, id: u64) {
+    env.storage().temporary().remove(&DataKey::Quote(id));
+}
+
+pub fn get_guardian(env: &Env) -> Result<Address, ContractError> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Guardian)
+        .ok_or(ContractError::GuardianNotSet)
+}
+
+pub fn set_guardian(env: &Env, guardian: &Address) {
+    env.storage().instance().set(&DataKey::Guardian, guardian);
+}
+
+pub fn get_recovery_vault(env: &Env) -> Option<RecoveryVault> {
+    env.storage().instance().get(&DataKey::RecoveryVault)
+}
+
+pub fn set_recovery_vault(env: &Env, vault: &RecoveryVault) {
+    env.storage().instance().set(&DataKey::RecoveryVault, vault);
+}
+
+pub fn is_evacuated(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::Evacuated)
+        .unwrap_or(false)
+}
+
+pub fn set_evacuated(env: &Env, evacuated: bool) {
+    env.storage().instance().set(&DataKey::Evacuated, &evacuated);
+}
+
+/// Instance keys captured by a configuration snapshot. Identity, accounting
+/// and recovery state (admin, token, counters, guardian, vault) is excluded,
+/// as is pause state so a rollback cannot lift a pause or an evacuation lock.
+fn config_keys(env: &Env) -> Vec<DataKey> {
+    Vec::from_array(
+        env,
+        [
+            DataKey::PlatformFeeBps,
+            DataKey::Treasury,
+            DataKey::AgentRebateBps,
+            DataKey::SlaPenaltyBps,
+            DataKey::FeeStrategy,
+            DataKey::MaxTotalLocked,
+            DataKey::ReserveBps,
+            DataKey::InsurancePremiumBps,
+            DataKey::GlobalExposureCap,
+            DataKey::ReviewThreshold,
+            DataKey::DefaultExpirySeconds,
+            DataKey::ExpiryWarningSeconds,
+            DataKey::DualConfirmTimeout,
+            DataKey::RateLimitConfig,
+            DataKey::ReferralConfig,
+            DataKey::ChallengeConfig,
+            DataKey::MaxPauseDuration,
+            DataKey::SettlementWindows,
+            DataKey::AllowedCurrencies,
+            DataKey::AllowedCountries,
+            DataKey::Features,
+            DataKey::EventVerbosity,
+        ],
+    )
+}
+
+pub fn get_config_snapshot_ids(env: &Env) -> Vec<u64> {
+    env.storage()
+        .instance()
+        .get(&DataKey::ConfigSnapshotIds)
+        .unwrap_or(Vec::new(env))
+}
+
+/// The configuration values a snapshot would capture, keyed by storage key.
+/// Unset keys are omitted.
+fn config_values(env: &Env) -> Map<DataKey, Val> {
+    let mut values: Map<DataKey, Val> = Map::new(env);
+    for key in config_keys(env).iter() {
+        if let Some(value) = env.storage().instance().get::<_, Val>(&key) {
+            values.set(key, value);
+        }
+    }
+    values
+}
+
+/// The current configuration as a single value, for comparing or hashing
+/// it as a whole.
+pub fn get_config_state(env: &Env) -> Val {
+    config_values(env).into_val(env)
+}
+
+/// Captures the current configuration under a new snapshot ID, pruning the
+/// oldest snapshot once more than `MAX_CONFIG_SNAPSHOTS` are retained.
+pub fn save_config_snapshot(env: &Env) -> u64 {
+    let values = config_values(env);
+
+    let snapshot_id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ConfigSnapshotCounter)
+        .unwrap_or(0u64)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::ConfigSnapshotCounter, &snapshot_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::ConfigSnapshot(snapshot_id), &values);
+
+    let mut ids = get_config_snapshot_ids(env);
+    ids.push_back(snapshot_id);
+    while ids.len() > MAX_CONFIG_SNAPSHOTS {
+        let pruned = ids.pop_front_unchecked();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ConfigSnapshot(pruned));
+    }
+    env.storage().instance().set(&DataKey::ConfigSnapshotIds, &ids);
+
+    snapshot_id
+}
+
+/// Writes back every configuration value captured by a snapshot and clears
+/// the keys that were unset when it was taken.
+pub fn restore_config_snapshot(env: &Env, snapshot_id: u64) -> Result<(), ContractError> {
+    let values: Map<DataKey, Val> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ConfigSnapshot(snapshot_id))
+        .ok_or(ContractError::InvalidStatus)?;
+
+    for key in config_keys(env).iter() {
+        match values.get(key.clone()) {
+            Some(value) => env.storage().instance().set(&key, &value),
+            None => env.storage().instance().remove(&key),
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether restoring `snapshot_id` would change any setting held back by the
+/// timelock: the platform fee, default treasury, fee strategy, agent rebate,
+/// insurance premium or SLA penalty.
+pub fn snapshot_changes_timelocked(env: &Env, snapshot_id: u64) -> Result<bool, ContractError> {
+    let values: Map<DataKey, Val> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ConfigSnapshot(snapshot_id))
+        .ok_or(ContractError::InvalidStatus)?;
+
+    let keys = Vec::from_array(
+        env,
+        [
+            DataKey::PlatformFeeBps,
+            DataKey::Treasury,
+            DataKey::FeeStrategy,
+            DataKey::AgentRebateBps,
+            DataKey::InsurancePremiumBps,
+            DataKey::SlaPenaltyBps,
+        ],
+    );
+    let mut restored: Map<DataKey, Val> = Map::new(env);
+    let mut current: Map<DataKey, Val> = Map::new(env);
+    for key in keys.iter() {
+        if let Some(value) = values.get(key.clone()) {
+            restored.set(key.clone(), value);
+        }
+        if let Some(value) = env.storage().instance().get::<_, Val>(&key) {
+            current.set(key, value);
+        }
+    }
+    Ok(restored != current)
+}
+
+pub fn get_fee_schedule(env: &Env, version: u32) -> Option<FeeSchedule> {
+    env.storage().persistent().get(&DataKey::FeeSchedule(version))
+}
+
+/// Appends the current platform fee, agent rebate and SLA penalty rates as
+/// a new fee schedule version, unless they match the latest version.
+pub fn archive_fee_schedule(env: &Env) -> Result<(), ContractError> {
+    let count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::FeeScheduleCount)
+        .unwrap_or(0);
+
+    let platform_fee_bps = get_platform_fee_bps(env)?;
+    let agent_rebate_bps = get_agent_rebate_bps(env);
+    let sla_penalty_bps = get_sla_penalty_bps(env);
+
+    if let Some(latest) = get_fee_schedule(env, count) {
+        if latest.platform_fee_bps == platform_fee_bps
+            && latest.agent_rebate_bps == agent_rebate_bps
+            && latest.sla_penalty_bps == sla_penalty_bps
+        {
+            return Ok(());
+        }
+    }
+
+    let version = count.checked_add(1).ok_or(ContractError::Overflow)?;
+    let schedule = FeeSchedule {
+        version,
+        platform_fee_bps,
+        agent_rebate_bps,
+        sla_penalty_bps,
+        effective_ledger: env.ledger().sequence(),
+        effective_at: env.ledger().timestamp(),
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::FeeSchedule(version), &schedule);
+    env.storage()
+        .instance()
+        .set(&DataKey::FeeScheduleCount, &version);
+    Ok(())
+}
+
+/// Latest fee schedule version that took effect at or before
+/// `ledger_sequence`, found by binary search over the archive.
+pub fn find_fee_schedule_at(env: &Env, ledger_sequence: u32) -> Option<FeeSchedule> {
+    let count: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::FeeScheduleCount)
+        .unwrap_or(0);
+
+    let mut low = 1;
+    let mut high = count;
+    let mut found = None;
+    while low <= high {
+        let mid = low + (high - low) / 2;
+        let schedule = get_fee_schedule(env, mid)?;
+        if schedule.effective_ledger <= ledger_sequence {
+            found = Some(schedule);
+            low = mid + 1;
+        } else {
+            high = mid - 1;
+        }
+    }
+    found
 }
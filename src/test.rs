@@ -1,13 +1,14 @@
 #![cfg(test)]
+extern crate std;
 
 use crate::{SwiftRemitContract, SwiftRemitContractClient};
 use soroban_sdk::{
-    symbol_short, testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events},
-    token, Address, Env, IntoVal, String, Symbol,
+    symbol_short, testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events, Ledger},
+    token, vec, Address, BytesN, Env, IntoVal, String, Symbol, Vec,
 };
 
 fn create_token_contract<'a>(env: &Env, admin: &Address) -> token::StellarAssetClient<'a> {
-    token::StellarAssetClient::new(env, &env.register_stellar_asset_contract_v2(admin.clone()))
+    token::StellarAssetClient::new(env, &env.register_stellar_asset_contract_v2(admin.clone()).address())
 }
 
 fn create_swiftremit_contract<'a>(env: &Env) -> SwiftRemitContractClient<'a> {
@@ -25,7 +26,7 @@ fn test_initialize() {
 
     let contract = create_swiftremit_contract(&env);
 
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
 
     assert_eq!(contract.get_platform_fee_bps(), 250);
 }
@@ -42,8 +43,8 @@ fn test_initialize_twice() {
 
     let contract = create_swiftremit_contract(&env);
 
-    contract.initialize(&admin, &token.address, &250);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.initialize(&admin, &token.address, &250, &None);
 }
 
 #[test]
@@ -58,7 +59,7 @@ fn test_initialize_invalid_fee() {
 
     let contract = create_swiftremit_contract(&env);
 
-    contract.initialize(&admin, &token.address, &10001);
+    contract.initialize(&admin, &token.address, &10001, &None);
 }
 
 #[test]
@@ -72,12 +73,10 @@ fn test_register_agent() {
     let agent = Address::generate(&env);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
 
     contract.register_agent(&agent);
 
-    assert!(contract.is_agent_registered(&agent));
-
     assert_eq!(
         env.auths(),
         [(
@@ -85,13 +84,15 @@ fn test_register_agent() {
             AuthorizedInvocation {
                 function: AuthorizedFunction::Contract((
                     contract.address.clone(),
-                    symbol_short!("register_agent"),
+                    Symbol::new(&env, "register_agent"),
                     (&agent,).into_val(&env)
                 )),
                 sub_invocations: std::vec![]
             }
         )]
     );
+
+    assert!(contract.is_agent_registered(&agent));
 }
 
 #[test]
@@ -105,15 +106,42 @@ fn test_remove_agent() {
     let agent = Address::generate(&env);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
 
     contract.register_agent(&agent);
     assert!(contract.is_agent_registered(&agent));
 
-    contract.remove_agent(&agent);
+    contract.remove_agent(&agent, &None);
     assert!(!contract.is_agent_registered(&agent));
 }
 
+#[test]
+fn test_remove_agent_records_reason() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let agent = Address::generate(&env);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+
+    contract.register_agent(&agent);
+    contract.remove_agent(&agent, &Some(symbol_short!("kyc_lapse")));
+
+    assert_eq!(contract.get_agent_removal_reason(&agent), Some(symbol_short!("kyc_lapse")));
+
+    let events = env.events().all();
+    let removed_event = events.last().unwrap();
+    let event_data: (crate::EventEnvelope, Address, Address, Option<Symbol>) = removed_event.2.into_val(&env);
+    assert_eq!(event_data.3, Some(symbol_short!("kyc_lapse")));
+
+    contract.register_agent(&agent);
+    assert_eq!(contract.get_agent_removal_reason(&agent), None);
+}
+
 #[test]
 fn test_update_fee() {
     let env = Env::default();
@@ -124,7 +152,7 @@ fn test_update_fee() {
     let token = create_token_contract(&env, &token_admin);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
 
     contract.update_fee(&500);
     assert_eq!(contract.get_platform_fee_bps(), 500);
@@ -141,7 +169,7 @@ fn test_update_fee_invalid() {
     let token = create_token_contract(&env, &token_admin);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
 
     contract.update_fee(&10001);
 }
@@ -160,10 +188,10 @@ fn test_create_remittance() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     assert_eq!(remittance_id, 1);
 
@@ -173,8 +201,8 @@ fn test_create_remittance() {
     assert_eq!(remittance.amount, 1000);
     assert_eq!(remittance.fee, 25);
 
-    assert_eq!(token.balance(&contract.address), 1000);
-    assert_eq!(token.balance(&sender), 9000);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&contract.address), 1000);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&sender), 9000);
 }
 
 #[test]
@@ -190,10 +218,10 @@ fn test_create_remittance_invalid_amount() {
     let agent = Address::generate(&env);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    contract.create_remittance(&sender, &agent, &0, &None);
+    contract.create_remittance(&sender, &agent, &0, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 }
 
 #[test]
@@ -211,9 +239,9 @@ fn test_create_remittance_unregistered_agent() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
 
-    contract.create_remittance(&sender, &agent, &1000, &None);
+    contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 }
 
 #[test]
@@ -230,19 +258,19 @@ fn test_confirm_payout() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     contract.confirm_payout(&remittance_id);
 
     let remittance = contract.get_remittance(&remittance_id);
     assert_eq!(remittance.status, crate::types::RemittanceStatus::Completed);
 
-    assert_eq!(token.balance(&agent), 975);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 975);
     assert_eq!(contract.get_accumulated_fees(), 25);
-    assert_eq!(token.balance(&contract.address), 25);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&contract.address), 25);
 }
 
 #[test]
@@ -260,10 +288,10 @@ fn test_confirm_payout_twice() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     contract.confirm_payout(&remittance_id);
     contract.confirm_payout(&remittance_id);
@@ -283,18 +311,18 @@ fn test_cancel_remittance() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     contract.cancel_remittance(&remittance_id);
 
     let remittance = contract.get_remittance(&remittance_id);
     assert_eq!(remittance.status, crate::types::RemittanceStatus::Cancelled);
 
-    assert_eq!(token.balance(&sender), 10000);
-    assert_eq!(token.balance(&contract.address), 0);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&sender), 10000);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&contract.address), 0);
 }
 
 #[test]
@@ -312,10 +340,10 @@ fn test_cancel_remittance_already_completed() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
     contract.confirm_payout(&remittance_id);
 
     contract.cancel_remittance(&remittance_id);
@@ -336,17 +364,17 @@ fn test_withdraw_fees() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
     contract.confirm_payout(&remittance_id);
 
-    contract.withdraw_fees(&fee_recipient);
+    contract.withdraw_fees(&admin, &fee_recipient);
 
-    assert_eq!(token.balance(&fee_recipient), 25);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&fee_recipient), 25);
     assert_eq!(contract.get_accumulated_fees(), 0);
-    assert_eq!(token.balance(&contract.address), 0);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&contract.address), 0);
 }
 
 #[test]
@@ -361,9 +389,9 @@ fn test_withdraw_fees_no_fees() {
     let fee_recipient = Address::generate(&env);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
 
-    contract.withdraw_fees(&fee_recipient);
+    contract.withdraw_fees(&admin, &fee_recipient);
 }
 
 #[test]
@@ -380,16 +408,16 @@ fn test_fee_calculation() {
     token.mint(&sender, &100000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &500);
+    contract.initialize(&admin, &token.address, &500, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &10000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &10000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     let remittance = contract.get_remittance(&remittance_id);
     assert_eq!(remittance.fee, 500);
 
     contract.confirm_payout(&remittance_id);
-    assert_eq!(token.balance(&agent), 9500);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 9500);
     assert_eq!(contract.get_accumulated_fees(), 500);
 }
 
@@ -409,11 +437,11 @@ fn test_multiple_remittances() {
     token.mint(&sender2, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id1 = contract.create_remittance(&sender1, &agent, &1000, &None);
-    let remittance_id2 = contract.create_remittance(&sender2, &agent, &2000, &None);
+    let remittance_id1 = contract.create_remittance(&sender1, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let remittance_id2 = contract.create_remittance(&sender2, &agent, &2000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     assert_eq!(remittance_id1, 1);
     assert_eq!(remittance_id2, 2);
@@ -422,7 +450,7 @@ fn test_multiple_remittances() {
     contract.confirm_payout(&remittance_id2);
 
     assert_eq!(contract.get_accumulated_fees(), 75);
-    assert_eq!(token.balance(&agent), 2925);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 2925);
 }
 
 #[test]
@@ -439,7 +467,7 @@ fn test_events_emitted() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
 
     contract.register_agent(&agent);
 
@@ -447,18 +475,18 @@ fn test_events_emitted() {
     let agent_reg_event = events.last().unwrap();
 
     assert_eq!(
-        agent_reg_event.topics,
-        (symbol_short!("agent_reg"),).into_val(&env)
+        agent_reg_event.1,
+        (symbol_short!("agent"), symbol_short!("register")).into_val(&env)
     );
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     let events = env.events().all();
     let create_event = events.last().unwrap();
 
     assert_eq!(
-        create_event.topics,
-        (symbol_short!("created"),).into_val(&env)
+        create_event.1,
+        (symbol_short!("remit"), symbol_short!("created"), None::<BytesN<32>>).into_val(&env)
     );
 
     contract.confirm_payout(&remittance_id);
@@ -467,14 +495,15 @@ fn test_events_emitted() {
     let complete_event = events.last().unwrap();
 
     assert_eq!(
-        complete_event.topics,
-        (symbol_short!("completed"),).into_val(&env)
+        complete_event.1,
+        (symbol_short!("settle"), symbol_short!("complete"), None::<BytesN<32>>).into_val(&env)
     );
 }
 
 #[test]
 fn test_authorization_enforcement() {
     let env = Env::default();
+    env.mock_all_auths();
 
     let admin = Address::generate(&env);
     let token_admin = Address::generate(&env);
@@ -487,11 +516,11 @@ fn test_authorization_enforcement() {
     let contract = create_swiftremit_contract(&env);
 
     env.mock_all_auths();
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
     env.mock_all_auths();
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     env.mock_all_auths();
     contract.confirm_payout(&remittance_id);
@@ -503,7 +532,7 @@ fn test_authorization_enforcement() {
             AuthorizedInvocation {
                 function: AuthorizedFunction::Contract((
                     contract.address.clone(),
-                    symbol_short!("confirm_payout"),
+                    Symbol::new(&env, "confirm_payout"),
                     (remittance_id,).into_val(&env)
                 )),
                 sub_invocations: std::vec![]
@@ -527,16 +556,16 @@ fn test_withdraw_fees_valid_address() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
     contract.confirm_payout(&remittance_id);
 
     // This should succeed with a valid address
-    contract.withdraw_fees(&fee_recipient);
+    contract.withdraw_fees(&admin, &fee_recipient);
 
-    assert_eq!(token.balance(&fee_recipient), 25);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&fee_recipient), 25);
     assert_eq!(contract.get_accumulated_fees(), 0);
 }
 
@@ -554,17 +583,17 @@ fn test_confirm_payout_valid_address() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     // This should succeed with a valid agent address
     contract.confirm_payout(&remittance_id);
 
     let remittance = contract.get_remittance(&remittance_id);
     assert_eq!(remittance.status, crate::types::RemittanceStatus::Completed);
-    assert_eq!(token.balance(&agent), 975);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 975);
 }
 
 #[test]
@@ -581,11 +610,11 @@ fn test_address_validation_in_settlement_flow() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
     // Create remittance with valid addresses
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
     
     // Confirm payout - should validate agent address
     contract.confirm_payout(&remittance_id);
@@ -593,7 +622,7 @@ fn test_address_validation_in_settlement_flow() {
     // Verify the settlement completed successfully
     let remittance = contract.get_remittance(&remittance_id);
     assert_eq!(remittance.status, crate::types::RemittanceStatus::Completed);
-    assert_eq!(token.balance(&agent), 975);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 975);
     assert_eq!(contract.get_accumulated_fees(), 25);
 }
 
@@ -614,20 +643,20 @@ fn test_multiple_settlements_with_address_validation() {
     token.mint(&sender2, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent1);
     contract.register_agent(&agent2);
 
     // Create and confirm multiple remittances
-    let remittance_id1 = contract.create_remittance(&sender1, &agent1, &1000, &None);
-    let remittance_id2 = contract.create_remittance(&sender2, &agent2, &2000, &None);
+    let remittance_id1 = contract.create_remittance(&sender1, &agent1, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let remittance_id2 = contract.create_remittance(&sender2, &agent2, &2000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     // Both should succeed with valid addresses
     contract.confirm_payout(&remittance_id1);
     contract.confirm_payout(&remittance_id2);
 
-    assert_eq!(token.balance(&agent1), 975);
-    assert_eq!(token.balance(&agent2), 1950);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent1), 975);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent2), 1950);
     assert_eq!(contract.get_accumulated_fees(), 75);
 }
 
@@ -645,21 +674,21 @@ fn test_settlement_with_future_expiry() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
     // Set expiry to 1 hour in the future
     let current_time = env.ledger().timestamp();
     let expiry_time = current_time + 3600;
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &Some(expiry_time));
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &Some(expiry_time), &None, &Vec::new(&env), &0, &0);
 
     // Should succeed since expiry is in the future
     contract.confirm_payout(&remittance_id);
 
     let remittance = contract.get_remittance(&remittance_id);
     assert_eq!(remittance.status, crate::types::RemittanceStatus::Completed);
-    assert_eq!(token.balance(&agent), 975);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 975);
 }
 
 #[test]
@@ -677,14 +706,15 @@ fn test_settlement_with_past_expiry() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
     // Set expiry to 1 hour in the past
+    env.ledger().set_timestamp(10_000);
     let current_time = env.ledger().timestamp();
     let expiry_time = current_time.saturating_sub(3600);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &Some(expiry_time));
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &Some(expiry_time), &None, &Vec::new(&env), &0, &0);
 
     // Should fail with SettlementExpired error
     contract.confirm_payout(&remittance_id);
@@ -704,18 +734,18 @@ fn test_settlement_without_expiry() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
     // Create remittance without expiry
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     // Should succeed since there's no expiry
     contract.confirm_payout(&remittance_id);
 
     let remittance = contract.get_remittance(&remittance_id);
     assert_eq!(remittance.status, crate::types::RemittanceStatus::Completed);
-    assert_eq!(token.balance(&agent), 975);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 975);
 }
 
 #[test]
@@ -733,10 +763,10 @@ fn test_duplicate_settlement_prevention() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     // First settlement should succeed
     contract.confirm_payout(&remittance_id);
@@ -744,7 +774,7 @@ fn test_duplicate_settlement_prevention() {
     // Verify first settlement completed
     let remittance = contract.get_remittance(&remittance_id);
     assert_eq!(remittance.status, crate::types::RemittanceStatus::Completed);
-    assert_eq!(token.balance(&agent), 975);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 975);
     assert_eq!(contract.get_accumulated_fees(), 25);
 
     // Manually reset status to Pending to bypass status check
@@ -775,12 +805,12 @@ fn test_different_settlements_allowed() {
     token.mint(&sender, &20000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
     // Create two different remittances
-    let remittance_id1 = contract.create_remittance(&sender, &agent, &1000, &None);
-    let remittance_id2 = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id1 = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let remittance_id2 = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
     // Both settlements should succeed as they are different remittances
     contract.confirm_payout(&remittance_id1);
@@ -792,7 +822,7 @@ fn test_different_settlements_allowed() {
     
     assert_eq!(remittance1.status, crate::types::RemittanceStatus::Completed);
     assert_eq!(remittance2.status, crate::types::RemittanceStatus::Completed);
-    assert_eq!(token.balance(&agent), 1950);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 1950);
     assert_eq!(contract.get_accumulated_fees(), 50);
 }
 
@@ -810,18 +840,18 @@ fn test_settlement_hash_storage_efficiency() {
     token.mint(&sender, &50000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
     // Create and settle multiple remittances
     for _ in 0..5 {
-        let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+        let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
         contract.confirm_payout(&remittance_id);
     }
 
     // Verify all settlements completed
     assert_eq!(contract.get_accumulated_fees(), 125);
-    assert_eq!(token.balance(&agent), 4875);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 4875);
     
     // Storage should only contain settlement hashes (boolean flags), not full remittance data duplicates
     // This is verified by the fact that the contract still functions correctly
@@ -841,13 +871,13 @@ fn test_duplicate_prevention_with_expiry() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
     let current_time = env.ledger().timestamp();
     let expiry_time = current_time + 3600;
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &Some(expiry_time));
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &Some(expiry_time), &None, &Vec::new(&env), &0, &0);
 
     // First settlement should succeed
     contract.confirm_payout(&remittance_id);
@@ -869,11 +899,11 @@ fn test_pause_unpause() {
     let token = create_token_contract(&env, &token_admin);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
 
     assert!(!contract.is_paused());
 
-    contract.pause();
+    contract.pause(&None, &None);
     assert!(contract.is_paused());
 
     contract.unpause();
@@ -883,7 +913,6 @@ fn test_pause_unpause() {
 #[test]
 #[should_panic(expected = "Error(Contract, #13)")]
 fn test_settlement_blocked_when_paused() {
-fn test_get_settlement_valid() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -896,32 +925,18 @@ fn test_get_settlement_valid() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
-    contract.pause();
+    contract.pause(&None, &None);
 
     contract.confirm_payout(&remittance_id);
 }
 
 #[test]
 fn test_settlement_works_after_unpause() {
-    contract.confirm_payout(&remittance_id);
-
-    let settlement = contract.get_settlement(&remittance_id);
-    assert_eq!(settlement.id, remittance_id);
-    assert_eq!(settlement.sender, sender);
-    assert_eq!(settlement.agent, agent);
-    assert_eq!(settlement.amount, 1000);
-    assert_eq!(settlement.fee, 25);
-    assert_eq!(settlement.status, crate::types::RemittanceStatus::Completed);
-}
-
-#[test]
-#[should_panic(expected = "RemittanceNotFound")]
-fn test_get_settlement_invalid_id() {
     let env = Env::default();
     env.mock_all_auths();
 
@@ -934,12 +949,12 @@ fn test_get_settlement_invalid_id() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
 
-    contract.pause();
+    contract.pause(&None, &None);
     contract.unpause();
 
     contract.confirm_payout(&remittance_id);
@@ -948,6 +963,48 @@ fn test_get_settlement_invalid_id() {
     assert_eq!(remittance.status, crate::types::RemittanceStatus::Completed);
 }
 
+#[test]
+fn test_get_settlement_valid() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&remittance_id);
+
+    let settlement = contract.get_settlement(&remittance_id);
+    assert_eq!(settlement.id, remittance_id);
+    assert_eq!(settlement.sender, sender);
+    assert_eq!(settlement.agent, agent);
+    assert_eq!(settlement.amount, 1000);
+    assert_eq!(settlement.fee, 25);
+    assert_eq!(settlement.status, crate::types::RemittanceStatus::Completed);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #6)")]
+fn test_get_settlement_invalid_id() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+
     contract.get_settlement(&999);
 }
 
@@ -965,30 +1022,31 @@ fn test_settlement_completed_event_emission() {
     token.mint(&sender, &10000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &250);
+    contract.initialize(&admin, &token.address, &250, &None);
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
     
     contract.confirm_payout(&remittance_id);
 
     // Verify SettlementCompleted event was emitted
     let events = env.events().all();
     let settlement_event = events.iter().find(|e| {
-        e.topics.get(0).unwrap() == &symbol_short!("settle") &&
-        e.topics.get(1).unwrap() == &symbol_short!("complete")
+        e.1 == (symbol_short!("settle"), symbol_short!("complete"), None::<BytesN<32>>).into_val(&env)
     });
 
     assert!(settlement_event.is_some(), "SettlementCompleted event should be emitted");
     
     let event = settlement_event.unwrap();
-    let event_data: (u32, u32, u64, Address, Address, Address, i128) = event.data.clone().try_into().unwrap();
+    let event_data: (crate::EventEnvelope, Address, Address, Address, i128, i128, i128, i128) = event.2.into_val(&env);
     
     // Verify event fields match executed settlement data
-    assert_eq!(event_data.3, sender, "Event sender should match remittance sender");
-    assert_eq!(event_data.4, agent, "Event recipient should match remittance agent");
-    assert_eq!(event_data.5, token.address, "Event token should match USDC token");
-    assert_eq!(event_data.6, 975, "Event amount should match payout amount (1000 - 25 fee)");
+    assert_eq!(event_data.1, sender, "Event sender should match remittance sender");
+    assert_eq!(event_data.2, agent, "Event recipient should match remittance agent");
+    assert_eq!(event_data.3, token.address, "Event token should match USDC token");
+    assert_eq!(event_data.4, 975, "Event amount should match payout amount (1000 - 25 fee)");
+    assert_eq!(event_data.5, 1000, "Event gross amount should match remittance amount");
+    assert_eq!(event_data.6, 25, "Event platform fee should match remittance fee");
 }
 
 #[test]
@@ -1005,29 +1063,4280 @@ fn test_settlement_completed_event_fields_accuracy() {
     token.mint(&sender, &20000);
 
     let contract = create_swiftremit_contract(&env);
-    contract.initialize(&admin, &token.address, &500); // 5% fee
+    contract.initialize(&admin, &token.address, &500, &None); // 5% fee
     contract.register_agent(&agent);
 
-    let remittance_id = contract.create_remittance(&sender, &agent, &10000, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &10000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
     
     contract.confirm_payout(&remittance_id);
 
     // Find the SettlementCompleted event
     let events = env.events().all();
     let settlement_event = events.iter().find(|e| {
-        e.topics.get(0).unwrap() == &symbol_short!("settle") &&
-        e.topics.get(1).unwrap() == &symbol_short!("complete")
+        e.1 == (symbol_short!("settle"), symbol_short!("complete"), None::<BytesN<32>>).into_val(&env)
     });
 
     assert!(settlement_event.is_some());
     
     let event = settlement_event.unwrap();
-    let event_data: (u32, u32, u64, Address, Address, Address, i128) = event.data.clone().try_into().unwrap();
+    let event_data: (crate::EventEnvelope, Address, Address, Address, i128, i128, i128, i128) = event.2.into_val(&env);
     
     // Verify all fields with different fee calculation
     let expected_payout = 10000 - 500; // 10000 - (10000 * 500 / 10000)
-    assert_eq!(event_data.3, sender);
-    assert_eq!(event_data.4, agent);
-    assert_eq!(event_data.5, token.address);
-    assert_eq!(event_data.6, expected_payout);
+    assert_eq!(event_data.1, sender);
+    assert_eq!(event_data.2, agent);
+    assert_eq!(event_data.3, token.address);
+    assert_eq!(event_data.4, expected_payout);
+    assert_eq!(event_data.5, 10000);
+    assert_eq!(event_data.6, 500);
+    assert_eq!(event_data.7, 0);
+}
+
+#[test]
+fn test_external_ref_stored_and_emitted_as_topic() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let external_ref = BytesN::from_array(&env, &[7u8; 32]);
+    let remittance_id =
+        contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &Some(external_ref.clone()), &Vec::new(&env), &0, &0);
+
+    assert_eq!(contract.get_external_ref(&remittance_id), Some(external_ref.clone()));
+
+    let events = env.events().all();
+    let create_event = events.last().unwrap();
+    assert_eq!(
+        create_event.1,
+        (symbol_short!("remit"), symbol_short!("created"), Some(external_ref.clone())).into_val(&env)
+    );
+
+    contract.confirm_payout(&remittance_id);
+
+    let events = env.events().all();
+    let complete_event = events.last().unwrap();
+    assert_eq!(
+        complete_event.1,
+        (symbol_short!("settle"), symbol_short!("complete"), Some(external_ref)).into_val(&env)
+    );
+}
+
+#[test]
+fn test_remittance_from_quote_honors_locked_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let quote = contract.create_quote(&sender, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &1000, &None);
+    assert_eq!(quote.fee, 25);
+    assert_eq!(quote.payout_amount, 975);
+    assert_eq!(quote.expires_at, env.ledger().timestamp() + crate::QUOTE_VALIDITY_SECONDS);
+
+    contract.update_fee(&500);
+
+    // The quote entry outlives the default temporary TTL for its whole window
+    env.ledger().with_mut(|li| {
+        li.sequence_number += (crate::QUOTE_VALIDITY_SECONDS / 5) as u32;
+        li.timestamp = quote.expires_at;
+    });
+
+    let remittance_id = contract.create_remittance_from_quote(&quote.id, &agent, &None, &None, &Vec::new(&env), &0, &0);
+
+    let remittance = contract.get_remittance(&remittance_id);
+    assert_eq!(remittance.sender, sender);
+    assert_eq!(remittance.amount, 1000);
+    assert_eq!(remittance.fee, 25);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&contract.address), 1000);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #15)")]
+fn test_remittance_from_expired_quote() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let quote = contract.create_quote(&sender, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &1000, &None);
+
+    env.ledger().set_timestamp(quote.expires_at + 1);
+
+    contract.create_remittance_from_quote(&quote.id, &agent, &None, &None, &Vec::new(&env), &0, &0);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #14)")]
+fn test_quote_cannot_be_redeemed_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let quote = contract.create_quote(&sender, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &1000, &None);
+
+    contract.create_remittance_from_quote(&quote.id, &agent, &None, &None, &Vec::new(&env), &0, &0);
+    contract.create_remittance_from_quote(&quote.id, &agent, &None, &None, &Vec::new(&env), &0, &0);
+}
+
+#[test]
+fn test_corridor_persisted_and_stats_tracked() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let usd = String::from_str(&env, "USD");
+    let ng = String::from_str(&env, "NG");
+    let ph = String::from_str(&env, "PH");
+
+    let remittance_id1 = contract.create_remittance(&sender, &agent, &1000, &usd, &ng, &None, &None, &Vec::new(&env), &0, &0);
+    let remittance_id2 = contract.create_remittance(&sender, &agent, &2000, &usd, &ng, &None, &None, &Vec::new(&env), &0, &0);
+    let remittance_id3 = contract.create_remittance(&sender, &agent, &500, &usd, &ph, &None, &None, &Vec::new(&env), &0, &0);
+
+    let remittance = contract.get_remittance(&remittance_id1);
+    assert_eq!(remittance.currency, usd);
+    assert_eq!(remittance.country, ng);
+
+    contract.confirm_payout(&remittance_id1);
+    contract.cancel_remittance(&remittance_id2);
+    contract.confirm_payout(&remittance_id3);
+
+    let stats = contract.get_corridor_stats(&usd, &ng);
+    assert_eq!(stats.created_count, 2);
+    assert_eq!(stats.created_volume, 3000);
+    assert_eq!(stats.completed_count, 1);
+    assert_eq!(stats.completed_volume, 1000);
+    assert_eq!(stats.cancelled_count, 1);
+
+    let stats = contract.get_corridor_stats(&usd, &ph);
+    assert_eq!(stats.created_count, 1);
+    assert_eq!(stats.completed_volume, 500);
+}
+
+#[test]
+fn test_agent_workload_tracks_pending_remittances() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id1 = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let remittance_id2 = contract.create_remittance(&sender, &agent, &2000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.create_remittance(&sender, &agent, &3000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    let workload = contract.get_agent_workload(&agent);
+    assert_eq!(workload.pending_count, 3);
+    assert_eq!(workload.pending_value, 6000);
+
+    contract.confirm_payout(&remittance_id1);
+    contract.cancel_remittance(&remittance_id2);
+
+    let workload = contract.get_agent_workload(&agent);
+    assert_eq!(workload.pending_count, 1);
+    assert_eq!(workload.pending_value, 3000);
+}
+
+#[test]
+fn test_sender_exposure_tracks_pending_value() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let other_sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+    token.mint(&other_sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    assert_eq!(contract.get_sender_exposure(&sender), 0);
+
+    let remittance_id1 = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let remittance_id2 = contract.create_remittance(&sender, &agent, &2000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.create_remittance(&other_sender, &agent, &4000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    assert_eq!(contract.get_sender_exposure(&sender), 3000);
+    assert_eq!(contract.get_sender_exposure(&other_sender), 4000);
+
+    contract.confirm_payout(&remittance_id1);
+    assert_eq!(contract.get_sender_exposure(&sender), 2000);
+
+    contract.cancel_remittance(&remittance_id2);
+    assert_eq!(contract.get_sender_exposure(&sender), 0);
+}
+
+#[test]
+fn test_exposure_caps_enforced_when_enabled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let trusted_sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+    token.mint(&trusted_sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    contract.set_global_exposure_cap(&Some(1500));
+    contract.set_sender_exposure_cap(&trusted_sender, &Some(5000));
+
+    assert_eq!(contract.get_exposure_cap(&sender), Some(1500));
+    assert_eq!(contract.get_exposure_cap(&trusted_sender), Some(5000));
+
+    // Caps are configured but not yet enforced
+    contract.create_remittance(&sender, &agent, &2000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    contract.set_exposure_caps_enabled(&true);
+    assert!(contract.are_exposure_caps_enabled());
+
+    let result = contract.try_create_remittance(&sender, &agent, &100, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::ExposureCapExceeded)));
+
+    contract.create_remittance(&trusted_sender, &agent, &4000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let result = contract.try_create_remittance(&trusted_sender, &agent, &1001, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::ExposureCapExceeded)));
+
+    contract.set_sender_exposure_cap(&trusted_sender, &None);
+    assert_eq!(contract.get_exposure_cap(&trusted_sender), Some(1500));
+}
+
+#[test]
+fn test_total_locked_ceiling() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    contract.set_max_total_locked(&Some(3000));
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &2000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_total_locked(), 2000);
+
+    let result = contract.try_create_remittance(&sender, &agent, &1001, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::TvlCapExceeded)));
+
+    contract.confirm_payout(&remittance_id);
+    assert_eq!(contract.get_total_locked(), 0);
+
+    contract.create_remittance(&sender, &agent, &3000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_total_locked(), 3000);
+}
+
+#[test]
+fn test_agent_rebate_accrues_and_is_claimable() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &500, &None);
+    contract.register_agent(&agent);
+    contract.update_agent_rebate(&2000); // 20% of the platform fee
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &10000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&remittance_id);
+
+    // Fee 500, rebate 100 to the agent, 400 retained by the platform
+    assert_eq!(contract.get_agent_rebate_balance(&agent), 100);
+    assert_eq!(contract.get_accumulated_fees(), 400);
+
+    assert_eq!(contract.claim_agent_rebate(&agent), 100);
+    assert_eq!(contract.get_agent_rebate_balance(&agent), 0);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 9500 + 100);
+
+    let result = contract.try_claim_agent_rebate(&agent);
+    assert_eq!(result, Err(Ok(crate::ContractError::NoFeesToWithdraw)));
+}
+
+#[test]
+fn test_token_fee_override() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    contract.set_token_fee_bps(&token.address, &Some(100));
+    assert_eq!(contract.get_token_fee_bps(&token.address), Some(100));
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_remittance(&remittance_id).fee, 10);
+
+    contract.set_token_fee_bps(&token.address, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_remittance(&remittance_id).fee, 25);
+
+    let result = contract.try_set_token_fee_bps(&token.address, &Some(10001));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidFeeBps)));
+}
+
+#[test]
+fn test_emergency_evacuate_to_recovery_vault() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let vault = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_guardian(&guardian);
+    contract.set_recovery_vault(&vault);
+
+    contract.create_remittance(&sender, &agent, &3000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    // Vault is still timelocked
+    let result = contract.try_emergency_evacuate(&token.address, &vault);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidRecoveryVault)));
+
+    env.ledger().set_timestamp(crate::RECOVERY_VAULT_DELAY_SECONDS);
+
+    assert_eq!(contract.emergency_evacuate(&token.address, &vault), 3000);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&vault), 3000);
+    assert!(contract.is_paused());
+    assert!(contract.is_evacuated());
+
+    let result = contract.try_unpause();
+    assert_eq!(result, Err(Ok(crate::ContractError::ContractEvacuated)));
+
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::ContractEvacuated)));
+
+    contract.resume_after_evacuation();
+    assert!(contract.is_paused());
+    contract.unpause();
+    assert!(!contract.is_paused());
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_emergency_evacuate_requires_guardian() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let vault = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+
+    contract.emergency_evacuate(&token.address, &vault);
+}
+
+#[test]
+fn test_pause_expires_and_exposes_info() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_max_pause_duration(&Some(3600));
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    env.ledger().set_timestamp(1000);
+    // Requested end is beyond the maximum, so it is clamped
+    contract.pause(&Some(symbol_short!("incident")), &Some(100_000));
+
+    let info = contract.get_pause_info().unwrap();
+    assert_eq!(info.paused_by, admin);
+    assert_eq!(info.reason, Some(symbol_short!("incident")));
+    assert_eq!(info.paused_at, 1000);
+    assert_eq!(info.pause_until, Some(4600));
+
+    let result = contract.try_confirm_payout(&remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::ContractPaused)));
+
+    env.ledger().set_timestamp(4600);
+    assert!(!contract.is_paused());
+    assert_eq!(contract.get_pause_info(), None);
+
+    contract.confirm_payout(&remittance_id);
+}
+
+#[test]
+fn test_remittance_tags_are_stored_and_counted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let tags = vec![&env, symbol_short!("payroll"), symbol_short!("promo")];
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &tags, &0, &0);
+    contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &vec![&env, symbol_short!("payroll")], &0, &0);
+
+    assert_eq!(contract.get_remittance(&remittance_id).tags, tags);
+    assert_eq!(contract.get_tag_count(&symbol_short!("payroll")), 2);
+    assert_eq!(contract.get_tag_count(&symbol_short!("promo")), 1);
+    assert_eq!(contract.get_tag_count(&symbol_short!("other")), 0);
+
+    let duplicated = vec![&env, symbol_short!("promo"), symbol_short!("promo")];
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &duplicated, &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidTags)));
+}
+
+#[test]
+fn test_net_position_between_parties() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let party_a = Address::generate(&env);
+    let party_b = Address::generate(&env);
+
+    token.mint(&party_a, &10000);
+    token.mint(&party_b, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&party_a);
+    contract.register_agent(&party_b);
+
+    let a_to_b = contract.create_remittance(&party_a, &party_b, &3000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.create_remittance(&party_b, &party_a, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    assert_eq!(contract.get_net_position(&party_a, &party_b), 2000);
+    assert_eq!(contract.get_net_position(&party_b, &party_a), -2000);
+
+    contract.cancel_remittance(&a_to_b);
+    assert_eq!(contract.get_net_position(&party_a, &party_b), -1000);
+}
+
+#[test]
+fn test_payout_requires_authorization_when_enabled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let authorizer = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_authorization_required(&true);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    let result = contract.try_confirm_payout(&remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::RemittanceNotAuthorized)));
+
+    let result = contract.try_authorize_remittance(&authorizer, &remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+
+    contract.set_role(&crate::Role::Authorizer, &authorizer, &true);
+    assert!(contract.has_role(&crate::Role::Authorizer, &authorizer));
+
+    contract.authorize_remittance(&authorizer, &remittance_id);
+    assert_eq!(contract.get_remittance_authorizer(&remittance_id), Some(authorizer.clone()));
+
+    let result = contract.try_authorize_remittance(&authorizer, &remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    contract.confirm_payout(&remittance_id);
+    assert_eq!(contract.get_remittance(&remittance_id).status, crate::RemittanceStatus::Completed);
+}
+
+#[test]
+fn test_dual_confirmation_requires_sender_until_timeout() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_dual_confirm_timeout(&3600);
+
+    env.ledger().set_timestamp(1000);
+    let confirmed_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &crate::FLAG_DUAL_CONFIRM, &0);
+    let timed_out_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &crate::FLAG_DUAL_CONFIRM, &0);
+
+    let result = contract.try_confirm_payout(&confirmed_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::SenderConfirmationRequired)));
+
+    contract.confirm_received(&confirmed_id);
+    assert!(contract.get_remittance(&confirmed_id).sender_confirmed);
+    contract.confirm_payout(&confirmed_id);
+
+    env.ledger().set_timestamp(4600);
+    contract.confirm_payout(&timed_out_id);
+    assert_eq!(contract.get_remittance(&timed_out_id).status, crate::RemittanceStatus::Completed);
+
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &(1 << 31), &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidFlags)));
+}
+
+#[test]
+fn test_default_expiry_applied_unless_opted_out() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_default_expiry_seconds(&Some(86400));
+    assert_eq!(contract.get_default_expiry_seconds(), Some(86400));
+
+    env.ledger().set_timestamp(1000);
+    let defaulted = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let explicit = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &Some(5000), &None, &Vec::new(&env), &0, &0);
+    let opted_out = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &crate::FLAG_NO_DEFAULT_EXPIRY, &0);
+
+    assert_eq!(contract.get_remittance(&defaulted).expiry, Some(87400));
+    assert_eq!(contract.get_remittance(&explicit).expiry, Some(5000));
+    assert_eq!(contract.get_remittance(&opted_out).expiry, None);
+
+    let result = contract.try_set_default_expiry_seconds(&Some(0));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAmount)));
+}
+
+#[test]
+fn test_lookup_remittance_by_settlement_hash() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    assert_eq!(contract.get_settlement_hash(&first), None);
+
+    contract.confirm_payout(&first);
+    contract.confirm_payout(&second);
+
+    let first_hash = contract.get_settlement_hash(&first).unwrap();
+    let second_hash = contract.get_settlement_hash(&second).unwrap();
+    assert_ne!(first_hash, second_hash);
+
+    assert_eq!(contract.get_remittance_by_settle_hash(&first_hash).id, first);
+    assert_eq!(contract.get_remittance_by_settle_hash(&second_hash).id, second);
+
+    let result = contract.try_get_remittance_by_settle_hash(&BytesN::from_array(&env, &[0; 32]));
+    assert_eq!(result, Err(Ok(crate::ContractError::RemittanceNotFound)));
+}
+
+#[test]
+fn test_treasurer_can_withdraw_fees() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let treasurer = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&remittance_id);
+
+    let result = contract.try_withdraw_fees(&treasurer, &fee_recipient);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+
+    contract.set_role(&crate::Role::Treasurer, &treasurer, &true);
+    contract.withdraw_fees(&treasurer, &fee_recipient);
+
+    assert_eq!(token::Client::new(&env, &token.address).balance(&fee_recipient), 25);
+
+    // Treasurer holds no other privileges
+    let result = contract.try_authorize_remittance(&treasurer, &remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+}
+
+#[test]
+fn test_rate_limit_countdowns() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_rate_limit(&Some(crate::RateLimitConfig {
+        cooldown_seconds: 60,
+        window_seconds: 3600,
+        max_per_window: 2,
+    }));
+
+    env.ledger().set_timestamp(1000);
+    assert_eq!(contract.get_cooldown_remaining(&sender), 0);
+    contract.create_remittance(&sender, &agent, &100, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_last_settlement_time(&sender), Some(1000));
+
+    env.ledger().set_timestamp(1020);
+    assert_eq!(contract.get_cooldown_remaining(&sender), 40);
+    let result = contract.try_create_remittance(&sender, &agent, &100, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::RateLimited)));
+
+    env.ledger().set_timestamp(1060);
+    assert_eq!(contract.get_cooldown_remaining(&sender), 0);
+    contract.create_remittance(&sender, &agent, &100, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    env.ledger().set_timestamp(2000);
+    assert_eq!(contract.get_cooldown_remaining(&sender), 0);
+    assert_eq!(contract.get_window_remaining(&sender), 2600);
+    let result = contract.try_create_remittance(&sender, &agent, &100, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::RateLimited)));
+
+    env.ledger().set_timestamp(4600);
+    assert_eq!(contract.get_window_remaining(&sender), 0);
+    contract.create_remittance(&sender, &agent, &100, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+}
+
+#[test]
+fn test_priority_tips_order_settlement_queue() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    // A tip can be attached at creation and topped up later
+    let third = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &50);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&sender), 10000 - 3000 - 50);
+
+    contract.add_priority_tip(&second, &10);
+    contract.add_priority_tip(&third, &5);
+    assert_eq!(contract.get_remittance(&third).priority_tip, 55);
+
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &-1);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAmount)));
+
+    assert_eq!(contract.get_settlement_queue(&1, &10), vec![&env, third, second, first]);
+
+    contract.confirm_payout(&third);
+    assert_eq!(contract.get_accumulated_fees(), 25 + 55);
+
+    contract.cancel_remittance(&second);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&sender), 10000 - 3000 - 55 + 1000);
+    assert_eq!(contract.get_settlement_queue(&1, &10), vec![&env, first]);
+}
+
+#[test]
+fn test_convert_remittance_to_milestone_escrow() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    let result = contract.try_convert_to_escrow(&remittance_id, &vec![&env, 500, 500]);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidMilestones)));
+
+    contract.convert_to_escrow(&remittance_id, &vec![&env, 600, 375]);
+
+    assert_eq!(contract.get_remittance(&remittance_id).status, crate::RemittanceStatus::Converted);
+    assert_eq!(contract.get_accumulated_fees(), 25);
+    assert_eq!(contract.get_sender_exposure(&sender), 0);
+
+    assert_eq!(contract.release_escrow_milestone(&remittance_id), 600);
+    assert_eq!(contract.get_escrow(&remittance_id).status, crate::EscrowStatus::Active);
+    assert_eq!(contract.release_escrow_milestone(&remittance_id), 375);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 975);
+
+    let escrow = contract.get_escrow(&remittance_id);
+    assert_eq!(escrow.released_count, 2);
+    assert_eq!(escrow.released_amount, 975);
+    assert_eq!(escrow.status, crate::EscrowStatus::Released);
+
+    let result = contract.try_release_escrow_milestone(&remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    let result = contract.try_confirm_payout(&remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_recovery_claims_admin_after_inactivity() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let recovery = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let agent = Address::generate(&env);
+
+    env.ledger().set_timestamp(1000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &Some(recovery.clone()));
+    assert_eq!(contract.get_admin_recovery(), Some(recovery.clone()));
+
+    let deadline = 1000 + crate::ADMIN_RECOVERY_INACTIVITY_SECONDS;
+    assert_eq!(contract.get_admin_recovery_deadline(), deadline);
+
+    // Admin activity pushes the deadline back
+    env.ledger().set_timestamp(5000);
+    contract.register_agent(&agent);
+    let deadline = 5000 + crate::ADMIN_RECOVERY_INACTIVITY_SECONDS;
+    assert_eq!(contract.get_admin_recovery_deadline(), deadline);
+
+    env.ledger().set_timestamp(deadline - 1);
+    let result = contract.try_claim_admin();
+    assert_eq!(result, Err(Ok(crate::ContractError::AdminStillActive)));
+
+    assert_eq!(contract.check_admin_recovery(), 1);
+    let warning = env.events().all().last().unwrap();
+    assert_eq!(
+        warning.1,
+        (symbol_short!("recovery"), symbol_short!("warning")).into_val(&env)
+    );
+
+    env.ledger().set_timestamp(deadline);
+    contract.claim_admin();
+
+    // The recovery address now holds admin authority
+    contract.remove_agent(&agent, &None);
+    let auths = env.auths();
+    assert_eq!(auths.last().unwrap().0, recovery);
+}
+
+#[test]
+fn test_contract_metadata() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    assert_eq!(contract.get_contract_metadata(), None);
+
+    let url_hash = BytesN::from_array(&env, &[7; 32]);
+    contract.set_contract_metadata(&String::from_str(&env, "SwiftRemit NG"), &symbol_short!("staging"), &url_hash);
+
+    let metadata = contract.get_contract_metadata().unwrap();
+    assert_eq!(metadata.name, String::from_str(&env, "SwiftRemit NG"));
+    assert_eq!(metadata.environment, symbol_short!("staging"));
+    assert_eq!(metadata.operator_url_hash, url_hash);
+}
+
+#[test]
+fn test_corridor_codes_are_normalized() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "usd"), &String::from_str(&env, "ng"), &None, &None, &Vec::new(&env), &0, &0);
+
+    let remittance = contract.get_remittance(&remittance_id);
+    assert_eq!(remittance.currency, String::from_str(&env, "USD"));
+    assert_eq!(remittance.country, String::from_str(&env, "NG"));
+    assert_eq!(contract.get_corridor_stats(&String::from_str(&env, "USD"), &String::from_str(&env, "NG")).created_count, 1);
+
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USDC"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidCurrency)));
+
+    let result = contract.try_create_quote(&sender, &String::from_str(&env, "USD"), &String::from_str(&env, "NGA"), &1000, &None);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidCountry)));
+}
+
+#[test]
+fn test_corridor_registry_restricts_creation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    contract.set_currency_allowed(&String::from_str(&env, "usd"), &true);
+    contract.set_currency_allowed(&String::from_str(&env, "USD"), &true);
+    contract.set_currency_allowed(&String::from_str(&env, "EUR"), &true);
+    contract.set_currency_allowed(&String::from_str(&env, "EUR"), &false);
+    contract.set_country_allowed(&String::from_str(&env, "NG"), &true);
+    contract.set_corridor_registry_enabled(&true);
+
+    assert_eq!(contract.get_allowed_currencies(), vec![&env, String::from_str(&env, "USD")]);
+    assert_eq!(contract.get_allowed_countries(), vec![&env, String::from_str(&env, "NG")]);
+
+    contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "EUR"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::CorridorDisabled)));
+
+    let result = contract.try_create_quote(&sender, &String::from_str(&env, "USD"), &String::from_str(&env, "GH"), &1000, &None);
+    assert_eq!(result, Err(Ok(crate::ContractError::CorridorDisabled)));
+
+    contract.set_corridor_registry_enabled(&false);
+    contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "EUR"), &String::from_str(&env, "GH"), &None, &None, &Vec::new(&env), &0, &0);
+}
+
+#[test]
+fn test_settlement_proof_recomputes_hash() {
+    use soroban_sdk::xdr::ToXdr;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    let result = contract.try_get_settlement_proof(&remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 42;
+        li.timestamp = 7000;
+    });
+    contract.confirm_payout(&remittance_id);
+
+    let proof = contract.get_settlement_proof(&remittance_id);
+    assert_eq!(proof.ledger_sequence, 42);
+    assert_eq!(proof.settled_at, 7000);
+    assert_eq!(proof.hash, contract.get_settlement_hash(&remittance_id).unwrap());
+
+    let preimage = (
+        proof.contract.clone(),
+        proof.remittance_id,
+        proof.sender.clone(),
+        proof.agent.clone(),
+        proof.amount,
+        proof.fee,
+        proof.ledger_sequence,
+    );
+    let recomputed: BytesN<32> = env.crypto().sha256(&preimage.to_xdr(&env)).into();
+    assert_eq!(recomputed, proof.hash);
+    assert_eq!(proof.contract, contract.address);
+}
+
+#[test]
+fn test_whitelisted_token_info_is_cached() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    assert_eq!(contract.get_token_info(&token.address), None);
+
+    contract.set_token_whitelisted(&token.address, &true);
+
+    let info = contract.get_token_info(&token.address).unwrap();
+    assert_eq!(info.name, token::Client::new(&env, &token.address).name());
+    assert_eq!(info.symbol, token::Client::new(&env, &token.address).symbol());
+    assert_eq!(info.decimals, 7);
+    assert_eq!(info.probed_at, env.ledger().sequence());
+
+    contract.set_token_whitelisted(&token.address, &false);
+    assert_eq!(contract.get_token_info(&token.address), None);
+}
+
+#[test]
+fn test_rate_limit_entries_expire() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_rate_limit(&Some(crate::RateLimitConfig {
+        cooldown_seconds: 600,
+        window_seconds: 3600,
+        max_per_window: 5,
+    }));
+
+    contract.create_remittance(&sender, &agent, &100, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert!(contract.get_last_settlement_time(&sender).is_some());
+
+    // Well past both the cooldown and the window, in ledgers
+    env.ledger().with_mut(|li| {
+        li.sequence_number += 1000;
+        li.timestamp += 5000;
+    });
+    assert_eq!(contract.get_last_settlement_time(&sender), None);
+    assert_eq!(contract.get_cooldown_remaining(&sender), 0);
+    assert_eq!(contract.get_window_remaining(&sender), 0);
+}
+
+#[test]
+fn test_simulate_settlement_emits_rejections() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    env.ledger().set_timestamp(1000);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &Some(2000), &None, &Vec::new(&env), &0, &0);
+
+    assert_eq!(contract.simulate_settlement(&remittance_id), None);
+
+    env.ledger().set_timestamp(3000);
+    let expected = crate::ContractError::SettlementExpired as u32;
+    assert_eq!(contract.simulate_settlement(&remittance_id), Some(expected));
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (symbol_short!("settle"), symbol_short!("rejected")).into_val(&env)
+    );
+    let data: (crate::EventEnvelope, u64, u32, String, String) = event.2.into_val(&env);
+    assert_eq!(data.1, remittance_id);
+    assert_eq!(data.2, expected);
+    assert_eq!(data.3, String::from_str(&env, "USD"));
+}
+
+#[test]
+fn test_total_settlement_and_cancellation_counters() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let third = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    contract.confirm_payout(&first);
+    contract.confirm_payout(&second);
+    contract.cancel_remittance(&third);
+
+    assert_eq!(contract.get_total_settlements_count(), 2);
+    assert_eq!(contract.get_total_cancellations_count(), 1);
+}
+
+#[test]
+fn test_batch_settle_requires_batch_settler() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let settler = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &agent, &2000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let ids = vec![&env, first, second];
+
+    let result = contract.try_batch_settle_with_netting(&settler, &ids);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+
+    contract.set_role(&crate::Role::BatchSettler, &settler, &true);
+    let batch_id = contract.batch_settle_with_netting(&settler, &ids);
+
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 975 + 1950);
+    assert_eq!(contract.get_accumulated_fees(), 75);
+    assert_eq!(contract.get_remittance(&second).status, crate::RemittanceStatus::Completed);
+
+    let batch = contract.get_batch(&batch_id);
+    assert_eq!(batch.submitter, settler);
+    assert_eq!(batch.remittance_ids, ids);
+
+    // Already settled entries are rejected
+    let result = contract.try_batch_settle_with_netting(&settler, &vec![&env, first]);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_batch_settle_or_abort_records_failed_attempt() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let cancelled = contract.create_remittance(&sender, &agent, &2000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.cancel_remittance(&cancelled);
+    assert_eq!(contract.get_batch_abort(&admin), None);
+
+    env.ledger().set_timestamp(500);
+    assert_eq!(contract.batch_settle_or_abort(&admin, &vec![&env, first, cancelled]), None);
+    assert_eq!(contract.get_remittance(&first).status, crate::RemittanceStatus::Pending);
+
+    let abort = contract.get_batch_abort(&admin).unwrap();
+    assert_eq!(abort.entry_count, 2);
+    assert_eq!(abort.failing_remittance_id, Some(cancelled));
+    assert_eq!(abort.error_code, crate::ContractError::InvalidStatus as u32);
+    assert_eq!(abort.aborted_at, 500);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(event.1, (symbol_short!("batch"), symbol_short!("aborted"), admin.clone()).into_val(&env));
+
+    // Authorization failures still revert
+    let outsider = Address::generate(&env);
+    let result = contract.try_batch_settle_or_abort(&outsider, &vec![&env, first]);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+
+    let batch_id = contract.batch_settle_or_abort(&admin, &vec![&env, first]).unwrap();
+    assert_eq!(contract.get_batch(&batch_id).remittance_ids, vec![&env, first]);
+    assert_eq!(contract.get_remittance(&first).status, crate::RemittanceStatus::Completed);
+}
+
+#[test]
+fn test_batch_settle_permissionless_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_batch_permissionless(&true);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let batch_id = contract.batch_settle_with_netting(&keeper, &vec![&env, remittance_id]);
+
+    assert_eq!(contract.get_batch(&batch_id).submitter, keeper);
+
+    let result = contract.try_batch_settle_with_netting(&keeper, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidBatch)));
+}
+
+#[test]
+fn test_batch_settle_requires_agent_approval_when_enabled() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_batch_approval_required(&true);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    contract.approve_settlement(&agent, &first);
+    assert!(contract.is_settlement_approved(&first));
+
+    let result = contract.try_batch_settle_with_netting(&admin, &vec![&env, first, second]);
+    assert_eq!(result, Err(Ok(crate::ContractError::AgentApprovalMissing)));
+
+    let result = contract.try_approve_settlement(&sender, &second);
+    assert_eq!(result, Err(Ok(crate::ContractError::AgentNotRegistered)));
+
+    contract.approve_settlement(&agent, &second);
+    contract.batch_settle_with_netting(&admin, &vec![&env, first, second]);
+    assert_eq!(contract.get_total_settlements_count(), 2);
+}
+
+#[test]
+fn test_escrow_indices_by_sender_and_recipient() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let other_agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.register_agent(&other_agent);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &other_agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let third = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    assert_eq!(contract.get_escrow_count(), 0);
+
+    contract.convert_to_escrow(&first, &vec![&env, 975]);
+    contract.convert_to_escrow(&second, &vec![&env, 975]);
+    contract.convert_to_escrow(&third, &vec![&env, 975]);
+
+    assert_eq!(contract.get_escrow_count(), 3);
+
+    let by_sender = contract.get_escrows_by_sender(&sender, &1, &10);
+    assert_eq!(by_sender.len(), 2);
+    assert_eq!(by_sender.get(0).unwrap().remittance_id, second);
+    assert_eq!(by_sender.get(1).unwrap().remittance_id, third);
+
+    let by_agent = contract.get_escrows_by_recipient(&agent, &0, &1);
+    assert_eq!(by_agent.len(), 1);
+    assert_eq!(by_agent.get(0).unwrap().remittance_id, first);
+
+    assert_eq!(contract.get_escrows_by_recipient(&other_agent, &5, &10).len(), 0);
+}
+
+#[test]
+fn test_agent_declines_remittance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let other_agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.register_agent(&other_agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    let result = contract.try_decline_remittance(&other_agent, &remittance_id, &None);
+    assert_eq!(result, Err(Ok(crate::ContractError::AgentNotRegistered)));
+
+    contract.decline_remittance(&agent, &remittance_id, &Some(symbol_short!("no_cash")));
+
+    assert_eq!(contract.get_remittance(&remittance_id).status, crate::RemittanceStatus::DeclinedByAgent);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&sender), 10000);
+    assert_eq!(contract.get_sender_exposure(&sender), 0);
+    assert_eq!(contract.get_agent_reputation(&agent).declined_count, 1);
+
+    let result = contract.try_confirm_payout(&remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_settlement_terms_fixed_at_creation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    contract.update_fee(&500);
+
+    let terms = contract.get_effective_settlement_terms(&remittance_id);
+    assert_eq!(terms.fee, 25);
+    assert_eq!(terms.payout_amount, 975);
+    assert_eq!(terms.current_fee, 50);
+
+    contract.confirm_payout(&remittance_id);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&agent), 975);
+}
+
+#[test]
+fn test_late_settlement_pays_sla_penalty_to_sender() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_corridor_sla(&String::from_str(&env, "usd"), &String::from_str(&env, "ng"), &Some(3600));
+    contract.set_sla_penalty_bps(&4000);
+
+    assert_eq!(contract.get_corridor_sla(&String::from_str(&env, "USD"), &String::from_str(&env, "NG")), Some(3600));
+
+    let on_time = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&on_time);
+    assert_eq!(contract.get_accumulated_fees(), 25);
+
+    let late = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    env.ledger().with_mut(|li| li.timestamp += 3601);
+    contract.confirm_payout(&late);
+
+    // 40% of the 25 fee goes back to the sender
+    assert_eq!(contract.get_accumulated_fees(), 25 + 15);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&sender), 10000 - 2000 + 10);
+}
+
+#[test]
+fn test_find_by_external_ref() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let ticket = BytesN::from_array(&env, &[7u8; 32]);
+    let other = BytesN::from_array(&env, &[8u8; 32]);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &Some(ticket.clone()), &Vec::new(&env), &0, &0);
+    contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &Some(other.clone()), &Vec::new(&env), &0, &0);
+    let third = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &Some(ticket.clone()), &Vec::new(&env), &0, &0);
+
+    // Another sender reusing the reference does not join this sender's list
+    let other_sender = Address::generate(&env);
+    token.mint(&other_sender, &10000);
+    let foreign = contract.create_remittance(&other_sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &Some(ticket.clone()), &Vec::new(&env), &0, &0);
+
+    assert_eq!(contract.find_by_external_ref(&sender, &ticket), vec![&env, first, third]);
+    assert_eq!(contract.find_by_external_ref(&other_sender, &ticket), vec![&env, foreign]);
+    assert_eq!(contract.find_by_external_ref(&sender, &BytesN::from_array(&env, &[9u8; 32])).len(), 0);
+}
+
+#[test]
+fn test_event_sequence_is_contiguous() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&remittance_id);
+
+    let events = env.events().all();
+    let contract_events: std::vec::Vec<_> = events.iter().filter(|e| e.0 == contract.address).collect();
+
+    let last = contract.get_event_sequence();
+    assert_eq!(last, contract_events.len() as u64);
+    for (index, event) in contract_events.iter().enumerate() {
+        let data: Vec<soroban_sdk::Val> = event.2.into_val(&env);
+        let envelope: crate::EventEnvelope = data.get_unchecked(0).into_val(&env);
+        assert_eq!(envelope.schema_version, 5);
+        assert_eq!(envelope.event_sequence, index as u64 + 1);
+    }
+}
+
+#[test]
+fn test_cancel_remittance_to_alternate_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    let result = contract.try_cancel_remittance_to(&remittance_id, &new_wallet);
+    assert_eq!(result, Err(Ok(crate::ContractError::AlternateRefundDisabled)));
+
+    contract.set_alternate_refund_enabled(&true);
+    contract.cancel_remittance_to(&remittance_id, &new_wallet);
+
+    let token_client = token::Client::new(&env, &token.address);
+    assert_eq!(token_client.balance(&new_wallet), 1000);
+    assert_eq!(token_client.balance(&sender), 9000);
+    assert_eq!(contract.get_remittance(&remittance_id).status, crate::RemittanceStatus::Cancelled);
+    assert_eq!(contract.get_total_cancellations_count(), 1);
+}
+
+#[test]
+fn test_fee_rounding_leaves_no_dust() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &333, &None);
+    contract.register_agent(&agent);
+    contract.update_agent_rebate(&3333);
+
+    for amount in [999i128, 1001, 37] {
+        let remittance_id = contract.create_remittance(&sender, &agent, &amount, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+        contract.confirm_payout(&remittance_id);
+    }
+
+    // Everything the contract still holds is owned by the treasury or the agent
+    let held = token::Client::new(&env, &token.address).balance(&contract.address);
+    assert_eq!(held, contract.get_accumulated_fees() + contract.get_agent_rebate_balance(&agent));
+}
+
+#[test]
+fn test_settlement_rejected_when_contract_is_short() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let elsewhere = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    // Simulate an accounting hole
+    token::Client::new(&env, &token.address).transfer(&contract.address, &elsewhere, &1500);
+
+    let result = contract.try_batch_settle_with_netting(&admin, &vec![&env, first, second]);
+    assert_eq!(result, Err(Ok(crate::ContractError::InsufficientContractLiquidity)));
+
+    let result = contract.try_confirm_payout(&first);
+    assert_eq!(result, Err(Ok(crate::ContractError::InsufficientContractLiquidity)));
+
+    token.mint(&contract.address, &1000);
+    contract.confirm_payout(&first);
+    assert_eq!(contract.get_remittance(&first).status, crate::RemittanceStatus::Completed);
+}
+
+#[test]
+fn test_nonce_increments_on_sender_operations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    assert_eq!(contract.get_nonce(&sender), 0);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_nonce(&sender), 1);
+
+    // A failed call leaves the nonce untouched
+    let result = contract.try_add_priority_tip(&remittance_id, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAmount)));
+    assert_eq!(contract.get_nonce(&sender), 1);
+
+    contract.cancel_remittance(&remittance_id);
+    assert_eq!(contract.get_nonce(&sender), 2);
+    assert_eq!(contract.get_nonce(&agent), 0);
+}
+
+#[test]
+fn test_batch_settlement_limited_to_windows() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let settler = Address::generate(&env);
+    let on_call = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_role(&crate::Role::BatchSettler, &settler, &true);
+    contract.set_role(&crate::Role::BatchSettler, &on_call, &true);
+    contract.set_role(&crate::Role::WindowOverride, &on_call, &true);
+
+    let bad = vec![&env, crate::SettlementWindow { start_seconds: 3600, end_seconds: 3600 }];
+    let result = contract.try_set_settlement_windows(&Some(bad));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidSettlementWindow)));
+
+    // 09:00-17:00 UTC, plus 22:00-02:00 wrapping midnight
+    contract.set_settlement_windows(&Some(vec![
+        &env,
+        crate::SettlementWindow { start_seconds: 9 * 3600, end_seconds: 17 * 3600 },
+        crate::SettlementWindow { start_seconds: 22 * 3600, end_seconds: 2 * 3600 },
+    ]));
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let third = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    env.ledger().set_timestamp(86_400 * 100 + 5 * 3600);
+    let result = contract.try_batch_settle_with_netting(&settler, &vec![&env, first]);
+    assert_eq!(result, Err(Ok(crate::ContractError::OutsideSettlementWindow)));
+
+    contract.batch_settle_with_netting(&on_call, &vec![&env, first]);
+
+    env.ledger().set_timestamp(86_400 * 100 + 3600);
+    contract.batch_settle_with_netting(&settler, &vec![&env, second]);
+
+    env.ledger().set_timestamp(86_400 * 100 + 12 * 3600);
+    contract.batch_settle_with_netting(&settler, &vec![&env, third]);
+
+    assert_eq!(contract.get_total_settlements_count(), 3);
+}
+
+#[test]
+fn test_high_value_remittance_held_for_review() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let officer = Address::generate(&env);
+
+    token.mint(&sender, &20000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_role(&crate::Role::ComplianceOfficer, &officer, &true);
+    contract.set_review_threshold(&Some(5000));
+
+    let small = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_remittance(&small).status, crate::RemittanceStatus::Pending);
+
+    let large = contract.create_remittance(&sender, &agent, &8000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let rejected = contract.create_remittance(&sender, &agent, &6000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_remittance(&large).status, crate::RemittanceStatus::PendingReview);
+
+    let result = contract.try_confirm_payout(&large);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    let result = contract.try_approve_review(&agent, &large);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+
+    env.ledger().with_mut(|li| li.timestamp += 600);
+    contract.approve_review(&officer, &large);
+    let review = contract.get_review(&large).unwrap();
+    assert!(review.approved);
+    assert_eq!(review.reviewed_at - review.requested_at, 600);
+
+    contract.confirm_payout(&large);
+    assert_eq!(contract.get_remittance(&large).status, crate::RemittanceStatus::Completed);
+
+    contract.reject_review(&officer, &rejected);
+    assert_eq!(contract.get_remittance(&rejected).status, crate::RemittanceStatus::ReviewRejected);
+    assert!(!contract.get_review(&rejected).unwrap().approved);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&sender), 20000 - 1000 - 8000);
+    assert_eq!(contract.get_sender_exposure(&sender), 1000);
+}
+
+#[test]
+fn test_settle_from_float_credits_agent_float() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+    token.mint(&agent, &500);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    // Settling from float does not draw on it, so an empty float settles
+    contract.set_settle_from_float(&agent, &true);
+    let unfunded = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&unfunded);
+    assert_eq!(contract.get_agent_float(&agent), 975);
+    contract.withdraw_float(&agent, &975);
+
+    contract.deposit_float(&agent, &500);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    contract.confirm_payout(&first);
+    contract.batch_settle_with_netting(&admin, &vec![&env, second]);
+
+    let token_client = token::Client::new(&env, &token.address);
+    assert_eq!(token_client.balance(&agent), 975);
+    assert_eq!(contract.get_agent_float(&agent), 500 + 975 * 2);
+
+    let result = contract.try_withdraw_float(&agent, &3000);
+    assert_eq!(result, Err(Ok(crate::ContractError::InsufficientAgentFloat)));
+
+    contract.withdraw_float(&agent, &2000);
+    assert_eq!(token_client.balance(&agent), 975 + 2000);
+    assert_eq!(contract.get_agent_float(&agent), 450);
+}
+
+#[test]
+fn test_closed_corridor_rejects_new_remittances_only() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let existing = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    contract.set_corridor_enabled(&String::from_str(&env, "usd"), &String::from_str(&env, "ng"), &false);
+    assert!(!contract.is_corridor_enabled(&String::from_str(&env, "USD"), &String::from_str(&env, "NG")));
+
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::CorridorDisabled)));
+
+    let result = contract.try_create_quote(&sender, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &1000, &None);
+    assert_eq!(result, Err(Ok(crate::ContractError::CorridorDisabled)));
+
+    contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "GH"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&existing);
+
+    contract.set_corridor_enabled(&String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &true);
+    contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+}
+
+#[soroban_sdk::contract]
+struct RecordingNotifier;
+
+#[soroban_sdk::contractimpl]
+impl RecordingNotifier {
+    pub fn on_remittance_update(env: Env, notice: crate::RemittanceNotice) {
+        env.storage().instance().set(&symbol_short!("last"), &notice);
+    }
+
+    pub fn last(env: Env) -> Option<crate::RemittanceNotice> {
+        env.storage().instance().get(&symbol_short!("last"))
+    }
+}
+
+mod failing_notifier {
+    use soroban_sdk::Env;
+
+    #[soroban_sdk::contract]
+    pub struct FailingNotifier;
+
+    #[soroban_sdk::contractimpl]
+    impl FailingNotifier {
+        pub fn on_remittance_update(_env: Env, _notice: crate::RemittanceNotice) {
+            panic!("notifier unavailable");
+        }
+    }
+}
+
+#[test]
+fn test_notification_contract_receives_settlement_and_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let notifier_id = env.register_contract(None, RecordingNotifier);
+    let notifier = RecordingNotifierClient::new(&env, &notifier_id);
+    contract.set_notification_contract(&sender, &Some(notifier_id.clone()));
+    assert_eq!(contract.get_notification_contract(&sender), Some(notifier_id));
+
+    let settled = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&settled);
+
+    let notice = notifier.last().unwrap();
+    assert_eq!(notice.remittance_id, settled);
+    assert_eq!(notice.status, crate::RemittanceStatus::Completed);
+    assert_eq!(notice.agent, agent);
+    assert_eq!(notice.amount, 1000);
+    assert_eq!(notice.fee, 25);
+
+    let cancelled = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.cancel_remittance(&cancelled);
+
+    let notice = notifier.last().unwrap();
+    assert_eq!(notice.remittance_id, cancelled);
+    assert_eq!(notice.status, crate::RemittanceStatus::Cancelled);
+
+    contract.set_notification_contract(&sender, &None);
+    assert_eq!(contract.get_notification_contract(&sender), None);
+}
+
+#[test]
+fn test_failing_notification_contract_does_not_block_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let notifier_id = env.register_contract(None, failing_notifier::FailingNotifier);
+    contract.set_notification_contract(&sender, &Some(notifier_id.clone()));
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&remittance_id);
+
+    let failed = env.events().all().iter().any(|event| {
+        event.0 == contract.address
+            && event.1 == (symbol_short!("notify"), symbol_short!("failed")).into_val(&env)
+    });
+    assert!(failed);
+
+    assert_eq!(contract.get_remittance(&remittance_id).status, crate::RemittanceStatus::Completed);
+    assert_eq!(token_client.balance(&agent), 975);
+}
+
+mod flat_fee_strategy {
+    use soroban_sdk::{symbol_short, Address, Env, String};
+
+    #[soroban_sdk::contract]
+    pub struct FlatFeeStrategy;
+
+    #[soroban_sdk::contractimpl]
+    impl FlatFeeStrategy {
+        pub fn set_fee(env: Env, fee: i128) {
+            env.storage().instance().set(&symbol_short!("fee"), &fee);
+        }
+
+        pub fn compute_fees(
+            env: Env,
+            _sender: Address,
+            agent: Option<Address>,
+            _amount: i128,
+            _currency: String,
+            _country: String,
+            _token: Address,
+        ) -> crate::FeeBreakdown {
+            let fee: i128 = env.storage().instance().get(&symbol_short!("fee")).unwrap();
+            // Quotes are priced without an agent and get a surcharge.
+            let fee = if agent.is_some() { fee } else { fee + 1 };
+            crate::FeeBreakdown { fee }
+        }
+    }
+}
+
+#[test]
+fn test_fee_strategy_replaces_builtin_fee() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let strategy_id = env.register_contract(None, flat_fee_strategy::FlatFeeStrategy);
+    let strategy = flat_fee_strategy::FlatFeeStrategyClient::new(&env, &strategy_id);
+    strategy.set_fee(&10);
+
+    let config = crate::FeeStrategy { contract: strategy_id.clone(), max_fee_bps: 500 };
+    contract.set_fee_strategy(&Some(config.clone()));
+    assert_eq!(contract.get_fee_strategy(), Some(config));
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_remittance(&remittance_id).fee, 10);
+    assert_eq!(contract.get_effective_settlement_terms(&remittance_id).current_fee, 10);
+
+    let quote = contract.create_quote(&sender, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &1000, &None);
+    assert_eq!(quote.fee, 11);
+    assert_eq!(quote.fee_bps, 110);
+
+    // 500 bps of 1000 is the ceiling
+    strategy.set_fee(&51);
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::FeeStrategyFailed)));
+
+    strategy.set_fee(&-1);
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::FeeStrategyFailed)));
+
+    contract.set_fee_strategy(&None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_remittance(&remittance_id).fee, 25);
+}
+
+#[test]
+fn test_broken_fee_strategy_blocks_creation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let result = contract.try_set_fee_strategy(&Some(crate::FeeStrategy { contract: token.address.clone(), max_fee_bps: 10001 }));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidFeeBps)));
+
+    // The token contract has no compute_fees entry point
+    contract.set_fee_strategy(&Some(crate::FeeStrategy { contract: token.address.clone(), max_fee_bps: 500 }));
+
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::FeeStrategyFailed)));
+    assert_eq!(token::Client::new(&env, &token.address).balance(&sender), 10000);
+}
+
+#[test]
+fn test_watch_tags_publish_status_topics() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    contract.watch(&remittance_id, &symbol_short!("acme"));
+    contract.watch(&remittance_id, &symbol_short!("acme"));
+    contract.watch(&remittance_id, &symbol_short!("globex"));
+    assert_eq!(contract.get_watch_tags(&remittance_id), vec![&env, symbol_short!("acme"), symbol_short!("globex")]);
+
+    contract.confirm_payout(&remittance_id);
+
+    let watched: std::vec::Vec<_> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| {
+            event.0 == contract.address
+                && event.1 == (symbol_short!("watch"), symbol_short!("status"), symbol_short!("acme")).into_val(&env)
+        })
+        .collect();
+    assert_eq!(watched.len(), 1);
+
+    let data: (crate::EventEnvelope, u64, crate::RemittanceStatus) = watched[0].2.clone().into_val(&env);
+    assert_eq!(data.1, remittance_id);
+    assert_eq!(data.2, crate::RemittanceStatus::Completed);
+}
+
+#[test]
+fn test_watch_tags_are_bounded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    for tag in [symbol_short!("a"), symbol_short!("b"), symbol_short!("c"), symbol_short!("d")] {
+        contract.watch(&remittance_id, &tag);
+    }
+
+    let result = contract.try_watch(&remittance_id, &symbol_short!("e"));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidTags)));
+
+    let result = contract.try_watch(&99, &symbol_short!("a"));
+    assert_eq!(result, Err(Ok(crate::ContractError::RemittanceNotFound)));
+}
+
+#[test]
+fn test_debug_check_invariants_reports_violations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let settled = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&settled);
+    let cancelled = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.cancel_remittance(&cancelled);
+    let pending = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    let report = contract.debug_check_invariants(&0, &100);
+    assert_eq!(report.checked_count, 3);
+    assert!(report.complete);
+    assert_eq!(report.violations.len(), 0);
+
+    let partial = contract.debug_check_invariants(&2, &1);
+    assert_eq!(partial.checked_count, 1);
+    assert!(!partial.complete);
+
+    env.as_contract(&contract.address, || {
+        let mut remittance = crate::get_remittance(&env, pending).unwrap();
+        remittance.fee = 2000;
+        crate::set_remittance(&env, pending, &remittance);
+    });
+
+    let report = contract.debug_check_invariants(&1, &100);
+    assert_eq!(
+        report.violations,
+        vec![&env, crate::InvariantViolation { remittance_id: pending, check: symbol_short!("amounts") }]
+    );
+}
+
+#[test]
+fn test_seed_demo_state_is_deterministic_and_consistent() {
+    let env = Env::default();
+    env.mock_all_auths_allowing_non_root_auth();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+
+    token.mint(&admin, &100000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+
+    let state = contract.seed_demo_state(&8, &3);
+    assert_eq!(state.agents.len(), 3);
+    assert_eq!(state.senders.len(), 3);
+    assert_eq!(state.remittance_ids, vec![&env, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+    let statuses: std::vec::Vec<_> = state
+        .remittance_ids
+        .iter()
+        .map(|id| contract.get_remittance(&id).status)
+        .collect();
+    assert_eq!(statuses[0], crate::RemittanceStatus::Pending);
+    assert_eq!(statuses[1], crate::RemittanceStatus::Completed);
+    assert_eq!(statuses[2], crate::RemittanceStatus::Cancelled);
+    assert_eq!(statuses[3], crate::RemittanceStatus::Converted);
+    assert_eq!(statuses[4..], statuses[..4]);
+
+    let fifth = contract.get_remittance(&5);
+    assert_eq!(fifth.amount, 1400);
+    assert_eq!(fifth.sender, state.senders.get_unchecked(1));
+    assert_eq!(fifth.agent, state.agents.get_unchecked(1));
+    assert!(contract.is_agent_registered(&state.agents.get_unchecked(2)));
+    assert_eq!(contract.get_escrow(&4).milestones.len(), 2);
+
+    let report = contract.debug_check_invariants(&1, &50);
+    assert!(report.complete);
+    assert_eq!(report.violations.len(), 0);
+
+    assert_eq!(
+        contract.try_seed_demo_state(&1, &0),
+        Err(Ok(crate::ContractError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_whitelisting_non_token_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+
+    let not_a_token = create_swiftremit_contract(&env);
+    let result = contract.try_set_token_whitelisted(&not_a_token.address, &true);
+    assert_eq!(result, Err(Ok(crate::ContractError::NotATokenContract)));
+    assert_eq!(contract.get_token_info(&not_a_token.address), None);
+
+    // Removing a whitelist entry never probes the address
+    contract.set_token_whitelisted(&not_a_token.address, &false);
+}
+
+#[test]
+fn test_event_verbosity_filters_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    assert_eq!(contract.get_event_verbosity(), crate::EventVerbosity::Verbose);
+
+    let contract_event_count = || env.events().all().iter().filter(|event| event.0 == contract.address).count();
+
+    contract.set_event_verbosity(&crate::EventVerbosity::Standard);
+    let sequence = contract.get_event_sequence();
+    let count = contract_event_count();
+
+    contract.create_quote(&sender, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &1000, &None);
+    assert_eq!(contract_event_count(), count);
+    assert_eq!(contract.get_event_sequence(), sequence);
+
+    contract.set_event_verbosity(&crate::EventVerbosity::ErrorsOnly);
+    let verbosity_event = env.events().all().last().unwrap();
+    let data: (crate::EventEnvelope, Address, crate::EventVerbosity) = verbosity_event.2.into_val(&env);
+    assert_eq!(data.0.level, crate::EventVerbosity::ErrorsOnly);
+    assert_eq!(data.2, crate::EventVerbosity::ErrorsOnly);
+    let count = contract_event_count();
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&remittance_id);
+    assert_eq!(contract_event_count(), count);
+
+    contract.simulate_settlement(&remittance_id);
+    let rejected = env.events().all().last().unwrap();
+    assert_eq!(rejected.1, (symbol_short!("settle"), symbol_short!("rejected")).into_val(&env));
+    let data: (crate::EventEnvelope, u64, u32, String, String) = rejected.2.into_val(&env);
+    assert_eq!(data.0.level, crate::EventVerbosity::ErrorsOnly);
+    assert_eq!(data.0.event_sequence, sequence + 2);
+}
+
+#[test]
+fn test_linked_remittance_settles_after_parent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let customer = Address::generate(&env);
+    let hub_agent = Address::generate(&env);
+    let last_mile_agent = Address::generate(&env);
+
+    token.mint(&customer, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&hub_agent);
+    contract.register_agent(&last_mile_agent);
+
+    let parent = contract.create_remittance(&customer, &hub_agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    token.mint(&hub_agent, &1000);
+    let child = contract.create_linked_remittance(&parent, &hub_agent, &last_mile_agent, &900, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0);
+
+    // Only the parent's sender or its hub agent may link under it
+    let stranger = Address::generate(&env);
+    token.mint(&stranger, &1000);
+    let result = contract.try_create_linked_remittance(&parent, &stranger, &last_mile_agent, &900, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAddress)));
+
+    assert_eq!(contract.get_remittance_children(&parent), vec![&env, child]);
+    assert_eq!(contract.get_remittance_parent(&child), Some(parent));
+    assert_eq!(contract.get_remittance_parent(&parent), None);
+
+    let result = contract.try_confirm_payout(&child);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    assert_eq!(contract.simulate_settlement(&child), Some(crate::ContractError::InvalidStatus as u32));
+
+    contract.confirm_payout(&parent);
+    contract.confirm_payout(&child);
+    assert_eq!(contract.get_remittance(&child).status, crate::RemittanceStatus::Completed);
+}
+
+#[test]
+fn test_linked_remittance_requires_live_parent() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let parent = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.cancel_remittance(&parent);
+
+    let result = contract.try_create_linked_remittance(&parent, &sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    let result = contract.try_create_linked_remittance(&99, &sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::RemittanceNotFound)));
+    assert_eq!(contract.get_remittance_children(&parent).len(), 0);
+}
+
+#[test]
+fn test_fx_lock_is_recorded_on_settlement() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    assert_eq!(
+        contract.try_lock_fx_rate(&remittance_id, &0, &15_000_000_000),
+        Err(Ok(crate::ContractError::InvalidAmount))
+    );
+
+    // The fiat amount must match the payout converted at the rate
+    assert_eq!(
+        contract.try_lock_fx_rate(&remittance_id, &1_500_000, &15_000_000_000),
+        Err(Ok(crate::ContractError::InvalidAmount))
+    );
+
+    // 975 token payout at 1500 fiat minor units per token unit
+    contract.lock_fx_rate(&remittance_id, &1_462_500, &15_000_000_000);
+    assert_eq!(contract.get_remittance(&remittance_id).fiat_amount, Some(1_462_500));
+    assert_eq!(
+        contract.try_lock_fx_rate(&remittance_id, &1_462_500, &15_000_000_000),
+        Err(Ok(crate::ContractError::InvalidStatus))
+    );
+
+    contract.confirm_payout(&remittance_id);
+
+    let proof = contract.get_settlement_proof(&remittance_id);
+    assert_eq!(proof.fx_destination_amount, 1_462_500);
+    assert_eq!(proof.fx_rate, 15_000_000_000);
+    assert_eq!(proof.fx_rate * 975 / crate::FX_RATE_SCALE, proof.fx_destination_amount);
+    assert_eq!(contract.get_fx_lock(&remittance_id).unwrap().rate, proof.fx_rate);
+
+    let event = env
+        .events()
+        .all()
+        .iter()
+        .find(|event| event.1 == (symbol_short!("settle"), symbol_short!("fx")).into_val(&env))
+        .unwrap();
+    let data: (crate::EventEnvelope, u64, Address, i128, String, i128, i128) = event.2.into_val(&env);
+    assert_eq!(data.1, remittance_id);
+    assert_eq!(data.3, 975);
+    assert_eq!(data.4, String::from_str(&env, "USD"));
+    assert_eq!(data.5, 1_462_500);
+
+    let completed = env
+        .events()
+        .all()
+        .iter()
+        .find(|event| event.1 == (symbol_short!("remit"), symbol_short!("complete"), None::<BytesN<32>>).into_val(&env))
+        .unwrap();
+    let data: (crate::EventEnvelope, u64, Address, Address, Address, i128, String, String, Option<i128>) = completed.2.into_val(&env);
+    assert_eq!((data.5, data.8), (975, Some(1_462_500)));
+
+    let unlocked = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&unlocked);
+    assert_eq!(contract.get_settlement_proof(&unlocked).fx_rate, 0);
+    assert_eq!(contract.get_remittance(&unlocked).fiat_amount, None);
+}
+
+#[test]
+fn test_sender_allowlist_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let member = Address::generate(&env);
+    let other_member = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&member, &10000);
+    token.mint(&outsider, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    assert!(!contract.is_sender_allowlist_enabled());
+
+    contract.create_remittance(&outsider, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    contract.set_roles(&crate::Role::Sender, &vec![&env, member.clone(), other_member.clone(), member.clone()], &true);
+    assert_eq!(contract.get_role_members(&crate::Role::Sender, &0, &10), vec![&env, member.clone(), other_member.clone()]);
+    assert_eq!(contract.get_role_members(&crate::Role::Sender, &1, &10), vec![&env, other_member.clone()]);
+    assert_eq!(contract.get_role_members(&crate::Role::Sender, &5, &10).len(), 0);
+
+    contract.set_sender_allowlist_enabled(&true);
+
+    let result = contract.try_create_remittance(&outsider, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+    contract.create_remittance(&member, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    contract.set_roles(&crate::Role::Sender, &vec![&env, member.clone()], &false);
+    assert_eq!(contract.get_role_members(&crate::Role::Sender, &0, &10), vec![&env, other_member]);
+    let result = contract.try_create_remittance(&member, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+
+    let result = contract.try_set_roles(&crate::Role::Sender, &Vec::new(&env), &true);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidBatch)));
+}
+
+#[test]
+fn test_config_snapshot_and_rollback() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+
+    let snapshot_id = contract.snapshot_config();
+    assert_eq!(snapshot_id, 1);
+
+    contract.update_fee(&500);
+    contract.set_max_total_locked(&Some(5000));
+    contract.set_sender_allowlist_enabled(&true);
+    contract.pause(&None, &None);
+
+    contract.rollback_config(&snapshot_id);
+    assert_eq!(contract.get_platform_fee_bps(), 250);
+    assert_eq!(contract.get_max_total_locked(), None);
+    assert!(!contract.is_sender_allowlist_enabled());
+    // Pause state is not part of the snapshot
+    assert!(contract.is_paused());
+
+    for _ in 0..crate::MAX_CONFIG_SNAPSHOTS {
+        contract.snapshot_config();
+    }
+    let ids = contract.get_config_snapshot_ids();
+    assert_eq!(ids.len(), crate::MAX_CONFIG_SNAPSHOTS);
+    assert_eq!(ids.first_unchecked(), 2);
+
+    let result = contract.try_rollback_config(&snapshot_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_config_changed_events() {
+    use soroban_sdk::xdr::ToXdr;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+
+    contract.update_fee(&500);
+    contract.update_fee(&500);
+    contract.set_sender_exposure_cap(&sender, &Some(1000));
+
+    let changes = |field: &str| -> std::vec::Vec<(crate::EventEnvelope, Address, BytesN<32>, BytesN<32>)> {
+        env.events()
+            .all()
+            .iter()
+            .filter(|event| event.0 == contract.address && event.1 == (symbol_short!("config"), symbol_short!("changed"), Symbol::new(&env, field)).into_val(&env))
+            .map(|event| event.2.into_val(&env))
+            .collect()
+    };
+
+    // The repeated update leaves the fee unchanged, so only one event.
+    let fee = changes("platform_fee_bps");
+    assert_eq!(fee.len(), 1);
+    let old_hash: BytesN<32> = env.crypto().sha256(&250u32.to_xdr(&env)).into();
+    let new_hash: BytesN<32> = env.crypto().sha256(&500u32.to_xdr(&env)).into();
+    assert_eq!((fee[0].1.clone(), fee[0].2.clone(), fee[0].3.clone()), (admin.clone(), old_hash, new_hash));
+
+    let cap = changes("sender_exposure_cap");
+    assert_eq!(cap.len(), 1);
+    let new_hash: BytesN<32> = env.crypto().sha256(&(sender.clone(), Some(1000i128)).to_xdr(&env)).into();
+    assert_eq!(cap[0].3, new_hash);
+
+    // Role grants and revocations are audited; a no-op grant is not
+    contract.set_role(&crate::Role::Treasurer, &sender, &true);
+    contract.set_roles(&crate::Role::Treasurer, &Vec::from_array(&env, [sender.clone()]), &true);
+    contract.set_roles(&crate::Role::Treasurer, &Vec::from_array(&env, [sender.clone()]), &false);
+    let roles = changes("role");
+    assert_eq!(roles.len(), 2);
+    let granted: BytesN<32> = env.crypto().sha256(&(crate::Role::Treasurer, sender.clone(), true).to_xdr(&env)).into();
+    assert_eq!((roles[0].3.clone(), roles[1].2.clone()), (granted.clone(), granted));
+
+    // So is the recovery address taking over the admin role
+    let recovery = Address::generate(&env);
+    contract.set_admin_recovery(&Some(recovery.clone()));
+    env.ledger().set_timestamp(env.ledger().timestamp() + crate::ADMIN_RECOVERY_INACTIVITY_SECONDS);
+    contract.claim_admin();
+    let takeover = changes("admin");
+    assert_eq!(takeover.len(), 1);
+    let new_hash: BytesN<32> = env.crypto().sha256(&recovery.clone().to_xdr(&env)).into();
+    assert_eq!((takeover[0].1.clone(), takeover[0].3.clone()), (recovery, new_hash));
+}
+
+#[test]
+fn test_confirm_payout_with_sig() {
+    use ed25519_dalek::{Signer, SigningKey};
+    use soroban_sdk::xdr::ToXdr;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    contract.set_agent_signing_key(&agent, &Some(public_key));
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    env.ledger().set_timestamp(1_000);
+    let sign = |approval: &crate::PayoutApproval| -> BytesN<64> {
+        let message = approval.clone().to_xdr(&env);
+        let mut bytes = [0u8; 256];
+        let len = message.len() as usize;
+        message.copy_into_slice(&mut bytes[..len]);
+        BytesN::from_array(&env, &signing_key.sign(&bytes[..len]).to_bytes())
+    };
+    let approval = |remittance_id: u64, nonce: u64, deadline: u64| crate::PayoutApproval {
+        contract: contract.address.clone(),
+        remittance_id,
+        nonce,
+        deadline,
+    };
+
+    let expired = approval(first, 5, 999);
+    let result = contract.try_confirm_payout_with_sig(&first, &expired, &sign(&expired));
+    assert_eq!(result, Err(Ok(crate::ContractError::SettlementExpired)));
+
+    let mismatched = approval(second, 5, 2_000);
+    let result = contract.try_confirm_payout_with_sig(&first, &mismatched, &sign(&mismatched));
+    assert_eq!(result, Err(Ok(crate::ContractError::AgentApprovalMissing)));
+
+    let valid = approval(first, 5, 2_000);
+    contract.confirm_payout_with_sig(&first, &valid, &sign(&valid));
+    assert_eq!(contract.get_remittance(&first).status, crate::RemittanceStatus::Completed);
+    assert_eq!(token_client.balance(&agent), 975);
+
+    // Nonces may be used out of order but never twice.
+    let replayed = approval(second, 5, 2_000);
+    let result = contract.try_confirm_payout_with_sig(&second, &replayed, &sign(&replayed));
+    assert_eq!(result, Err(Ok(crate::ContractError::DuplicateSettlement)));
+
+    let forged = approval(second, 3, 2_000);
+    assert!(contract.try_confirm_payout_with_sig(&second, &forged, &sign(&valid)).is_err());
+
+    contract.confirm_payout_with_sig(&second, &forged, &sign(&forged));
+    assert_eq!(contract.get_remittance(&second).status, crate::RemittanceStatus::Completed);
+}
+
+#[test]
+fn test_confirm_payout_with_session_key() {
+    use ed25519_dalek::{Signer, SigningKey};
+    use soroban_sdk::xdr::ToXdr;
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let session_signer = SigningKey::from_bytes(&[9u8; 32]);
+    let session_key = BytesN::from_array(&env, &session_signer.verifying_key().to_bytes());
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    env.ledger().set_timestamp(1_000);
+    let result = contract.try_register_session_key(&agent, &session_key, &1500, &1_000);
+    assert_eq!(result, Err(Ok(crate::ContractError::SessionKeyExpired)));
+    contract.register_session_key(&agent, &session_key, &1500, &3_000);
+
+    let sign = |approval: &crate::PayoutApproval| -> BytesN<64> {
+        let message = approval.clone().to_xdr(&env);
+        let mut bytes = [0u8; 256];
+        let len = message.len() as usize;
+        message.copy_into_slice(&mut bytes[..len]);
+        BytesN::from_array(&env, &session_signer.sign(&bytes[..len]).to_bytes())
+    };
+    let approval = |remittance_id: u64, nonce: u64| crate::PayoutApproval {
+        contract: contract.address.clone(),
+        remittance_id,
+        nonce,
+        deadline: 5_000,
+    };
+
+    let valid = approval(first, 1);
+    contract.confirm_payout_with_session(&first, &session_key, &valid, &sign(&valid));
+    assert_eq!(token_client.balance(&agent), 975);
+    assert_eq!(contract.get_session_key(&agent, &session_key).unwrap().spent, 975);
+
+    // A second payout would take the session past its cap.
+    let over_cap = approval(second, 2);
+    let result = contract.try_confirm_payout_with_session(&second, &session_key, &over_cap, &sign(&over_cap));
+    assert_eq!(result, Err(Ok(crate::ContractError::SessionCapExceeded)));
+
+    contract.register_session_key(&agent, &session_key, &5000, &3_000);
+    env.ledger().set_timestamp(3_001);
+    let result = contract.try_confirm_payout_with_session(&second, &session_key, &over_cap, &sign(&over_cap));
+    assert_eq!(result, Err(Ok(crate::ContractError::SessionKeyExpired)));
+
+    contract.revoke_session_key(&agent, &session_key);
+    assert_eq!(contract.get_session_key(&agent, &session_key), None);
+    let result = contract.try_confirm_payout_with_session(&second, &session_key, &over_cap, &sign(&over_cap));
+    assert_eq!(result, Err(Ok(crate::ContractError::AgentApprovalMissing)));
+}
+
+#[test]
+fn test_expiring_soon_warnings() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_expiry_warning_seconds(&Some(600));
+
+    env.ledger().set_timestamp(1_000);
+    let soon = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &Some(2_000), &None, &Vec::new(&env), &0, &0);
+    let later = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &Some(5_000), &None, &Vec::new(&env), &0, &0);
+    contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    let warnings = || -> std::vec::Vec<(crate::EventEnvelope, u64, Address, Address, u64, u64)> {
+        env.events()
+            .all()
+            .iter()
+            .filter(|event| event.0 == contract.address && event.1 == (symbol_short!("remit"), symbol_short!("expiring")).into_val(&env))
+            .map(|event| event.2.into_val(&env))
+            .collect()
+    };
+
+    env.ledger().set_timestamp(1_200);
+    assert_eq!(contract.scan_expiring(&0, &10).warned, 0);
+
+    env.ledger().set_timestamp(1_500);
+    let scan = contract.scan_expiring(&0, &1);
+    assert_eq!((scan.warned, scan.next_start_id), (1, Some(soon + 1)));
+    let warned = warnings();
+    assert_eq!(warned.len(), 1);
+    assert_eq!((warned[0].1, warned[0].4, warned[0].5), (soon, 2_000, 500));
+
+    // Each remittance is only warned about once.
+    let scan = contract.scan_expiring(&0, &10);
+    assert_eq!((scan.warned, scan.next_start_id), (0, None));
+
+    // Updating a remittance inside its window also warns.
+    env.ledger().set_timestamp(4_600);
+    contract.add_priority_tip(&later, &10);
+    let warned = warnings();
+    assert_eq!(warned.len(), 2);
+    assert_eq!((warned[1].1, warned[1].5), (later, 400));
+}
+
+#[test]
+fn test_batch_fee_on_net_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let party_a = Address::generate(&env);
+    let party_b = Address::generate(&env);
+
+    token.mint(&party_a, &10000);
+    token.mint(&party_b, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&party_a);
+    contract.register_agent(&party_b);
+
+    let create = |sender: &Address, agent: &Address, amount: i128| contract.create_remittance(sender, agent, &amount, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    // Gross mode: opposing flows each pay their full fee.
+    let ids = vec![&env, create(&party_a, &party_b, 1000), create(&party_b, &party_a, 600)];
+    let batch_id = contract.batch_settle_with_netting(&admin, &ids);
+    let batch = contract.get_batch(&batch_id);
+    assert_eq!((batch.fee_mode, batch.fee_discount), (crate::NettingFeeMode::Gross, 0));
+    assert_eq!(contract.get_accumulated_fees(), 25 + 15);
+
+    contract.set_corridor_fee_mode(&String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &crate::NettingFeeMode::Net);
+
+    // Net mode: fees scale by net / gross = 400 / 1600.
+    let a_to_b = create(&party_a, &party_b, 1000);
+    let b_to_a = create(&party_b, &party_a, 600);
+    let balance_a = token_client.balance(&party_a);
+    let balance_b = token_client.balance(&party_b);
+    let batch_id = contract.batch_settle_with_netting(&admin, &vec![&env, a_to_b, b_to_a]);
+    let batch = contract.get_batch(&batch_id);
+    assert_eq!(batch.fee_mode, crate::NettingFeeMode::Net);
+    assert_eq!(batch.fee_discount, (25 - 6) + (15 - 3));
+    assert_eq!(contract.get_remittance(&a_to_b).fee, 6);
+    assert_eq!(contract.get_remittance(&b_to_a).fee, 3);
+    assert_eq!(token_client.balance(&party_b) - balance_b, 994);
+    assert_eq!(token_client.balance(&party_a) - balance_a, 597);
+    assert_eq!(contract.get_accumulated_fees(), 40 + 6 + 3);
+
+    // One-directional flows are charged in full even on a Net corridor.
+    let batch_id = contract.batch_settle_with_netting(&admin, &vec![&env, create(&party_a, &party_b, 1000)]);
+    assert_eq!(contract.get_batch(&batch_id).fee_mode, crate::NettingFeeMode::Gross);
+}
+
+#[test]
+fn test_payout_route_with_memo() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let exchange = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let too_long = crate::PayoutRoute { destination: exchange.clone(), memo: String::from_str(&env, "12345678901234567890123456789") };
+    let result = contract.try_set_payout_route(&agent, &Some(too_long));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAddress)));
+
+    let route = crate::PayoutRoute { destination: exchange.clone(), memo: String::from_str(&env, "1048576") };
+    contract.set_payout_route(&agent, &Some(route.clone()));
+    assert_eq!(contract.get_payout_route(&agent), Some(route));
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&remittance_id);
+    assert_eq!(token_client.balance(&exchange), 975);
+    assert_eq!(token_client.balance(&agent), 0);
+
+    let routed: std::vec::Vec<(crate::EventEnvelope, Address, String, Address, i128)> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| event.0 == contract.address && event.1 == (symbol_short!("settle"), symbol_short!("routed"), exchange.clone()).into_val(&env))
+        .map(|event| event.2.into_val(&env))
+        .collect();
+    assert_eq!(routed.len(), 1);
+    assert_eq!((routed[0].1.clone(), routed[0].2.clone(), routed[0].4), (agent.clone(), String::from_str(&env, "1048576"), 975));
+
+    contract.set_payout_route(&agent, &None);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&remittance_id);
+    assert_eq!(token_client.balance(&agent), 975);
+}
+
+#[test]
+fn test_export_remittance_page() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    env.ledger().set_timestamp(1_000);
+    let settled = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &Some(5_000), &None, &Vec::new(&env), &0, &0);
+    contract.create_remittance(&sender, &agent, &2000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.create_remittance(&sender, &agent, &3000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    env.ledger().set_timestamp(1_500);
+    contract.confirm_payout(&settled);
+
+    let page = contract.export_remittance_page(&0, &2, &None);
+    assert_eq!(page.rows.len(), 2);
+    assert_eq!(page.next_start_id, Some(3));
+    let row = page.rows.get_unchecked(0);
+    assert_eq!((row.id, row.amount, row.fee, row.payout_amount), (settled, 1000, 25, 975));
+    assert_eq!((row.created_at, row.expiry, row.settled_at), (1_000, 5_000, 1_500));
+    assert_eq!(row.status, crate::RemittanceStatus::Completed);
+
+    let page = contract.export_remittance_page(&3, &2, &None);
+    assert_eq!(page.rows.len(), 1);
+    assert_eq!(page.next_start_id, None);
+
+    let pending = contract.export_remittance_page(&0, &10, &Some(crate::RemittanceStatus::Pending));
+    assert_eq!(pending.rows.len(), 2);
+    assert_eq!(pending.rows.get_unchecked(0).settled_at, 0);
+    assert_eq!(pending.rows.get_unchecked(0).expiry, 0);
+}
+
+#[test]
+fn test_escrow_counter_offer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let transfer_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.convert_to_escrow(&transfer_id, &vec![&env, 475, 500]);
+    contract.release_escrow_milestone(&transfer_id);
+
+    let result = contract.try_accept_settlement_amount(&transfer_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    let result = contract.try_propose_settlement_amount(&transfer_id, &500);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAmount)));
+
+    contract.propose_settlement_amount(&transfer_id, &300);
+    assert_eq!(contract.get_escrow(&transfer_id).proposed_amount, Some(300));
+
+    let sender_before = token_client.balance(&sender);
+    let refund = contract.accept_settlement_amount(&transfer_id);
+    assert_eq!(refund, 200);
+    assert_eq!(token_client.balance(&sender) - sender_before, 200);
+    assert_eq!(token_client.balance(&agent), 475 + 300);
+
+    let escrow = contract.get_escrow(&transfer_id);
+    assert_eq!(escrow.status, crate::EscrowStatus::Settled);
+    assert_eq!((escrow.released_amount, escrow.proposed_amount), (775, None));
+
+    let result = contract.try_release_escrow_milestone(&transfer_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_failed_token_transfer_returns_error() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &500);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::TokenTransferFailed)));
+    assert_eq!(token_client.balance(&sender), 500);
+    assert_eq!(token_client.balance(&contract.address), 0);
+
+    token.mint(&sender, &500);
+    contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(token_client.balance(&contract.address), 1000);
+}
+
+#[test]
+fn test_private_mode_restricts_detail_reads() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let auditor = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_role(&crate::Role::Auditor, &auditor, &true);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_remittance_as(&outsider, &remittance_id).id, remittance_id);
+
+    contract.set_private_mode(&true);
+    assert!(contract.is_private_mode());
+
+    let result = contract.try_get_remittance(&remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+    let result = contract.try_export_remittance_page(&0, &10, &None);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+    let result = contract.try_get_remittance_as(&outsider, &remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+    let result = contract.try_export_remittance_page_as(&outsider, &0, &10, &None);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+
+    // Every other detail and book getter is gated before its lookup
+    let missing_role = Some(Ok(crate::ContractError::MissingRole));
+    assert_eq!(contract.try_get_settlement(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_external_ref(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_find_by_external_ref(&sender, &BytesN::from_array(&env, &[1u8; 32])).err(), missing_role);
+    assert_eq!(contract.try_get_batch(&1).err(), missing_role);
+    assert_eq!(contract.try_get_settlement_queue(&1, &10).err(), missing_role);
+    assert_eq!(contract.try_get_escrow(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_escrows_by_sender(&sender, &0, &10).err(), missing_role);
+    assert_eq!(contract.try_get_escrows_by_recipient(&agent, &0, &10).err(), missing_role);
+    assert_eq!(contract.try_get_escrow_dispute_evidence(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_fx_lock(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_held_payout(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_review(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_effective_settlement_terms(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_settlement_hash(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_watch_tags(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_remittance_children(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_remittance_parent(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_sender_exposure(&sender).err(), missing_role);
+    assert_eq!(contract.try_get_insurance_claim(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_settlement_challenge(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_is_settlement_final(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_is_settlement_approved(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_sender_remittance_count(&sender).err(), missing_role);
+    assert_eq!(contract.try_get_agent_workload(&agent).err(), missing_role);
+    assert_eq!(contract.try_get_net_position(&sender, &agent).err(), missing_role);
+    assert_eq!(contract.try_get_remittance_authorizer(&remittance_id).err(), missing_role);
+    assert_eq!(contract.try_get_batch_abort(&outsider).err(), missing_role);
+
+    assert_eq!(contract.get_remittance_as(&sender, &remittance_id).id, remittance_id);
+    assert_eq!(contract.get_remittance_as(&agent, &remittance_id).id, remittance_id);
+    assert_eq!(contract.get_remittance_as(&auditor, &remittance_id).id, remittance_id);
+    assert_eq!(contract.export_remittance_page_as(&auditor, &0, &10, &None).rows.len(), 1);
+    let stats = contract.get_corridor_stats(&String::from_str(&env, "USD"), &String::from_str(&env, "NG"));
+    assert_eq!(stats.created_volume, 1000);
+}
+
+#[test]
+fn test_instant_corridor_chargeback_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_role(&crate::Role::Arbitrator, &arbitrator, &true);
+    contract.set_corridor_instant(&String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &Some(600));
+
+    env.ledger().set_timestamp(1_000);
+    // Dual confirmation is not awaited in an instant corridor
+    let released = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &crate::FLAG_DUAL_CONFIRM, &0);
+    let disputed = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&released);
+    contract.confirm_payout(&disputed);
+
+    assert_eq!(contract.get_remittance(&released).status, crate::RemittanceStatus::Completed);
+    assert_eq!(token_client.balance(&agent), 0);
+    let held = contract.get_held_payout(&released).unwrap();
+    assert_eq!((held.amount, held.release_at, held.disputed), (975, 1_600, false));
+
+    let result = contract.try_release_matured(&released);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    contract.open_chargeback(&disputed);
+    let result = contract.try_open_chargeback(&disputed);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    env.ledger().set_timestamp(1_600);
+    let result = contract.try_open_chargeback(&released);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    contract.release_matured(&released);
+    assert_eq!(token_client.balance(&agent), 975);
+    assert_eq!(contract.get_held_payout(&released), None);
+
+    // A disputed payout only moves on a ruling
+    let result = contract.try_release_matured(&disputed);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    let sender_before = token_client.balance(&sender);
+    contract.resolve_chargeback(&arbitrator, &disputed, &true);
+    assert_eq!(token_client.balance(&sender) - sender_before, 975);
+    assert_eq!(token_client.balance(&agent), 975);
+    assert_eq!(contract.get_held_payout(&disputed), None);
+
+    // A held Pull mode payout goes to the recipient, not the agent's float
+    let recipient = Address::generate(&env);
+    contract.set_settle_from_float(&agent, &true);
+    let pulled = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.set_payout_recipient(&pulled, &Some(recipient.clone()));
+    contract.claim_payout(&pulled);
+    assert_eq!(contract.get_held_payout(&pulled).unwrap().payee, recipient);
+    env.ledger().set_timestamp(2_200);
+    contract.release_matured(&pulled);
+    assert_eq!(token_client.balance(&recipient), 975);
+    assert_eq!(contract.get_agent_float(&agent), 0);
+}
+
+#[test]
+fn test_epoch_fee_reports() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.update_agent_rebate(&2000);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&first);
+
+    let result = contract.try_advance_epoch(&keeper);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+    contract.set_role(&crate::Role::EpochKeeper, &keeper, &true);
+
+    env.ledger().set_timestamp(5_000);
+    assert_eq!(contract.advance_epoch(&keeper), 1);
+    assert_eq!(contract.get_current_epoch(), 1);
+
+    let second = contract.create_remittance(&sender, &agent, &2000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&second);
+
+    let closed = contract.get_epoch_report(&0);
+    assert_eq!((closed.started_at, closed.closed_at, closed.remittances), (0, 5_000, 1));
+    assert_eq!((closed.fees, closed.protocol_fees, closed.rebates), (25, 20, 5));
+
+    let current = contract.get_epoch_report(&1);
+    assert_eq!((current.started_at, current.closed_at, current.remittances), (5_000, 0, 1));
+    assert_eq!((current.fees, current.protocol_fees, current.rebates), (50, 40, 10));
+    assert_eq!(contract.get_accumulated_fees(), closed.protocol_fees + current.protocol_fees);
+
+    let result = contract.try_get_epoch_report(&2);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_corridor_receive_limit_rolls() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let usd = String::from_str(&env, "USD");
+    let ng = String::from_str(&env, "NG");
+    let result = contract.try_set_corridor_receive_limit(&usd, &ng, &Some(0));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAmount)));
+    contract.set_corridor_receive_limit(&usd, &ng, &Some(2000));
+
+    let send = |corridor_country: &String| contract.create_remittance(&sender, &agent, &1000, &usd, corridor_country, &None, &None, &Vec::new(&env), &0, &0);
+
+    contract.confirm_payout(&send(&ng));
+    env.ledger().set_timestamp(crate::SECONDS_PER_DAY);
+    contract.confirm_payout(&send(&ng));
+    assert_eq!(contract.get_agent_receive_usage(&agent, &usd, &ng), 1950);
+
+    let blocked = send(&ng);
+    let result = contract.try_confirm_payout(&blocked);
+    assert_eq!(result, Err(Ok(crate::ContractError::ExposureCapExceeded)));
+
+    // Other corridors are not limited
+    contract.confirm_payout(&send(&String::from_str(&env, "KE")));
+
+    // The first day's payout leaves the window after 30 days
+    env.ledger().set_timestamp(crate::RECEIVE_LIMIT_WINDOW_DAYS * crate::SECONDS_PER_DAY);
+    assert_eq!(contract.get_agent_receive_usage(&agent, &usd, &ng), 975);
+    contract.confirm_payout(&blocked);
+    assert_eq!(contract.get_agent_receive_usage(&agent, &usd, &ng), 1950);
+}
+
+#[test]
+fn test_feature_flags_share_one_bitmask() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    assert_eq!(contract.get_features(), 0);
+
+    contract.set_feature(&crate::Feature::PrivateMode, &true);
+    assert!(contract.is_private_mode());
+    assert_eq!(contract.get_features(), 1 << 7);
+
+    contract.set_authorization_required(&true);
+    assert!(contract.is_feature_enabled(&crate::Feature::AuthorizationRequired));
+    assert_eq!(contract.get_features(), (1 << 7) | 1);
+
+    contract.set_private_mode(&false);
+    assert!(!contract.is_feature_enabled(&crate::Feature::PrivateMode));
+    assert_eq!(contract.get_features(), 1);
+}
+
+#[test]
+fn test_cancel_while_paused_needs_paused_refunds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.pause(&None, &None);
+
+    let result = contract.try_cancel_remittance(&remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::ContractPaused)));
+
+    contract.set_paused_refunds_allowed(&true);
+    assert!(contract.is_feature_enabled(&crate::Feature::PausedRefunds));
+    contract.cancel_remittance(&remittance_id);
+
+    assert_eq!(token::Client::new(&env, &token.address).balance(&sender), 10000);
+    assert_eq!(contract.get_remittance(&remittance_id).status, crate::types::RemittanceStatus::Cancelled);
+
+    let result = contract.try_confirm_payout(&remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::ContractPaused)));
+}
+
+#[test]
+fn test_network_metrics() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let other_agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.register_agent(&agent);
+    contract.register_agent(&other_agent);
+    contract.remove_agent(&other_agent, &None);
+
+    env.ledger().set_timestamp(1_000);
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.create_remittance(&sender, &agent, &500, &String::from_str(&env, "USD"), &String::from_str(&env, "KE"), &None, &None, &Vec::new(&env), &0, &0);
+
+    env.ledger().set_timestamp(1_300);
+    contract.confirm_payout(&first);
+
+    let metrics = contract.get_network_metrics();
+    assert_eq!(metrics.settlements, 1);
+    assert_eq!(metrics.average_settlement_seconds, 300);
+    assert_eq!(metrics.active_agents, 1);
+    assert_eq!(metrics.corridors, 2);
+    assert_eq!(metrics.total_value_locked, 1500);
+}
+
+#[test]
+fn test_recipient_claims_pull_mode_payout() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let recipient = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    let result = contract.try_claim_payout(&remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    contract.set_payout_recipient(&remittance_id, &Some(recipient.clone()));
+    let remittance = contract.get_remittance(&remittance_id);
+    assert_eq!(remittance.payout_mode, crate::PayoutMode::Pull);
+    assert_eq!(remittance.recipient, Some(recipient.clone()));
+
+    let result = contract.try_confirm_payout(&remittance_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    contract.claim_payout(&remittance_id);
+
+    let token_client = token::Client::new(&env, &token.address);
+    assert_eq!(token_client.balance(&recipient), 975);
+    assert_eq!(token_client.balance(&agent), 0);
+    assert_eq!(contract.get_remittance(&remittance_id).status, crate::RemittanceStatus::Completed);
+}
+
+#[test]
+fn test_admin_transition_batch() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let reason_hash = BytesN::from_array(&env, &[7; 32]);
+
+    let mut transitions = Vec::new(&env);
+    transitions.push_back((first, crate::RemittanceStatus::PayoutFailed));
+    transitions.push_back((second, crate::RemittanceStatus::PayoutFailed));
+    contract.admin_transition_batch(&transitions, &reason_hash);
+
+    assert_eq!(contract.get_remittance(&first).status, crate::RemittanceStatus::PayoutFailed);
+    assert_eq!(contract.get_remittance(&second).status, crate::RemittanceStatus::PayoutFailed);
+    let result = contract.try_confirm_payout(&first);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    let mut transitions = Vec::new(&env);
+    transitions.push_back((first, crate::RemittanceStatus::Pending));
+    transitions.push_back((second, crate::RemittanceStatus::Completed));
+    let result = contract.try_admin_transition_batch(&transitions, &reason_hash);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    assert_eq!(contract.get_remittance(&first).status, crate::RemittanceStatus::PayoutFailed);
+
+    let mut transitions = Vec::new(&env);
+    transitions.push_back((first, crate::RemittanceStatus::Pending));
+    contract.admin_transition_batch(&transitions, &reason_hash);
+    contract.confirm_payout(&first);
+    assert_eq!(contract.get_remittance(&first).status, crate::RemittanceStatus::Completed);
+
+    // The sender can still recover a PayoutFailed remittance's funds
+    let token_client = token::Client::new(&env, &token.address);
+    let sender_before = token_client.balance(&sender);
+    contract.cancel_remittance(&second);
+    assert_eq!(token_client.balance(&sender) - sender_before, 1000);
+    assert_eq!(contract.get_remittance(&second).status, crate::RemittanceStatus::Cancelled);
+    assert_eq!(contract.get_total_locked(), 0);
+}
+
+#[test]
+fn test_corridor_expiry_bounds() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let currency = String::from_str(&env, "USD");
+    let country = String::from_str(&env, "NG");
+    env.ledger().set_timestamp(1_000);
+
+    let result = contract.try_set_corridor_expiry_bounds(&currency, &country, &Some(7200), &Some(3600));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAmount)));
+    contract.set_corridor_expiry_bounds(&currency, &country, &Some(3600), &Some(86400));
+
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &currency, &country, &Some(1_060), &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::ExpiryTooShort)));
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &currency, &country, &Some(1_000 + 2 * 86400), &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::ExpiryTooLong)));
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &currency, &country, &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::ExpiryTooLong)));
+
+    contract.set_default_expiry_seconds(&Some(7200));
+    contract.create_remittance(&sender, &agent, &1000, &currency, &country, &None, &None, &Vec::new(&env), &0, &0);
+    contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "KE"), &Some(1_060), &None, &Vec::new(&env), &0, &0);
+
+    contract.set_corridor_expiry_bounds(&currency, &country, &None, &None);
+    assert_eq!(contract.get_corridor_expiry_bounds(&currency, &country), None);
+}
+
+#[test]
+fn test_escrow_dispute_split() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_role(&crate::Role::Arbitrator, &arbitrator, &true);
+
+    let transfer_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.convert_to_escrow(&transfer_id, &vec![&env, 475, 500]);
+    contract.release_escrow_milestone(&transfer_id);
+
+    let result = contract.try_resolve_escrow_dispute(&arbitrator, &transfer_id, &5000);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    let evidence_hash = BytesN::from_array(&env, &[3; 32]);
+    contract.open_escrow_dispute(&transfer_id, &evidence_hash);
+    let escrow = contract.get_escrow(&transfer_id);
+    assert_eq!(escrow.status, crate::EscrowStatus::Disputed);
+    assert_eq!(contract.get_escrow_dispute_evidence(&transfer_id), Some(evidence_hash));
+
+    let result = contract.try_release_escrow_milestone(&transfer_id);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    let result = contract.try_resolve_escrow_dispute(&arbitrator, &transfer_id, &10001);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAmount)));
+
+    let sender_before = token_client.balance(&sender);
+    let refund = contract.resolve_escrow_dispute(&arbitrator, &transfer_id, &3000);
+    assert_eq!(refund, 150);
+    assert_eq!(token_client.balance(&sender) - sender_before, 150);
+    assert_eq!(token_client.balance(&agent), 475 + 350);
+
+    let escrow = contract.get_escrow(&transfer_id);
+    assert_eq!(escrow.status, crate::EscrowStatus::Resolved);
+    assert_eq!((escrow.released_amount, escrow.sender_share_bps), (825, Some(3000)));
+}
+
+#[test]
+fn test_timelocked_fee_updates() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let treasury = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    env.ledger().set_timestamp(1_000);
+    let snapshot = contract.snapshot_config();
+
+    contract.set_timelock_delay(&Some(3600));
+    let result = contract.try_update_fee(&300);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    let result = contract.try_set_token_fee_bps(&token.address, &Some(100));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    let result = contract.try_set_treasury_for_token(&token.address, &Some(treasury.clone()));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    let result = contract.try_set_timelock_delay(&None);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    let result = contract.try_set_fee_strategy(&None);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    let result = contract.try_update_agent_rebate(&100);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    let result = contract.try_set_agent_surcharge_bps(&agent, &50);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    let result = contract.try_set_insurance_premium_bps(&10);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    let result = contract.try_set_sla_penalty_bps(&10);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    let fee_update = contract.queue_fee_update(&crate::TimelockedUpdate::PlatformFee(300));
+    let treasury_update = contract.queue_fee_update(&crate::TimelockedUpdate::Treasury(Some(treasury.clone())));
+    assert_eq!(contract.get_queued_update(&fee_update).unwrap().eta, 4_600);
+
+    let result = contract.try_execute_fee_update(&fee_update);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    contract.cancel_queued_update(&treasury_update);
+    assert_eq!(contract.get_queued_update(&treasury_update), None);
+
+    env.ledger().set_timestamp(4_600);
+    contract.execute_fee_update(&fee_update);
+    assert_eq!(contract.get_platform_fee_bps(), 300);
+    let result = contract.try_rollback_config(&snapshot);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+    let result = contract.try_execute_fee_update(&fee_update);
+    assert_eq!(result, Err(Ok(crate::ContractError::UpdateNotFound)));
+    let result = contract.try_execute_fee_update(&treasury_update);
+    assert_eq!(result, Err(Ok(crate::ContractError::UpdateNotFound)));
+    let result = contract.try_cancel_queued_update(&99);
+    assert_eq!(result, Err(Ok(crate::ContractError::UpdateNotFound)));
+
+    let token_fee_update = contract.queue_fee_update(&crate::TimelockedUpdate::TokenFee(token.address.clone(), Some(100)));
+    let delay_update = contract.queue_fee_update(&crate::TimelockedUpdate::Delay(None));
+    env.ledger().set_timestamp(8_200);
+    contract.execute_fee_update(&token_fee_update);
+    assert_eq!(contract.get_token_fee_bps(&token.address), Some(100));
+    contract.execute_fee_update(&delay_update);
+    assert_eq!(contract.get_timelock_delay(), None);
+    contract.update_fee(&200);
+    assert_eq!(contract.get_platform_fee_bps(), 200);
+
+    // Rolling back only the agent rebate is held back like the platform fee.
+    contract.update_agent_rebate(&100);
+    let rebate_snapshot = contract.snapshot_config();
+    contract.update_agent_rebate(&500);
+    contract.set_timelock_delay(&Some(3600));
+    let result = contract.try_rollback_config(&rebate_snapshot);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    let rebate_update = contract.queue_fee_update(&crate::TimelockedUpdate::AgentRebate(100));
+    let surcharge_update = contract.queue_fee_update(&crate::TimelockedUpdate::AgentSurcharge(agent.clone(), 50));
+    let premium_update = contract.queue_fee_update(&crate::TimelockedUpdate::InsurancePremium(10));
+    let penalty_update = contract.queue_fee_update(&crate::TimelockedUpdate::SlaPenalty(10));
+    let strategy_update = contract.queue_fee_update(&crate::TimelockedUpdate::RemoveFeeStrategy);
+    let result = contract.try_queue_fee_update(&crate::TimelockedUpdate::AgentRebate(10001));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidFeeBps)));
+
+    env.ledger().set_timestamp(11_800);
+    for update in [rebate_update, surcharge_update, premium_update, penalty_update, strategy_update] {
+        contract.execute_fee_update(&update);
+    }
+    assert_eq!(contract.get_agent_rebate_bps(), 100);
+    assert_eq!(contract.get_agent_surcharge_bps(&agent), 50);
+    assert_eq!(contract.get_insurance_premium_bps(), 10);
+    assert_eq!(contract.get_sla_penalty_bps(), 10);
+    assert_eq!(contract.get_fee_strategy(), None);
+}
+
+#[test]
+fn test_get_remittances_by_sender() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let other_sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+    token.mint(&other_sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let first = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.create_remittance(&other_sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let second = contract.create_remittance(&sender, &agent, &2000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let third = contract.create_remittance(&sender, &agent, &3000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    assert_eq!(contract.get_sender_remittance_count(&sender), 3);
+    assert_eq!(contract.get_sender_remittance_count(&other_sender), 1);
+
+    let page = contract.get_remittances_by_sender(&sender, &0, &2);
+    assert_eq!((page.get_unchecked(0).id, page.get_unchecked(1).id), (first, second));
+    let page = contract.get_remittances_by_sender(&sender, &2, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get_unchecked(0).id, third);
+    assert_eq!(contract.get_remittances_by_sender(&sender, &5, &10).len(), 0);
+
+    contract.set_private_mode(&true);
+    let result = contract.try_get_remittances_by_sender(&sender, &0, &10);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+}
+
+#[test]
+fn test_rollback_does_not_lift_evacuation_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let vault = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.set_guardian(&guardian);
+    contract.set_recovery_vault(&vault);
+
+    let snapshot_id = contract.snapshot_config();
+
+    env.ledger().set_timestamp(crate::RECOVERY_VAULT_DELAY_SECONDS);
+    contract.emergency_evacuate(&token.address, &vault);
+
+    contract.rollback_config(&snapshot_id);
+    assert!(contract.is_paused());
+    assert!(contract.is_evacuated());
+    assert_eq!(contract.get_pause_info().unwrap().reason, Some(symbol_short!("evacuate")));
+}
+
+#[test]
+fn test_open_index_with_many_remittances() {
+    let env = Env::default();
+    env.mock_all_auths();
+    // The budget spans every call in the test, not each invocation
+    env.budget().reset_unlimited();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &100000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    for _ in 0..60 {
+        contract.create_remittance(&sender, &agent, &100, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    }
+
+    // Close everything but the newest five, the oldest last so the head
+    // has a long run of closed IDs to skip
+    for remittance_id in 2..=55u64 {
+        contract.cancel_remittance(&remittance_id);
+    }
+    contract.cancel_remittance(&1);
+
+    let aged = contract.get_aged_pending(&0, &0, &10).remittances;
+    assert_eq!(aged.len(), 5);
+    assert_eq!(aged.get_unchecked(0).id, 56);
+    assert_eq!(aged.get_unchecked(4).id, 60);
+
+    let newest = contract.create_remittance(&sender, &agent, &100, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&58);
+    let aged = contract.get_aged_pending(&0, &0, &10).remittances;
+    assert_eq!(aged.len(), 5);
+    assert_eq!(aged.get_unchecked(4).id, newest);
+}
+
+#[test]
+fn test_get_aged_pending() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    env.ledger().set_timestamp(1_000);
+    let oldest = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let cancelled = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    env.ledger().set_timestamp(2_000);
+    let middle = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    env.ledger().set_timestamp(4_000);
+    contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+
+    contract.cancel_remittance(&cancelled);
+
+    let page = contract.get_aged_pending(&1_500, &0, &10);
+    assert_eq!(page.remittances.len(), 2);
+    assert_eq!(page.remittances.get_unchecked(0).id, oldest);
+    assert_eq!(page.remittances.get_unchecked(1).id, middle);
+    assert_eq!(page.next_start_id, None);
+
+    let page = contract.get_aged_pending(&1_500, &0, &1);
+    assert_eq!(page.remittances.get_unchecked(0).id, oldest);
+    let page = contract.get_aged_pending(&1_500, &page.next_start_id.unwrap(), &10);
+    assert_eq!(page.remittances.len(), 1);
+    assert_eq!(page.remittances.get_unchecked(0).id, middle);
+
+    contract.confirm_payout(&oldest);
+    let aged = contract.get_aged_pending(&1_500, &0, &10).remittances;
+    assert_eq!(aged.len(), 1);
+    assert_eq!(aged.get_unchecked(0).id, middle);
+
+    assert_eq!(contract.get_aged_pending(&0, &0, &10).remittances.len(), 2);
+    assert_eq!(contract.get_aged_pending(&5_000, &0, &10).remittances.len(), 0);
+}
+
+#[test]
+fn test_create_remittance_with_pull_funding() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    token_client.approve(&sender, &contract.address, &1500, &1000);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &crate::FLAG_PULL_FUNDING, &0);
+    assert_eq!(token_client.allowance(&sender, &contract.address), 500);
+    assert_eq!(token_client.balance(&sender), 9000);
+    assert_eq!(token_client.balance(&contract.address), 1000);
+    assert_eq!(contract.get_remittance(&remittance_id).flags, crate::FLAG_PULL_FUNDING);
+
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &crate::FLAG_PULL_FUNDING, &0);
+    assert!(result.is_err());
+    assert_eq!(token_client.balance(&sender), 9000);
+
+    contract.convert_to_escrow(&remittance_id, &vec![&env, 500, 475]);
+}
+
+#[test]
+fn test_agent_surcharge() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let other_agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.register_agent(&other_agent);
+
+    let result = contract.try_set_agent_surcharge_bps(&agent, &10001);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidFeeBps)));
+    let result = contract.try_set_agent_surcharge_bps(&Address::generate(&env), &100);
+    assert_eq!(result, Err(Ok(crate::ContractError::AgentNotRegistered)));
+
+    contract.set_agent_surcharge_bps(&agent, &100);
+    assert_eq!(contract.get_agent_surcharge_bps(&agent), 100);
+    assert_eq!(contract.get_agent_surcharge_bps(&other_agent), 0);
+
+    let quote = contract.create_quote(&sender, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &1000, &Some(agent.clone()));
+    assert_eq!(quote.fee, 25);
+    assert_eq!(quote.agent_surcharge, 10);
+    assert_eq!(quote.payout_amount, 965);
+
+    let result = contract.try_create_remittance_from_quote(&quote.id, &other_agent, &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAddress)));
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_remittance(&remittance_id).agent_surcharge, 10);
+
+    contract.confirm_payout(&remittance_id);
+    assert_eq!(token_client.balance(&agent), 965);
+    assert_eq!(contract.get_accumulated_fees(), 25);
+    assert_eq!(contract.get_agent_rebate_balance(&agent), 10);
+
+    let unsurcharged = contract.create_remittance(&sender, &other_agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_remittance(&unsurcharged).agent_surcharge, 0);
+
+    assert_eq!(contract.claim_agent_rebate(&agent), 10);
+    assert_eq!(token_client.balance(&agent), 975);
+}
+
+#[test]
+fn test_reserve_requirement_blocks_fee_withdrawal() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let fee_recipient = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    assert_eq!(contract.get_reserve_ratio_bps(), None);
+
+    let result = contract.try_set_reserve_bps(&10001);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidFeeBps)));
+    contract.set_reserve_bps(&1000);
+    assert_eq!(contract.get_reserve_bps(), 1000);
+
+    let settled = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let pending = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&settled);
+    assert_eq!(contract.get_reserve_ratio_bps(), Some(250));
+
+    let result = contract.try_withdraw_fees(&admin, &fee_recipient);
+    assert_eq!(result, Err(Ok(crate::ContractError::InsufficientContractLiquidity)));
+    assert_eq!(contract.get_accumulated_fees(), 25);
+
+    // Float money sits in the contract but belongs to the agent.
+    contract.deposit_float(&agent, &100);
+    assert_eq!(contract.get_reserve_ratio_bps(), Some(250));
+    let result = contract.try_withdraw_fees(&admin, &fee_recipient);
+    assert_eq!(result, Err(Ok(crate::ContractError::InsufficientContractLiquidity)));
+
+    token.mint(&contract.address, &100);
+    contract.withdraw_fees(&admin, &fee_recipient);
+    assert_eq!(token::Client::new(&env, &token.address).balance(&fee_recipient), 25);
+    assert_eq!(contract.get_reserve_ratio_bps(), Some(1000));
+
+    contract.cancel_remittance(&pending);
+    assert_eq!(contract.get_reserve_ratio_bps(), None);
+}
+
+#[test]
+fn test_fee_schedule_archive() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+
+    let contract = create_swiftremit_contract(&env);
+    env.ledger().set_sequence_number(10);
+    contract.initialize(&admin, &token.address, &250, &None);
+
+    env.ledger().set_sequence_number(20);
+    contract.update_fee(&300);
+    contract.update_fee(&300);
+
+    env.ledger().set_sequence_number(30);
+    contract.update_agent_rebate(&1000);
+
+    assert_eq!(contract.get_fee_schedule_at(&9), None);
+
+    let first = contract.get_fee_schedule_at(&19).unwrap();
+    assert_eq!(first.version, 1);
+    assert_eq!(first.platform_fee_bps, 250);
+    assert_eq!(first.effective_ledger, 10);
+
+    let second = contract.get_fee_schedule_at(&20).unwrap();
+    assert_eq!(second.version, 2);
+    assert_eq!(second.platform_fee_bps, 300);
+    assert_eq!(second.agent_rebate_bps, 0);
+
+    let latest = contract.get_fee_schedule_at(&1_000).unwrap();
+    assert_eq!(latest.version, 3);
+    assert_eq!(latest.platform_fee_bps, 300);
+    assert_eq!(latest.agent_rebate_bps, 1000);
+    assert_eq!(latest.effective_ledger, 30);
+}
+
+#[test]
+fn test_referral_rewards() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let referrer = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_referral_config(&Some(crate::ReferralConfig { share_bps: 2000, period_seconds: 1000 }));
+
+    let result = contract.try_set_referrer(&sender, &sender);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAddress)));
+
+    env.ledger().set_timestamp(100);
+    contract.set_referrer(&sender, &referrer);
+    assert_eq!(contract.get_referrer(&sender), Some(referrer.clone()));
+    let result = contract.try_set_referrer(&sender, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&remittance_id);
+    assert_eq!(contract.get_referral_balance(&referrer), 5);
+    assert_eq!(contract.get_accumulated_fees(), 20);
+
+    env.ledger().set_timestamp(1_101);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&remittance_id);
+    assert_eq!(contract.get_referral_balance(&referrer), 5);
+    assert_eq!(contract.get_accumulated_fees(), 45);
+
+    assert_eq!(contract.claim_referral_rewards(&referrer), 5);
+    assert_eq!(token_client.balance(&referrer), 5);
+    let result = contract.try_claim_referral_rewards(&referrer);
+    assert_eq!(result, Err(Ok(crate::ContractError::NoFeesToWithdraw)));
+}
+
+#[test]
+fn test_insured_remittance_claim() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let result = contract.try_create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &crate::FLAG_INSURED, &0);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidFlags)));
+
+    contract.set_insurance_premium_bps(&5000);
+    let insured = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &crate::FLAG_INSURED, &0);
+    let uninsured = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(contract.get_remittance(&insured).insurance_premium, 500);
+    assert_eq!(contract.get_remittance(&uninsured).insurance_premium, 0);
+    assert_eq!(token_client.balance(&sender), 7500);
+    assert_eq!(contract.get_insurance_fund().balance, 500);
+
+    // A claim is only paid when an Arbitrator upholds a challenge to the
+    // settlement, and only for insured remittances
+    let challenger = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    contract.set_role(&crate::Role::Challenger, &challenger, &true);
+    contract.set_role(&crate::Role::Arbitrator, &arbitrator, &true);
+    contract.set_challenge_config(&Some(crate::ChallengeConfig { period_seconds: 500, bond: 0 }));
+    let evidence = BytesN::from_array(&env, &[7u8; 32]);
+
+    contract.confirm_payout(&insured);
+    contract.confirm_payout(&uninsured);
+    let rejected = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &crate::FLAG_INSURED, &0);
+    contract.confirm_payout(&rejected);
+    assert_eq!(contract.get_insurance_fund().balance, 1000);
+
+    contract.challenge_settlement(&challenger, &rejected, &evidence);
+    contract.resolve_challenge(&arbitrator, &rejected, &false);
+    assert_eq!(contract.get_insurance_claim(&rejected), None);
+
+    contract.challenge_settlement(&challenger, &uninsured, &evidence);
+    contract.resolve_challenge(&arbitrator, &uninsured, &true);
+    assert_eq!(contract.get_insurance_claim(&uninsured), None);
+
+    // The claim is capped at the remittance amount
+    let sender_before = token_client.balance(&sender);
+    env.ledger().set_timestamp(100);
+    contract.challenge_settlement(&challenger, &insured, &evidence);
+    contract.resolve_challenge(&arbitrator, &insured, &true);
+    assert_eq!(token_client.balance(&sender) - sender_before, 1000);
+    assert_eq!(
+        contract.get_insurance_claim(&insured),
+        Some(crate::InsuranceClaim { amount: 1000, arbitrator: arbitrator.clone(), ruled_at: 100 })
+    );
+    assert_eq!(
+        contract.get_insurance_fund(),
+        crate::InsuranceFund { premiums_collected: 1000, claims_paid: 1000, claim_count: 1, balance: 0 }
+    );
+}
+
+#[test]
+fn test_settlement_challenge_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let challenger = Address::generate(&env);
+    let arbitrator = Address::generate(&env);
+    let evidence = BytesN::from_array(&env, &[7u8; 32]);
+
+    token.mint(&sender, &10000);
+    token.mint(&challenger, &1000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_roles(&crate::Role::Challenger, &vec![&env, challenger.clone()], &true);
+    contract.set_roles(&crate::Role::Arbitrator, &vec![&env, arbitrator.clone()], &true);
+
+    env.ledger().set_timestamp(1_000);
+    let rejected = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let upheld = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    let unchallenged = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&rejected);
+    contract.confirm_payout(&upheld);
+    contract.confirm_payout(&unchallenged);
+    assert!(contract.is_settlement_final(&rejected));
+
+    contract.set_challenge_config(&Some(crate::ChallengeConfig { period_seconds: 500, bond: 100 }));
+    assert!(!contract.is_settlement_final(&rejected));
+
+    let result = contract.try_challenge_settlement(&sender, &rejected, &evidence);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+
+    contract.challenge_settlement(&challenger, &rejected, &evidence);
+    contract.challenge_settlement(&challenger, &upheld, &evidence);
+    assert_eq!(token_client.balance(&challenger), 800);
+    assert_eq!(contract.get_settlement_challenge(&rejected).unwrap().evidence_hash, evidence);
+    let result = contract.try_challenge_settlement(&challenger, &rejected, &evidence);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    env.ledger().set_timestamp(1_501);
+    assert!(contract.is_settlement_final(&unchallenged));
+    assert!(!contract.is_settlement_final(&rejected));
+    let result = contract.try_challenge_settlement(&challenger, &unchallenged, &evidence);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+
+    let result = contract.try_resolve_challenge(&challenger, &rejected, &false);
+    assert_eq!(result, Err(Ok(crate::ContractError::MissingRole)));
+
+    contract.resolve_challenge(&arbitrator, &rejected, &false);
+    assert!(contract.is_settlement_final(&rejected));
+    assert_eq!(contract.get_accumulated_fees(), 175);
+
+    contract.resolve_challenge(&arbitrator, &upheld, &true);
+    assert!(!contract.is_settlement_final(&upheld));
+    assert_eq!(token_client.balance(&challenger), 900);
+
+    let result = contract.try_resolve_challenge(&arbitrator, &upheld, &false);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidStatus)));
+}
+
+#[test]
+fn test_get_remittance_view() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_rate_limit(&Some(crate::RateLimitConfig { cooldown_seconds: 60, window_seconds: 0, max_per_window: 0 }));
+
+    env.ledger().set_timestamp(1_000);
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &Some(1_500), &None, &Vec::new(&env), &0, &0);
+
+    env.ledger().set_timestamp(1_020);
+    let view = contract.get_remittance_view(&remittance_id);
+    assert_eq!(view.remittance, contract.get_remittance(&remittance_id));
+    assert!(view.settleable);
+    assert!(!view.settlement_final);
+    assert!(view.agent_registered);
+    assert!(view.corridor_enabled);
+    assert_eq!(view.payout_amount, 975);
+    assert_eq!(view.expires_in, Some(480));
+    assert_eq!(view.sender_cooldown, 40);
+
+    contract.confirm_payout(&remittance_id);
+    let view = contract.get_remittance_view(&remittance_id);
+    assert!(!view.settleable);
+    assert!(view.settlement_final);
+    assert_eq!(view.agent_reputation.completed_count, 1);
+}
+
+#[test]
+fn test_limit_threshold_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+    contract.set_rate_limit(&Some(crate::RateLimitConfig { cooldown_seconds: 0, window_seconds: 3600, max_per_window: 5 }));
+    contract.set_exposure_caps_enabled(&true);
+    contract.set_sender_exposure_cap(&sender, &Some(1000));
+
+    env.ledger().set_timestamp(1_000);
+    for amount in [100, 100, 100, 500, 200] {
+        contract.create_remittance(&sender, &agent, &amount, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    }
+
+    let crossings = |limit_type: Symbol| -> std::vec::Vec<(crate::EventEnvelope, u32, i128, i128, Option<u64>)> {
+        env.events()
+            .all()
+            .iter()
+            .filter(|event| event.0 == contract.address && event.1 == (symbol_short!("limit"), limit_type.clone(), sender.clone()).into_val(&env))
+            .map(|event| event.2.into_val(&env))
+            .collect()
+    };
+
+    let rate = crossings(symbol_short!("rate"));
+    assert_eq!(rate.len(), 2);
+    assert_eq!((rate[0].1, rate[0].2, rate[0].3, rate[0].4), (80, 4, 5, Some(4_600)));
+    assert_eq!((rate[1].1, rate[1].2), (100, 5));
+
+    let exposure = crossings(symbol_short!("exposure"));
+    assert_eq!(exposure.len(), 2);
+    assert_eq!((exposure[0].1, exposure[0].2, exposure[0].3, exposure[0].4), (80, 800, 1000, None));
+    assert_eq!((exposure[1].1, exposure[1].2), (100, 1000));
+}
+
+#[test]
+fn test_sender_self_caps() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    env.ledger().set_timestamp(1_000);
+    contract.set_my_max_tx(&sender, &Some(500));
+    contract.set_my_daily_cap(&sender, &Some(800));
+    assert_eq!(contract.get_my_max_tx(&sender).limit, Some(500));
+
+    let create = |amount: i128| contract.try_create_remittance(&sender, &agent, &amount, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    assert_eq!(create(600), Err(Ok(crate::ContractError::ExposureCapExceeded)));
+    assert!(create(500).is_ok());
+    assert!(create(300).is_ok());
+    assert_eq!(create(1), Err(Ok(crate::ContractError::ExposureCapExceeded)));
+
+    // Raising waits out the delay; the old cap still applies meanwhile.
+    let raised = contract.set_my_daily_cap(&sender, &Some(2000));
+    assert_eq!((raised.limit, raised.pending, raised.raise_at), (Some(800), Some(2000), 1_000 + crate::SELF_CAP_RAISE_DELAY_SECONDS));
+    assert_eq!(create(1), Err(Ok(crate::ContractError::ExposureCapExceeded)));
+
+    // A new day resets the spend, and the matured raise applies.
+    env.ledger().set_timestamp(1_000 + crate::SELF_CAP_RAISE_DELAY_SECONDS);
+    assert_eq!(contract.get_my_daily_cap(&sender).limit, Some(2000));
+    assert!(create(500).is_ok());
+
+    let lowered = contract.set_my_max_tx(&sender, &Some(100));
+    assert_eq!((lowered.limit, lowered.pending), (Some(100), None));
+    assert_eq!(create(200), Err(Ok(crate::ContractError::ExposureCapExceeded)));
+
+    let result = contract.try_set_my_max_tx(&sender, &Some(0));
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAmount)));
+}
+
+#[test]
+fn test_forward_fees_routes_by_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let token_admin = Address::generate(&env);
+    let token = create_token_contract(&env, &token_admin);
+    let token_client = token::Client::new(&env, &token.address);
+    let other_token = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let ops_treasury = Address::generate(&env);
+    let stable_treasury = Address::generate(&env);
+
+    token.mint(&sender, &10000);
+
+    let contract = create_swiftremit_contract(&env);
+    contract.initialize(&admin, &token.address, &250, &None);
+    contract.register_agent(&agent);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&remittance_id);
+
+    let result = contract.try_forward_fees(&admin);
+    assert_eq!(result, Err(Ok(crate::ContractError::InvalidAddress)));
+
+    contract.set_treasury(&Some(ops_treasury.clone()));
+    contract.set_treasury_for_token(&token.address, &Some(stable_treasury.clone()));
+    assert_eq!(contract.get_treasury_for_token(&token.address), Some(stable_treasury.clone()));
+    assert_eq!(contract.get_treasury_for_token(&other_token), Some(ops_treasury.clone()));
+
+    assert_eq!(contract.forward_fees(&admin), stable_treasury);
+    assert_eq!(token_client.balance(&stable_treasury), 25);
+    assert_eq!(contract.get_accumulated_fees(), 0);
+
+    let remittance_id = contract.create_remittance(&sender, &agent, &1000, &String::from_str(&env, "USD"), &String::from_str(&env, "NG"), &None, &None, &Vec::new(&env), &0, &0);
+    contract.confirm_payout(&remittance_id);
+    contract.set_treasury_for_token(&token.address, &None);
+    assert_eq!(contract.forward_fees(&admin), ops_treasury);
+    assert_eq!(token_client.balance(&ops_treasury), 25);
 }
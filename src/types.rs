@@ -1,4 +1,81 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN, String, Symbol, Vec};
+
+/// Event verbosity levels, from quietest to loudest. Each event is published
+/// at one level and only when that level does not exceed the configured
+/// verbosity.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EventVerbosity {
+    /// Failures and alarms only.
+    ErrorsOnly = 0,
+    /// State changes, configuration updates and the above.
+    Standard = 1,
+    /// Informational events and debug logs as well.
+    Verbose = 2,
+}
+
+/// Contract-wide toggles kept in a single bitmask; the value is the bit index.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Feature {
+    /// Remittances need Authorizer approval before payout.
+    AuthorizationRequired = 0,
+    /// Only Sender role holders may create remittances.
+    SenderAllowlist = 1,
+    /// Anyone may submit a netting batch.
+    BatchPermissionless = 2,
+    /// Batch entries need the agent's prior approval.
+    BatchAgentApproval = 3,
+    /// Senders may direct cancellation refunds elsewhere.
+    AlternateRefund = 4,
+    /// Creation is restricted to registered currencies and countries.
+    CorridorRegistry = 5,
+    /// Global and per-agent exposure caps are enforced.
+    ExposureCaps = 6,
+    /// Remittance detail reads are restricted to parties and auditors.
+    PrivateMode = 7,
+    /// Cancellation refunds stay open while the contract is paused.
+    PausedRefunds = 8,
+}
+
+/// Header at the start of every event payload.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventEnvelope {
+    pub schema_version: u32,
+    pub event_sequence: u64,
+    pub ledger_sequence: u32,
+    pub timestamp: u64,
+    pub level: EventVerbosity,
+}
+
+/// Operational roles the admin can delegate to other addresses.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// Approves remittances for payout when pre-authorization is required.
+    Authorizer,
+    /// Withdraws accumulated platform fees.
+    Treasurer,
+    /// Submits netting batch settlements.
+    BatchSettler,
+    /// Submits netting batches outside the configured settlement windows.
+    WindowOverride,
+    /// Approves or rejects remittances held for compliance review.
+    ComplianceOfficer,
+    /// Creates remittances while sender allowlist mode is on.
+    Sender,
+    /// Challenges settlements during the challenge period.
+    Challenger,
+    /// Rules on settlement challenges.
+    Arbitrator,
+    /// Closes the current accounting epoch.
+    EpochKeeper,
+    /// Reads remittance details while private mode is on.
+    Auditor,
+}
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -6,6 +83,26 @@ pub enum RemittanceStatus {
     Pending,
     Completed,
     Cancelled,
+    /// Funds moved into a linked milestone escrow.
+    Converted,
+    /// Agent declined the payout and the sender was refunded.
+    DeclinedByAgent,
+    /// Held for compliance review before the agent may settle.
+    PendingReview,
+    /// Rejected in compliance review and refunded to the sender.
+    ReviewRejected,
+    /// Marked by the admin after a failed payout; funds stay locked until
+    /// it is moved back to Pending.
+    PayoutFailed,
+}
+
+/// Who receives a remittance's payout. Push pays the agent when it
+/// confirms; Pull lets the recipient claim it directly with `claim_payout`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayoutMode {
+    Push,
+    Pull,
 }
 
 #[contracttype]
@@ -18,4 +115,648 @@ pub struct Remittance {
     pub fee: i128,
     pub status: RemittanceStatus,
     pub expiry: Option<u64>,
+    pub currency: String,
+    pub country: String,
+    pub tags: Vec<Symbol>,
+    pub flags: u32,
+    pub created_at: u64,
+    pub sender_confirmed: bool,
+    pub priority_tip: i128,
+    /// Agent surcharge deducted from the payout and accrued to the agent
+    pub agent_surcharge: i128,
+    /// Insurance premium paid on top of the amount; 0 when uninsured
+    pub insurance_premium: i128,
+    /// Payout in minor units of `currency`: the token payout converted at
+    /// the locked FX rate, recomputed from the executed payout on
+    /// settlement. `None` while no rate is locked.
+    pub fiat_amount: Option<i128>,
+    /// End beneficiary who claims the payout in Pull mode
+    pub recipient: Option<Address>,
+    pub payout_mode: PayoutMode,
+}
+
+/// Fee quote locked for a sender until `expires_at`.
+///
+/// A remittance created from a quote is charged `fee` regardless of any
+/// platform fee change made after the quote was issued. A quote for a
+/// specific `agent` also locks that agent's surcharge and can only be
+/// redeemed with it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Quote {
+    pub id: u64,
+    pub sender: Address,
+    pub amount: i128,
+    pub currency: String,
+    pub country: String,
+    pub fee_bps: u32,
+    pub fee: i128,
+    pub agent: Option<Address>,
+    pub agent_surcharge: i128,
+    pub payout_amount: i128,
+    pub expires_at: u64,
+}
+
+/// Counters touched on nearly every state-changing call, kept in one
+/// storage entry so hot paths read and write them once.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Counters {
+    /// Highest remittance ID issued so far
+    pub remittance_counter: u64,
+    /// Platform fees awaiting withdrawal
+    pub accumulated_fees: i128,
+    /// Total value held for Pending remittances across all senders
+    pub total_locked: i128,
+    /// Lifetime number of settled remittances
+    pub total_settlements: u64,
+    /// Lifetime number of cancelled remittances
+    pub total_cancellations: u64,
+}
+
+/// How long a settlement stays open to challenge, and the bond a
+/// Challenger posts to challenge it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChallengeConfig {
+    pub period_seconds: u64,
+    pub bond: i128,
+}
+
+/// Challenge posted against a settlement. `resolved` is set when an
+/// Arbitrator rules, with `upheld` recording whether the challenge stood.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementChallenge {
+    pub challenger: Address,
+    pub evidence_hash: BytesN<32>,
+    pub bond: i128,
+    pub challenged_at: u64,
+    pub resolved: bool,
+    pub upheld: bool,
+}
+
+/// Payout of a settlement in an instant corridor, held in the contract
+/// until its chargeback window closes at `release_at`. `disputed` is set
+/// when the sender opens a chargeback, after which only an Arbitrator
+/// ruling moves the funds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HeldPayout {
+    /// Payee: the agent, or the recipient of a Pull mode remittance
+    pub payee: Address,
+    pub amount: i128,
+    pub release_at: u64,
+    pub disputed: bool,
+}
+
+/// Fee accounting for one epoch, attributed when each remittance's fee is
+/// booked on settlement or escrow conversion. `fees` is the gross fee
+/// charged; `protocol_fees` is what accrued to the platform after rebates,
+/// SLA penalties and referral rewards, plus priority tips. `closed_at` is 0
+/// while the epoch is current.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpochReport {
+    pub epoch: u64,
+    pub started_at: u64,
+    pub closed_at: u64,
+    pub remittances: u64,
+    pub fees: i128,
+    pub protocol_fees: i128,
+    pub rebates: i128,
+}
+
+/// Insurance fund accounting. `balance` is premiums collected less claims
+/// paid and is held apart from platform fees.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct InsuranceFund {
+    pub premiums_collected: i128,
+    pub claims_paid: i128,
+    pub claim_count: u64,
+    pub balance: i128,
+}
+
+/// Compensation paid on an insured remittance and the ruling that paid it:
+/// the Arbitrator who upheld the settlement challenge and when.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsuranceClaim {
+    pub amount: i128,
+    pub arbitrator: Address,
+    pub ruled_at: u64,
+}
+
+/// Network-wide running totals behind `get_network_metrics`, maintained
+/// incrementally as agents register and remittances are created and settled.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NetworkTally {
+    /// Sum of creation-to-settlement times over all settlements, in seconds
+    pub settlement_seconds: u64,
+    /// Currently registered agents
+    pub active_agents: u32,
+    /// Corridors that have seen at least one remittance
+    pub corridors: u32,
+}
+
+/// Snapshot of network health for public dashboards.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NetworkMetrics {
+    pub settlements: u64,
+    pub average_settlement_seconds: u64,
+    pub active_agents: u32,
+    pub corridors: u32,
+    /// Value held for Pending remittances, as in `get_total_locked`
+    pub total_value_locked: i128,
+}
+
+/// Limits on how far ahead of creation a corridor's remittances may
+/// expire, in seconds. `None` leaves that side unbounded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiryBounds {
+    pub min_seconds: Option<u64>,
+    pub max_seconds: Option<u64>,
+}
+
+/// Running totals for a (currency, country) corridor, derived from the
+/// corridor fields stored on each remittance.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CorridorStats {
+    pub created_count: u64,
+    pub created_volume: i128,
+    pub completed_count: u64,
+    pub completed_volume: i128,
+    pub cancelled_count: u64,
+}
+
+/// Outstanding work assigned to an agent, maintained incrementally as
+/// remittances are created, settled and cancelled.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AgentWorkload {
+    pub pending_count: u64,
+    pub pending_value: i128,
+}
+
+/// Everything a wallet needs to render one remittance, read in one call.
+/// `expires_in` is `None` for open-ended remittances and 0 once expired;
+/// `sender_cooldown` is how long until the sender may create another
+/// remittance under the rate limit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemittanceView {
+    pub remittance: Remittance,
+    pub settleable: bool,
+    pub settlement_final: bool,
+    pub agent_registered: bool,
+    pub agent_reputation: AgentReputation,
+    pub corridor_enabled: bool,
+    pub payout_amount: i128,
+    pub expires_in: Option<u64>,
+    pub sender_cooldown: u64,
+}
+
+/// One remittance flattened into fixed scalar columns for accounting
+/// exports. Absent values are 0: `expiry` for open-ended remittances,
+/// `settled_at` until the remittance settles and `fiat_amount` while no FX
+/// rate is locked.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemittanceRow {
+    pub id: u64,
+    pub sender: Address,
+    pub agent: Address,
+    pub currency: String,
+    pub country: String,
+    pub status: RemittanceStatus,
+    pub amount: i128,
+    pub fee: i128,
+    pub agent_surcharge: i128,
+    pub insurance_premium: i128,
+    pub priority_tip: i128,
+    pub payout_amount: i128,
+    pub fiat_amount: i128,
+    pub created_at: u64,
+    pub expiry: u64,
+    pub settled_at: u64,
+}
+
+/// A page of `export_remittance_page`. `next_start_id` is where the next
+/// page starts, or `None` once every remittance has been scanned.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemittanceExportPage {
+    pub rows: Vec<RemittanceRow>,
+    pub next_start_id: Option<u64>,
+}
+
+/// A page of `get_aged_pending`. `next_start_id` is where the next page
+/// starts, or `None` once no later remittance can be aged.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AgedPendingPage {
+    pub remittances: Vec<Remittance>,
+    pub next_start_id: Option<u64>,
+}
+
+/// Outcome of one `scan_expiring` call. `next_start_id` is where the next
+/// call starts, or `None` once every open remittance has been visited.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiryScan {
+    pub warned: u32,
+    pub next_start_id: Option<u64>,
+}
+
+/// Terms a remittance will settle on, next to what the same transfer would
+/// be charged at today's fee schedule. Fees are fixed at creation, so
+/// `current_fee` is informational only.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementTerms {
+    pub remittance_id: u64,
+    pub amount: i128,
+    pub fee: i128,
+    pub payout_amount: i128,
+    pub current_fee: i128,
+}
+
+/// Contract-wide fee rates in force from `effective_ledger` until the next
+/// version. Versions are numbered from 1 and never rewritten.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSchedule {
+    pub version: u32,
+    pub platform_fee_bps: u32,
+    pub agent_rebate_bps: u32,
+    pub sla_penalty_bps: u32,
+    pub effective_ledger: u32,
+    pub effective_at: u64,
+}
+
+/// External contract that replaces the built-in fee calculation. Any fee it
+/// returns above `max_fee_bps` of the amount is rejected.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeStrategy {
+    pub contract: Address,
+    pub max_fee_bps: u32,
+}
+
+/// Fee computed by a fee strategy contract's `compute_fees`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeBreakdown {
+    pub fee: i128,
+}
+
+/// Remittance summary passed to a sender's notification contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemittanceNotice {
+    pub remittance_id: u64,
+    pub status: RemittanceStatus,
+    pub sender: Address,
+    pub agent: Address,
+    pub amount: i128,
+    pub fee: i128,
+}
+
+/// Outcome of a compliance review, with the timestamps needed to track
+/// review turnaround.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReviewRecord {
+    pub reviewer: Address,
+    pub approved: bool,
+    pub requested_at: u64,
+    pub reviewed_at: u64,
+}
+
+/// Lifetime payout track record of an agent.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AgentReputation {
+    pub completed_count: u64,
+    pub declined_count: u64,
+}
+
+/// Self-description of a deployment so tooling can confirm it is talking to
+/// the intended instance.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContractMetadata {
+    pub name: String,
+    /// Deployment environment, e.g. `staging` or `prod`
+    pub environment: Symbol,
+    /// sha256 of the operator's URL
+    pub operator_url_hash: BytesN<32>,
+}
+
+/// Step of the remittance lifecycle a token transfer belongs to, reported
+/// when the transfer fails.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TokenTransferStage {
+    /// Funds moving into the contract: remittance deposits, tips, bonds
+    /// and float deposits.
+    Deposit,
+    /// Settlement and escrow payouts to agents.
+    Payout,
+    /// Refunds, compensation and returned bonds to senders and challengers.
+    Refund,
+    /// Fee, rebate, reward and float withdrawals and evacuations.
+    Withdrawal,
+}
+
+/// Where an agent's settlement payouts are sent instead of the agent's own
+/// address, for custodial recipients that credit deposits by memo. Token
+/// transfers cannot carry a memo, so it is published on the
+/// `settle/routed` event for the receiving institution's indexer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutRoute {
+    pub destination: Address,
+    /// Sub-account identifier the destination requires, at most
+    /// `MAX_PAYOUT_MEMO_LEN` bytes
+    pub memo: String,
+}
+
+/// How platform fees are charged on a corridor's remittances when they
+/// settle in a netting batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NettingFeeMode {
+    /// Every remittance pays its full fee.
+    Gross,
+    /// Opposing flows between the same two parties in a batch pay fees on
+    /// the netted amount only.
+    Net,
+}
+
+/// Record of a netting batch settlement.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementBatch {
+    pub id: u64,
+    pub submitter: Address,
+    pub remittance_ids: Vec<u64>,
+    pub settled_at: u64,
+    /// `Net` when fees on at least one pair of opposing flows were charged
+    /// on the netted amount
+    pub fee_mode: NettingFeeMode,
+    /// Platform fees waived by netting, paid out to agents instead
+    pub fee_discount: i128,
+}
+
+/// Economics change that waits out the timelock delay before it applies.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimelockedUpdate {
+    /// New platform fee in basis points, as for `update_fee`
+    PlatformFee(u32),
+    /// New default treasury, as for `set_treasury`
+    Treasury(Option<Address>),
+    /// New fee override for a token, as for `set_token_fee_bps`
+    TokenFee(Address, Option<u32>),
+    /// New treasury override for a token, as for `set_treasury_for_token`
+    TokenTreasury(Address, Option<Address>),
+    /// New external fee strategy, as for `set_fee_strategy`
+    FeeStrategy(FeeStrategy),
+    /// Restores the built-in fee calculation, as for `set_fee_strategy(None)`
+    RemoveFeeStrategy,
+    /// New agent rebate in basis points, as for `update_agent_rebate`
+    AgentRebate(u32),
+    /// New surcharge for an agent, as for `set_agent_surcharge_bps`
+    AgentSurcharge(Address, u32),
+    /// New insurance premium, as for `set_insurance_premium_bps`
+    InsurancePremium(u32),
+    /// New SLA penalty, as for `set_sla_penalty_bps`
+    SlaPenalty(u32),
+    /// Shorter timelock delay; longer delays apply immediately
+    Delay(Option<u64>),
+}
+
+/// Update queued behind the timelock, executable from `eta`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedUpdate {
+    pub id: u64,
+    pub update: TimelockedUpdate,
+    pub queued_at: u64,
+    pub eta: u64,
+}
+
+/// Netting batch attempt that failed validation, recorded by
+/// `batch_settle_or_abort`. `failing_remittance_id` is the entry that
+/// failed, or `None` when the batch as a whole did (insufficient
+/// liquidity); `error_code` is the `ContractError` it failed with.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchAbort {
+    pub submitter: Address,
+    pub entry_count: u32,
+    pub failing_remittance_id: Option<u64>,
+    pub error_code: u32,
+    pub aborted_at: u64,
+}
+
+/// Token metadata probed and cached when the token is whitelisted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenInfo {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u32,
+    /// Ledger sequence at which the token interface was probed
+    pub probed_at: u32,
+}
+
+/// Exchange rate the sender declared for the fiat payout of a remittance.
+/// `rate` is destination fiat minor units per source token unit, scaled by
+/// `FX_RATE_SCALE`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FxLock {
+    pub destination_amount: i128,
+    pub rate: i128,
+    pub locked_at: u64,
+}
+
+/// Everything an external verifier needs to confirm a settlement.
+///
+/// `hash` is `sha256(xdr((contract, remittance_id, sender, agent, amount,
+/// fee, ledger_sequence)))`, where the tuple is serialized with Soroban's
+/// `ToXdr`. `settled_at` and the FX fields are informational and not part
+/// of the preimage.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementProof {
+    pub hash: BytesN<32>,
+    pub contract: Address,
+    pub remittance_id: u64,
+    pub sender: Address,
+    pub agent: Address,
+    pub amount: i128,
+    pub fee: i128,
+    pub ledger_sequence: u32,
+    pub settled_at: u64,
+    /// `FxLock::destination_amount` locked before settlement, 0 without a lock
+    pub fx_destination_amount: i128,
+    /// `FxLock::rate` locked before settlement, 0 without a lock
+    pub fx_rate: i128,
+}
+
+/// Settlement approval an agent signs offline for `confirm_payout_with_sig`.
+///
+/// The agent signs the payload's `ToXdr` encoding with the ed25519 key it
+/// registered. Each `nonce` can be used once per agent, in any order, and
+/// the approval is void after `deadline`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutApproval {
+    pub contract: Address,
+    pub remittance_id: u64,
+    pub nonce: u64,
+    pub deadline: u64,
+}
+
+/// Limited-scope ed25519 key an agent registers for settling from another
+/// device, such as a mobile app, without its main key.
+///
+/// A session key can only sign `PayoutApproval`s for
+/// `confirm_payout_with_session`. It stops working after `expiry` or once
+/// the payouts it settled reach `cap`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionKey {
+    /// Total payout value the key may settle.
+    pub cap: i128,
+    /// Payout value settled with the key so far.
+    pub spent: i128,
+    pub expiry: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EscrowStatus {
+    /// Milestones remain to be released.
+    Active,
+    /// Every milestone has been paid to the agent.
+    Released,
+    /// Closed on the agent's reduced amount; the rest went back to the sender.
+    Settled,
+    /// Frozen by the sender's dispute until an Arbitrator rules.
+    Disputed,
+    /// Closed by an Arbitrator splitting the balance between both parties.
+    Resolved,
+}
+
+/// Milestone escrow created from a Pending remittance. It shares the
+/// remittance's ID and releases `milestones` to the agent in order.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Escrow {
+    pub remittance_id: u64,
+    pub sender: Address,
+    pub agent: Address,
+    pub milestones: Vec<i128>,
+    pub released_count: u32,
+    pub released_amount: i128,
+    pub status: EscrowStatus,
+    /// Reduced amount the agent offered to close the escrow for, awaiting
+    /// the sender's acceptance
+    pub proposed_amount: Option<i128>,
+    /// Sender's share of the disputed balance, in basis points, once resolved
+    pub sender_share_bps: Option<u32>,
+}
+
+/// Daily UTC time range in which netting batches may execute, in seconds
+/// since midnight. A window with `start_seconds > end_seconds` wraps past
+/// midnight.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementWindow {
+    pub start_seconds: u32,
+    pub end_seconds: u32,
+}
+
+/// Per-sender limits on how often remittances can be created. A zero
+/// `cooldown_seconds` or `max_per_window` disables that limit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimitConfig {
+    pub cooldown_seconds: u64,
+    pub window_seconds: u64,
+    pub max_per_window: u32,
+}
+
+/// Share of the platform fee on a referred sender's settlements paid to
+/// their referrer, for `period_seconds` after the referral was recorded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralConfig {
+    pub share_bps: u32,
+    pub period_seconds: u64,
+}
+
+/// Referrer recorded for a sender and when.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Referral {
+    pub referrer: Address,
+    pub referred_at: u64,
+}
+
+/// Remittances a sender created in the current fixed rate-limit window.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RateLimitWindow {
+    pub started_at: u64,
+    pub count: u32,
+}
+
+/// A limit a sender set on their own remittances. Lowering takes effect at
+/// once; a raise or removal waits in `pending` until `raise_at`, which is 0
+/// when no raise is pending. `None` means no limit.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SelfCap {
+    pub limit: Option<i128>,
+    pub pending: Option<i128>,
+    pub raise_at: u64,
+}
+
+/// Value sent by a sender, or received by an agent, on one UTC day,
+/// numbered from the epoch.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DailySpend {
+    pub day: u64,
+    pub spent: i128,
+}
+
+/// Pre-registered destination for an emergency evacuation. It can only be
+/// used once `active_at` has passed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryVault {
+    pub address: Address,
+    pub active_at: u64,
+}
+
+/// Who paused the contract, why, and when the pause lifts on its own.
+/// `pause_until` of `None` means the pause lasts until `unpause`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseInfo {
+    pub paused_by: Address,
+    pub reason: Option<Symbol>,
+    pub paused_at: u64,
+    pub pause_until: Option<u64>,
 }
@@ -1,11 +1,11 @@
-use soroban_sdk::Address;
+use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
-use crate::ContractError;
+use crate::{ContractError, SettlementWindow, MAX_MILESTONES, MAX_SETTLEMENT_WINDOWS, MAX_TAGS, SUPPORTED_FLAGS};
 
 /// Validates that an address is properly formatted and not empty.
 /// Stellar addresses in Soroban are represented by the Address type,
 /// which is already validated by the SDK, but we check for additional constraints.
-pub fn validate_address(address: &Address) -> Result<(), ContractError> {
+pub fn validate_address(_address: &Address) -> Result<(), ContractError> {
     // The Address type in Soroban SDK is already validated by the runtime.
     // However, we can add additional checks if needed.
     // For now, we ensure the address is not a zero/empty address by checking
@@ -18,10 +18,107 @@ pub fn validate_address(address: &Address) -> Result<(), ContractError> {
     Ok(())
 }
 
+/// Length of an ISO 4217 currency code.
+pub const CURRENCY_CODE_LEN: u32 = 3;
+
+/// Length of an ISO 3166-1 alpha-2 country code.
+pub const COUNTRY_CODE_LEN: u32 = 2;
+
+/// Normalizes a currency code to upper case, rejecting anything that is not
+/// exactly `CURRENCY_CODE_LEN` ASCII letters.
+pub fn normalize_currency(env: &Env, currency: &String) -> Result<String, ContractError> {
+    normalize_code::<{ CURRENCY_CODE_LEN as usize }>(env, currency).ok_or(ContractError::InvalidCurrency)
+}
+
+/// Normalizes a country code to upper case, rejecting anything that is not
+/// exactly `COUNTRY_CODE_LEN` ASCII letters.
+pub fn normalize_country(env: &Env, country: &String) -> Result<String, ContractError> {
+    normalize_code::<{ COUNTRY_CODE_LEN as usize }>(env, country).ok_or(ContractError::InvalidCountry)
+}
+
+fn normalize_code<const N: usize>(env: &Env, code: &String) -> Option<String> {
+    if code.len() as usize != N {
+        return None;
+    }
+
+    let mut buf = [0u8; N];
+    code.copy_into_slice(&mut buf);
+    for byte in buf.iter_mut() {
+        if !byte.is_ascii_alphabetic() {
+            return None;
+        }
+        byte.make_ascii_uppercase();
+    }
+
+    Some(String::from_bytes(env, &buf))
+}
+
+/// Validates remittance tags: at most `MAX_TAGS`, with no tag repeated.
+pub fn validate_tags(tags: &Vec<Symbol>) -> Result<(), ContractError> {
+    if tags.len() > MAX_TAGS {
+        return Err(ContractError::InvalidTags);
+    }
+
+    for (i, tag) in tags.iter().enumerate() {
+        if tags.iter().skip(i + 1).any(|other| other == tag) {
+            return Err(ContractError::InvalidTags);
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates creation flags against `SUPPORTED_FLAGS`.
+pub fn validate_flags(flags: u32) -> Result<(), ContractError> {
+    if flags & !SUPPORTED_FLAGS != 0 {
+        return Err(ContractError::InvalidFlags);
+    }
+    Ok(())
+}
+
+/// Validates an escrow milestone schedule: 1 to `MAX_MILESTONES` positive
+/// amounts adding up to exactly `total`.
+pub fn validate_milestones(milestones: &Vec<i128>, total: i128) -> Result<(), ContractError> {
+    if milestones.is_empty() || milestones.len() > MAX_MILESTONES {
+        return Err(ContractError::InvalidMilestones);
+    }
+
+    let mut sum: i128 = 0;
+    for amount in milestones.iter() {
+        if amount <= 0 {
+            return Err(ContractError::InvalidMilestones);
+        }
+        sum = sum.checked_add(amount).ok_or(ContractError::Overflow)?;
+    }
+
+    if sum != total {
+        return Err(ContractError::InvalidMilestones);
+    }
+    Ok(())
+}
+
+/// Validates daily settlement windows: 1 to `MAX_SETTLEMENT_WINDOWS`
+/// non-empty windows with both bounds inside a day.
+pub fn validate_settlement_windows(windows: &Vec<SettlementWindow>) -> Result<(), ContractError> {
+    if windows.is_empty() || windows.len() > MAX_SETTLEMENT_WINDOWS {
+        return Err(ContractError::InvalidSettlementWindow);
+    }
+
+    for window in windows.iter() {
+        if window.start_seconds >= 86_400
+            || window.end_seconds >= 86_400
+            || window.start_seconds == window.end_seconds
+        {
+            return Err(ContractError::InvalidSettlementWindow);
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
+    use soroban_sdk::{symbol_short, testutils::Address as _, vec, Env};
 
     #[test]
     fn test_validate_valid_address() {
@@ -30,4 +127,57 @@ mod tests {
 
         assert!(validate_address(&address).is_ok());
     }
+
+    #[test]
+    fn test_validate_tags() {
+        let env = Env::default();
+
+        assert!(validate_tags(&vec![&env]).is_ok());
+        assert!(validate_tags(&vec![&env, symbol_short!("payroll"), symbol_short!("family")]).is_ok());
+
+        let duplicated = vec![&env, symbol_short!("b2b"), symbol_short!("b2b")];
+        assert_eq!(validate_tags(&duplicated), Err(ContractError::InvalidTags));
+
+        let too_many = vec![
+            &env,
+            symbol_short!("a"),
+            symbol_short!("b"),
+            symbol_short!("c"),
+            symbol_short!("d"),
+            symbol_short!("e"),
+            symbol_short!("f"),
+        ];
+        assert_eq!(validate_tags(&too_many), Err(ContractError::InvalidTags));
+    }
+
+    #[test]
+    fn test_normalize_corridor_codes() {
+        let env = Env::default();
+
+        assert_eq!(
+            normalize_currency(&env, &String::from_str(&env, "usd")),
+            Ok(String::from_str(&env, "USD"))
+        );
+        assert_eq!(
+            normalize_country(&env, &String::from_str(&env, "Ng")),
+            Ok(String::from_str(&env, "NG"))
+        );
+
+        assert_eq!(
+            normalize_currency(&env, &String::from_str(&env, "USDC")),
+            Err(ContractError::InvalidCurrency)
+        );
+        assert_eq!(
+            normalize_currency(&env, &String::from_str(&env, "US1")),
+            Err(ContractError::InvalidCurrency)
+        );
+        assert_eq!(
+            normalize_country(&env, &String::from_str(&env, "NGA")),
+            Err(ContractError::InvalidCountry)
+        );
+        assert_eq!(
+            normalize_country(&env, &String::from_str(&env, "N ")),
+            Err(ContractError::InvalidCountry)
+        );
+    }
 }